@@ -0,0 +1,27 @@
+//! Lists the sources of the generator for `drift::input_hash`, so that changing the generator
+//! changes the hash of its output as changing the templates does.
+
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    let dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    let mut sources: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "rs"))
+        .collect();
+    sources.sort();
+
+    let mut list = String::from("&[\n");
+    for path in &sources {
+        let name = path.file_name().unwrap().to_string_lossy();
+        list.push_str(&format!("    ({:?}, include_str!({:?})),\n", name, path));
+    }
+    list.push_str("]\n");
+    fs::write(
+        Path::new(&env::var("OUT_DIR").unwrap()).join("sources.rs"),
+        list,
+    )
+    .unwrap();
+}
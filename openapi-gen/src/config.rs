@@ -0,0 +1,131 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{Map, Value};
+
+/// Names of the `rustfmt` configuration files, as `rustfmt` looks for them in a directory.
+const RUSTFMT_CONFIGS: [&str; 2] = ["rustfmt.toml", ".rustfmt.toml"];
+
+/// Supporting files of the rust-axum generator, by template, rendered unless ignored.
+const SUPPORTING_FILES: [(&str, &str); 8] = [
+    ("Cargo.mustache", "Cargo.toml"),
+    ("README.mustache", "README.md"),
+    ("gitignore", ".gitignore"),
+    ("header.mustache", "src/header.rs"),
+    ("lib.mustache", "src/lib.rs"),
+    ("models.mustache", "src/models.rs"),
+    ("server-mod.mustache", "src/server/mod.rs"),
+    ("types.mustache", "src/types.rs"),
+];
+
+/// Generator configuration, read from the same files as `openapi-generator-cli`, e.g.
+/// `generator.yaml`.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub input_spec: PathBuf,
+    pub output_dir: PathBuf,
+    pub template_dir: PathBuf,
+    /// Options of the templates, e.g. `versionModule`.
+    pub additional_properties: Map<String, Value>,
+    /// Templates and the paths they are rendered to, relative to `output_dir`.
+    pub files: Vec<(String, String)>,
+    /// The `rustfmt.toml` or `.rustfmt.toml` in the directory of the configuration or the
+    /// nearest of its parents, which the Rust files are formatted with, as `cargo fmt` would.
+    pub rustfmt_config: Option<PathBuf>,
+}
+
+impl Config {
+    /// Read the configuration at `path`, whose paths are relative to its directory.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let yaml = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        Self::from_yaml(&yaml, dir).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Parse a configuration, whose paths are relative to `dir`.
+    pub fn from_yaml(yaml: &str, dir: &Path) -> Result<Self, String> {
+        let config: Value = serde_yaml::from_str(yaml).map_err(|e| e.to_string())?;
+        let path = |key: &str| {
+            config[key]
+                .as_str()
+                .map(|value| dir.join(value))
+                .ok_or_else(|| format!("`{}` is missing", key))
+        };
+        match config["generatorName"].as_str() {
+            Some("rust-axum") => {}
+            other => return Err(format!("unsupported generator {:?}", other)),
+        }
+        let output_dir = path("outputDir")?;
+
+        let ignored = match config["ignoreFileOverride"].as_str() {
+            Some(ignore_file) => read_ignore_file(&dir.join(ignore_file))?,
+            None => read_ignore_file(&output_dir.join(".openapi-generator-ignore"))?,
+        };
+        let mut files: Vec<(String, String)> = SUPPORTING_FILES
+            .iter()
+            .map(|(template, destination)| (template.to_string(), destination.to_string()))
+            .collect();
+        for (template, file) in config["files"].as_object().into_iter().flatten() {
+            let destination = file["destinationFilename"]
+                .as_str()
+                .ok_or_else(|| format!("`destinationFilename` of {} is missing", template))?;
+            files.push((template.clone(), destination.to_string()));
+        }
+        files.retain(|(_, destination)| !ignored.iter().any(|p| is_ignored(p, destination)));
+
+        Ok(Config {
+            input_spec: path("inputSpec")?,
+            output_dir,
+            template_dir: path("templateDir")?,
+            additional_properties: config["additionalProperties"]
+                .as_object()
+                .cloned()
+                .unwrap_or_default(),
+            files,
+            rustfmt_config: find_rustfmt_config(dir),
+        })
+    }
+}
+
+/// The `rustfmt` configuration file of `dir`, or of the nearest of its parents which has one.
+fn find_rustfmt_config(dir: &Path) -> Option<PathBuf> {
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    let dir = fs::canonicalize(dir).ok()?;
+    dir.ancestors().find_map(|dir| {
+        RUSTFMT_CONFIGS
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    })
+}
+
+/// Patterns of an `.openapi-generator-ignore` file, if there is one.
+fn read_ignore_file(path: &Path) -> Result<Vec<String>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let ignore = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(ignore
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Whether `pattern` of an ignore file matches the output file `path`. Only file names,
+/// directories (`dir/**`) and files anchored to the output directory (`/file`) are supported.
+fn is_ignored(pattern: &str, path: &str) -> bool {
+    let anchored = pattern.starts_with('/');
+    let pattern = pattern.trim_start_matches('/');
+    if let Some(dir) = pattern.strip_suffix("/**") {
+        return path.starts_with(&format!("{}/", dir));
+    }
+    path == pattern || (!anchored && path.rsplit('/').next() == Some(pattern))
+}
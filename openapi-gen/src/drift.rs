@@ -0,0 +1,247 @@
+//! Whether the checked-in output of a configuration is what it generates: the hash of the
+//! inputs embedded in the output tells a document or templates changed without regenerating
+//! from hand-edited files, and a diff shows what differs.
+
+use std::{fmt, fs, path::PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::{Config, File};
+
+/// Name of the constant the hash of the inputs is embedded as, by `lib.mustache`.
+pub const HASH_CONSTANT: &str = "GENERATOR_INPUT_HASH";
+
+/// Lines of context around the changes of a diff.
+const CONTEXT: usize = 3;
+
+/// Names and contents of the sources of the generator, as listed by its build script.
+const SOURCES: &[(&str, &str)] = include!(concat!(env!("OUT_DIR"), "/sources.rs"));
+
+/// Why checked-in files differ from the generated ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cause {
+    /// The document, templates, options, generator or `rustfmt` configuration changed since the
+    /// files were generated.
+    Stale,
+    /// The files were changed after they were generated, or a `rustfmt` which formats otherwise
+    /// formatted them.
+    Edited,
+}
+
+/// Checked-in files of a configuration which differ from the generated ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Drift {
+    pub cause: Cause,
+    /// The document of the configuration.
+    pub input_spec: PathBuf,
+    /// Unified diffs from the checked-in to the generated files, one per file.
+    pub diffs: Vec<String>,
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.cause {
+            Cause::Stale => writeln!(
+                f,
+                "{}, the templates, the generator or rustfmt.toml changed since the files were \
+                 generated, run `cargo run -p openapi-gen` and commit the changes:",
+                self.input_spec.display()
+            )?,
+            Cause::Edited => writeln!(
+                f,
+                "generated files were edited, change the templates or {} instead \
+                 and run `cargo run -p openapi-gen` (or rustfmt formats differently here):",
+                self.input_spec.display()
+            )?,
+        }
+        for diff in &self.diffs {
+            write!(f, "{}", diff)?;
+        }
+        Ok(())
+    }
+}
+
+/// SHA-256 of what the files of `config` are generated from: the document `yaml`, the
+/// templates, the options, the generator with its sources, and the edition and configuration
+/// `rustfmt` formats its output with.
+pub fn input_hash(config: &Config, yaml: &str) -> Result<String, String> {
+    let mut templates = Vec::new();
+    let dir = &config.template_dir;
+    for entry in fs::read_dir(dir).map_err(|e| format!("{}: {}", dir.display(), e))? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        // as `Templates::from_dir`, without the documentation of the templates
+        if path.is_file() && path.extension().is_none_or(|extension| extension != "md") {
+            let name = path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            let source = fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            templates.push((name, source));
+        }
+    }
+    templates.sort();
+
+    let mut hasher = Sha256::new();
+    // each input is followed by a NUL, so moving text from one to the next changes the hash
+    hasher.update(yaml);
+    hasher.update([0]);
+    for (name, source) in &templates {
+        hasher.update(name);
+        hasher.update([0]);
+        hasher.update(source);
+        hasher.update([0]);
+    }
+    hasher.update(serde_json::to_string(&config.additional_properties).unwrap_or_default());
+    hasher.update([0]);
+    hasher.update(crate::generator_version());
+    hasher.update([0]);
+    // how rustfmt formats, not its version: a toolchain formatting the same way leaves the output
+    // as it is, and any change to its output shows in the diff
+    hasher.update(crate::RUSTFMT_ARGS.join(" "));
+    hasher.update([0]);
+    if let Some(path) = &config.rustfmt_config {
+        hasher.update(fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?);
+    }
+    hasher.update([0]);
+    for (name, source) in SOURCES {
+        hasher.update(name);
+        hasher.update([0]);
+        hasher.update(source);
+        hasher.update([0]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Compare the files of `config` with the generated ones, without writing them.
+pub fn check(config: &Config) -> Result<Option<Drift>, String> {
+    let yaml = fs::read_to_string(&config.input_spec)
+        .map_err(|e| format!("{}: {}", config.input_spec.display(), e))?;
+    let hash = input_hash(config, &yaml)?;
+    let files = crate::generate(config)?;
+
+    let mut embedded = None;
+    let mut diffs = Vec::new();
+    for File { path, contents } in &files {
+        let checked_in = match fs::read_to_string(path) {
+            Ok(checked_in) => checked_in,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        embedded = embedded.or_else(|| embedded_hash(&checked_in));
+        if checked_in != *contents {
+            diffs.push(unified_diff(
+                &path.display().to_string(),
+                &checked_in,
+                contents,
+            ));
+        }
+    }
+
+    if diffs.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Drift {
+        cause: if embedded.as_deref() == Some(hash.as_str()) {
+            Cause::Edited
+        } else {
+            Cause::Stale
+        },
+        input_spec: config.input_spec.clone(),
+        diffs,
+    }))
+}
+
+/// The hash of the inputs embedded in `source`, if it has one.
+fn embedded_hash(source: &str) -> Option<String> {
+    let start = source.find(HASH_CONSTANT)?;
+    let value = &source[start..];
+    let value = &value[value.find('"')? + 1..];
+    Some(value[..value.find('"')?].to_string())
+}
+
+/// Unified diff of the lines of `path` from `old` to `new`.
+fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // only the lines between the common prefix and suffix are compared
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (a, b) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+
+    // longest common subsequences of the suffixes of `a` and `b`
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    // edits as (line of old, line of new, tag), with the common lines around them
+    let mut edits = Vec::new();
+    for i in prefix.saturating_sub(CONTEXT)..prefix {
+        edits.push((i, i, ' '));
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            edits.push((prefix + i, prefix + j, ' '));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            edits.push((prefix + i, prefix + j, '-'));
+            i += 1;
+        } else {
+            edits.push((prefix + i, prefix + j, '+'));
+            j += 1;
+        }
+    }
+    for k in 0..suffix.min(CONTEXT) {
+        edits.push((old.len() - suffix + k, new.len() - suffix + k, ' '));
+    }
+
+    let mut diff = format!("--- {}\n+++ {} (generated)\n", path, path);
+    let changed: Vec<usize> = (0..edits.len()).filter(|&k| edits[k].2 != ' ').collect();
+    let mut k = 0;
+    while k < changed.len() {
+        // a hunk spans the changes less than two contexts apart
+        let mut end = k;
+        while end + 1 < changed.len() && changed[end + 1] - changed[end] <= 2 * CONTEXT {
+            end += 1;
+        }
+        let first = changed[k].saturating_sub(CONTEXT);
+        let last = (changed[end] + CONTEXT).min(edits.len() - 1);
+        let hunk = &edits[first..=last];
+        let old_lines = hunk.iter().filter(|edit| edit.2 != '+').count();
+        let new_lines = hunk.iter().filter(|edit| edit.2 != '-').count();
+        diff.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            hunk[0].0 + 1,
+            old_lines,
+            hunk[0].1 + 1,
+            new_lines
+        ));
+        for &(i, j, tag) in hunk {
+            let line = if tag == '+' { new[j] } else { old[i] };
+            diff.push_str(&format!("{}{}\n", tag, line));
+        }
+        k = end + 1;
+    }
+    diff
+}
@@ -0,0 +1,193 @@
+//! Generates the `petstore-api` crate from the OpenAPI document with the templates of
+//! `templates`, as `openapi-generator-cli generate` with its rust-axum generator does, without
+//! Node or Java.
+//!
+//! The configurations are those of `openapi-generator-cli` ([`Config`]), the templates are
+//! rendered with the variables it would give them ([`mustache`], [`operations`], [`models`]),
+//! and the Rust files are formatted with `rustfmt` and the configuration of the workspace, so
+//! the output is the same. The output
+//! embeds a hash of its inputs, which [`drift::check`] compares to tell stale output from
+//! hand-edited output.
+
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use serde_json::{json, Value as Json};
+
+pub mod config;
+pub mod drift;
+pub mod models;
+pub mod mustache;
+pub mod naming;
+pub mod operations;
+pub mod spec;
+
+pub use config::Config;
+pub use spec::Spec;
+
+/// `description` of the crate if the document has none, as openapi-generator's.
+const DEFAULT_DESCRIPTION: &str =
+    "No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)";
+
+/// A rendered file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct File {
+    /// Path of the file, under the output directory of the configuration.
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+/// Render the files of `config`.
+pub fn generate(config: &Config) -> Result<Vec<File>, String> {
+    let yaml = fs::read_to_string(&config.input_spec)
+        .map_err(|e| format!("{}: {}", config.input_spec.display(), e))?;
+    let spec =
+        Spec::from_yaml(&yaml).map_err(|e| format!("{}: {}", config.input_spec.display(), e))?;
+    let context = context(config, &spec, &yaml)
+        .map_err(|e| format!("{}: {}", config.input_spec.display(), e))?;
+    let templates = mustache::Templates::from_dir(&config.template_dir)?;
+
+    let mut files = Vec::new();
+    for (template, destination) in &config.files {
+        let mut contents = templates.render(template, &context)?;
+        if destination.ends_with(".rs") {
+            contents = rustfmt(&contents, config.rustfmt_config.as_deref())
+                .map_err(|e| format!("{}: {}", destination, e))?;
+            contents = drop_blank_lines_before_braces(&contents);
+        }
+        files.push(File {
+            path: config.output_dir.join(destination),
+            contents,
+        });
+    }
+    Ok(files)
+}
+
+/// Write the files of `config`, returning their paths.
+pub fn write(config: &Config) -> Result<Vec<PathBuf>, String> {
+    let files = generate(config)?;
+    for file in &files {
+        if let Some(dir) = file.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        fs::write(&file.path, &file.contents)
+            .map_err(|e| format!("{}: {}", file.path.display(), e))?;
+    }
+    Ok(files.into_iter().map(|file| file.path).collect())
+}
+
+/// The variables of the templates for the document `spec`, whose text is `yaml`.
+pub fn context(config: &Config, spec: &Spec, yaml: &str) -> Result<Json, String> {
+    let info = &spec.doc["info"];
+    let version = info["version"].as_str().unwrap_or("1.0.0");
+    let description = info["description"].as_str().unwrap_or(DEFAULT_DESCRIPTION);
+    let package_name = config
+        .additional_properties
+        .get("packageName")
+        .and_then(Json::as_str)
+        .unwrap_or("openapi");
+    let server = spec.doc["servers"][0]["url"].as_str().unwrap_or("/");
+    let base_path = server
+        .split_once("://")
+        .map_or(server, |(_, rest)| {
+            rest.find('/').map_or("", |i| &rest[i..])
+        })
+        .trim_end_matches('/');
+
+    let mut context = json!({
+        "packageName": package_name,
+        "packageVersion": version,
+        "externCrateName": package_name.replace('-', "_"),
+        "appName": info["title"].as_str(),
+        "appVersion": version,
+        "appDescription": description,
+        "appDescriptionWithNewLines": description,
+        "licenseInfo": info["license"]["name"].as_str(),
+        "infoUrl": info["contact"]["url"].as_str(),
+        "infoEmail": info["contact"]["email"].as_str(),
+        "basePathWithoutHost": base_path,
+        "apiInfo": operations::api_info(spec)?,
+        "pathMethodOps": operations::path_method_ops(spec)?,
+        "models": models::models(spec)?,
+        "openapi-yaml": yaml,
+        "generatorInputHash": drift::input_hash(config, yaml)?,
+        "generatorVersion": generator_version(),
+    });
+    if let Json::Object(context) = &mut context {
+        for (key, value) in &config.additional_properties {
+            context.insert(key.clone(), value.clone());
+        }
+    }
+    Ok(context)
+}
+
+/// Version of the generator, e.g. `openapi-gen 1.0.0`.
+pub fn generator_version() -> String {
+    format!("openapi-gen {}", env!("CARGO_PKG_VERSION"))
+}
+
+/// Arguments `rustfmt` formats the Rust files with, besides the configuration file.
+pub const RUSTFMT_ARGS: [&str; 5] = ["--edition", "2021", "--emit", "stdout", "--quiet"];
+
+/// `rustfmt`, or the command in `RUSTFMT`.
+fn rustfmt_command() -> String {
+    std::env::var("RUSTFMT").unwrap_or_else(|_| "rustfmt".to_string())
+}
+
+/// `source` formatted by `rustfmt`, or by the command in `RUSTFMT`, with the configuration file
+/// `rustfmt_config`.
+fn rustfmt(source: &str, rustfmt_config: Option<&Path>) -> Result<String, String> {
+    let rustfmt = rustfmt_command();
+    let mut command = Command::new(&rustfmt);
+    command.args(RUSTFMT_ARGS);
+    if let Some(path) = rustfmt_config {
+        command.arg("--config-path").arg(path);
+    }
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("{}: {}", rustfmt, e))?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let source = source.to_string();
+    // written from a thread, as rustfmt may fill its output pipe before reading all its input
+    let writer = std::thread::spawn(move || stdin.write_all(source.as_bytes()));
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    writer
+        .join()
+        .expect("writing to rustfmt panicked")
+        .map_err(|e| format!("{}: {}", rustfmt, e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed: {}",
+            rustfmt,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    String::from_utf8(output.stdout).map_err(|e| e.to_string())
+}
+
+/// `source` without the blank lines before closing braces, which rustfmt drops everywhere but
+/// in the macro bodies it leaves as they are, such as the `proptest!` block of the model tests.
+fn drop_blank_lines_before_braces(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut blank = Vec::new();
+    for line in source.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            blank.push(line);
+            continue;
+        }
+        if !line.trim_start().starts_with('}') {
+            out.extend(blank.iter().copied());
+        }
+        blank.clear();
+        out.push_str(line);
+    }
+    out.extend(blank);
+    out
+}
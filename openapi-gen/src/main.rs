@@ -0,0 +1,65 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::Parser;
+
+use openapi_gen::{drift, Config};
+
+/// Generates `petstore-api` from the OpenAPI documents and the templates, as
+/// `openapi-generator-cli generate` does.
+///
+/// With `--check`, exits with 1 if the checked-in files differ from the generated ones, and 2
+/// if they can't be generated.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    /// Generator configurations, rendered in order.
+    #[arg(default_values = ["generator.yaml", "generator-v2.yaml"])]
+    configs: Vec<PathBuf>,
+    /// Don't write the files, report how the checked-in ones differ from them.
+    #[arg(long)]
+    check: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let mut drifted = false;
+    for path in &cli.configs {
+        let config = match Config::from_file(path) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return ExitCode::from(2);
+            }
+        };
+        if cli.check {
+            match drift::check(&config) {
+                Ok(Some(drift)) => {
+                    print!("{}", drift);
+                    drifted = true;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    return ExitCode::from(2);
+                }
+            }
+            continue;
+        }
+        match openapi_gen::write(&config) {
+            Ok(files) => {
+                for file in files {
+                    println!("{}", file.display());
+                }
+            }
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return ExitCode::from(2);
+            }
+        }
+    }
+    if drifted {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
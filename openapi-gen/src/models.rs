@@ -0,0 +1,193 @@
+//! Template variables of the models of the document, as those of a `CodegenModel` of
+//! openapi-generator.
+
+use serde_json::{json, Value as Json};
+use serde_yaml::Value;
+
+use crate::{
+    naming,
+    operations::{extensions, required_names},
+    spec::{scalars, Spec},
+};
+
+/// `models` of the templates: the schemas of `components/schemas` generated as models, in the
+/// order of their names.
+pub fn models(spec: &Spec) -> Result<Json, String> {
+    let mut names: Vec<&str> = spec
+        .schemas()
+        .into_iter()
+        .map(|(name, _)| name)
+        .filter(|name| spec.is_model(name))
+        .collect();
+    names.sort_unstable();
+
+    let mut models = Vec::new();
+    for name in names {
+        let model = model_context(spec, name).map_err(|e| format!("schema {}: {}", name, e))?;
+        models.push(json!({ "model": model }));
+    }
+    Ok(Json::from(models))
+}
+
+fn model_context(spec: &Spec, name: &str) -> Result<Json, String> {
+    let schema = &spec.doc["components"]["schemas"][name];
+    let classname = naming::camel_case(name);
+
+    let vars = vars(spec, schema)?;
+
+    let allowable_values = match schema["enum"].as_sequence() {
+        Some(values) => {
+            let values = scalars(values);
+            let enum_vars: Vec<Json> = values
+                .iter()
+                .map(|value| json!({ "name": naming::camel_case(value), "value": format!("{:?}", value) }))
+                .collect();
+            json!({ "values": values, "enumVars": enum_vars })
+        }
+        None => Json::Null,
+    };
+
+    let (additional_properties_type, additional_properties) = match &schema["additionalProperties"]
+    {
+        Value::Bool(true) => (
+            Json::from("crate::types::Object"),
+            json!({ "isModel": false }),
+        ),
+        value if value.is_mapping() && !vars.is_empty() => (
+            Json::from(spec.data_type(value)?),
+            json!({ "isModel": spec.model_name(value).is_some() }),
+        ),
+        _ => (Json::Null, Json::Null),
+    };
+
+    let composed = |key: &str| -> Result<(Vec<String>, Vec<Json>), String> {
+        let mut names = Vec::new();
+        let mut schemas = Vec::new();
+        for schema in schema[key].as_sequence().into_iter().flatten() {
+            let data_type = spec.data_type(schema)?;
+            let base_type = data_type.trim_start_matches("models::").to_string();
+            names.push(base_type.clone());
+            schemas.push(json!({
+                "dataType": data_type,
+                "baseType": base_type,
+                "isModel": spec.model_name(schema).is_some(),
+            }));
+        }
+        Ok((names, schemas))
+    };
+    let (any_of, any_of_schemas) = composed("anyOf")?;
+    let (one_of, one_of_schemas) = composed("oneOf")?;
+
+    let discriminator = match schema["discriminator"]["propertyName"].as_str() {
+        Some(property) => {
+            let mut mapped_models = Vec::new();
+            for (mapping_name, reference) in schema["discriminator"]["mapping"]
+                .as_mapping()
+                .into_iter()
+                .flatten()
+            {
+                let (Some(mapping_name), Some(reference)) =
+                    (mapping_name.as_str(), reference.as_str())
+                else {
+                    continue;
+                };
+                let model = reference.rsplit('/').next().unwrap_or(reference);
+                mapped_models.push(json!({
+                    "mappingName": mapping_name,
+                    "modelName": naming::camel_case(model),
+                }));
+            }
+            json!({
+                "propertyBaseName": property,
+                // adjacently tagged, with the variant in this property rather than next to the
+                // discriminator
+                "contentBaseName": schema["x-discriminator-content"].as_str(),
+                "mappedModels": mapped_models,
+            })
+        }
+        None => Json::Null,
+    };
+
+    let mut vendor_extensions = extensions(schema);
+    let query_encodable = !schema["enum"].is_sequence()
+        && any_of.is_empty()
+        && one_of.is_empty()
+        && additional_properties_type.is_null()
+        && vars.iter().all(is_query_scalar);
+    vendor_extensions.insert("x-query-encodable".to_string(), Json::from(query_encodable));
+
+    let xml = &schema["xml"];
+    Ok(json!({
+        "classname": classname,
+        "name": name,
+        "description": schema["description"].as_str(),
+        "isEnum": schema["enum"].is_sequence(),
+        "allowableValues": allowable_values,
+        "dataType": Json::Null,
+        "isMap": false,
+        "arrayModelType": Json::Null,
+        "additionalPropertiesType": additional_properties_type,
+        "additionalProperties": additional_properties,
+        "anyOf": any_of,
+        "oneOf": one_of,
+        "composedSchemas": { "anyOf": any_of_schemas, "oneOf": one_of_schemas },
+        "discriminator": discriminator,
+        "vars": vars,
+        "xmlName": xml["name"].as_str(),
+        "xmlNamespace": xml["namespace"].as_str(),
+        "xmlPrefix": xml["prefix"].as_str(),
+        "vendorExtensions": vendor_extensions,
+    }))
+}
+
+/// Whether a property has a query string representation which decodes to the same value:
+/// neither nested nor `null`, which a query string can't tell from a missing parameter.
+fn is_query_scalar(var: &Json) -> bool {
+    [
+        "isNullable",
+        "isModel",
+        "isArray",
+        "isMap",
+        "isFreeFormObject",
+        "isFile",
+    ]
+    .iter()
+    .all(|flag| var[flag] != true)
+}
+
+/// The properties of an object schema, with those of the parts of an `allOf` merged in order.
+fn vars(spec: &Spec, schema: &Value) -> Result<Vec<Json>, String> {
+    let mut parts = vec![schema];
+    for part in schema["allOf"].as_sequence().into_iter().flatten() {
+        parts.push(spec.resolve(part));
+    }
+    let required: Vec<&str> = parts.iter().flat_map(|part| required_names(part)).collect();
+
+    let mut vars: Vec<Json> = Vec::new();
+    for part in &parts[1..] {
+        add_vars(spec, part, &required, &mut vars)?;
+    }
+    add_vars(spec, schema, &required, &mut vars)?;
+    Ok(vars)
+}
+
+fn add_vars(
+    spec: &Spec,
+    schema: &Value,
+    required: &[&str],
+    vars: &mut Vec<Json>,
+) -> Result<(), String> {
+    for (name, property) in schema["properties"].as_mapping().into_iter().flatten() {
+        let Some(name) = name.as_str() else {
+            continue;
+        };
+        let var = spec
+            .property(property, name, required.contains(&name))
+            .map_err(|e| format!("property {}: {}", name, e))?;
+        match vars.iter_mut().find(|var| var["baseName"] == name) {
+            Some(existing) => *existing = Json::Object(var),
+            None => vars.push(Json::Object(var)),
+        }
+    }
+    Ok(())
+}
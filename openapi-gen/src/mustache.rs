@@ -0,0 +1,330 @@
+//! Mustache templates, rendered as openapi-generator's jmustache does: missing names render
+//! empty, names are looked up through the enclosing sections, lines holding only a section,
+//! partial or comment tag are dropped, and `{{x}}` is HTML escaped.
+//!
+//! Beyond the spec, list sections define `-first`, `-last` and `-index`, `list.size` is the
+//! length of a list, and `lambda.*` sections transform their rendered content.
+
+use std::{borrow::Cow, collections::HashMap, fs, path::Path};
+
+use serde_json::Value;
+
+use crate::naming;
+
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Variable {
+        name: String,
+        escape: bool,
+    },
+    Section {
+        name: String,
+        inverted: bool,
+        children: Vec<Node>,
+    },
+    Partial(String),
+}
+
+/// A parsed template.
+#[derive(Debug)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+/// The templates of a directory, by file name.
+#[derive(Debug, Default)]
+pub struct Templates {
+    templates: HashMap<String, Template>,
+}
+
+struct Frame<'a> {
+    value: Cow<'a, Value>,
+    /// Index and length of the list the value is an item of.
+    item: Option<(usize, usize)>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut sections: Vec<(String, bool, Vec<Node>)> = Vec::new();
+        let mut nodes = Vec::new();
+        let mut pos = 0;
+
+        while let Some(offset) = source[pos..].find("{{") {
+            let start = pos + offset;
+            let (content, end, unescaped) = if source[start..].starts_with("{{{") {
+                let close = source[start..]
+                    .find("}}}")
+                    .ok_or_else(|| format!("unclosed tag at byte {}", start))?;
+                (&source[start + 3..start + close], start + close + 3, true)
+            } else {
+                let close = source[start..]
+                    .find("}}")
+                    .ok_or_else(|| format!("unclosed tag at byte {}", start))?;
+                (&source[start + 2..start + close], start + close + 2, false)
+            };
+            let sigil = if unescaped {
+                None
+            } else {
+                content.chars().next().filter(|c| "#^/>!&".contains(*c))
+            };
+            let name = match sigil {
+                Some(_) => content[1..].trim(),
+                None => content.trim(),
+            };
+
+            // a line with nothing but this tag is dropped with the tag
+            let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+            let line_end = source[end..]
+                .find('\n')
+                .map_or(source.len(), |i| end + i + 1);
+            let standalone = matches!(sigil, Some('#' | '^' | '/' | '!'))
+                && line_start >= pos
+                && source[line_start..start]
+                    .trim_matches([' ', '\t'])
+                    .is_empty()
+                && source[end..line_end].trim().is_empty();
+            let (text_end, next) = if standalone {
+                (line_start, line_end)
+            } else {
+                (start, end)
+            };
+            if text_end > pos {
+                nodes.push(Node::Text(source[pos..text_end].to_string()));
+            }
+            pos = next;
+
+            match sigil {
+                Some('#') | Some('^') => {
+                    sections.push((name.to_string(), sigil == Some('^'), nodes));
+                    nodes = Vec::new();
+                }
+                Some('/') => {
+                    let (open, inverted, parent) = sections
+                        .pop()
+                        .ok_or_else(|| format!("unopened section {}", name))?;
+                    if open != name {
+                        return Err(format!("section {} closed by {}", open, name));
+                    }
+                    let children = std::mem::replace(&mut nodes, parent);
+                    nodes.push(Node::Section {
+                        name: open,
+                        inverted,
+                        children,
+                    });
+                }
+                Some('>') => nodes.push(Node::Partial(name.to_string())),
+                Some('!') => {}
+                _ => nodes.push(Node::Variable {
+                    name: name.to_string(),
+                    escape: !unescaped && sigil != Some('&'),
+                }),
+            }
+        }
+        if let Some((open, _, _)) = sections.last() {
+            return Err(format!("unclosed section {}", open));
+        }
+        if pos < source.len() {
+            nodes.push(Node::Text(source[pos..].to_string()));
+        }
+        Ok(Template { nodes })
+    }
+}
+
+impl Templates {
+    /// Parse the templates of `dir`.
+    pub fn from_dir(dir: &Path) -> Result<Self, String> {
+        let mut templates = HashMap::new();
+        let entries = fs::read_dir(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        for entry in entries {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if !path.is_file() || name.ends_with(".md") {
+                continue;
+            }
+            let source =
+                fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            let template =
+                Template::parse(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+            templates.insert(name.to_string(), template);
+        }
+        Ok(Templates { templates })
+    }
+
+    /// Render the template file `name` with `context`.
+    pub fn render(&self, name: &str, context: &Value) -> Result<String, String> {
+        let template = self
+            .templates
+            .get(name)
+            .ok_or_else(|| format!("no template {}", name))?;
+        let mut stack = vec![Frame {
+            value: Cow::Borrowed(context),
+            item: None,
+        }];
+        let mut out = String::new();
+        self.render_nodes(&template.nodes, &mut stack, &mut out)
+            .map_err(|e| format!("{}: {}", name, e))?;
+        Ok(out)
+    }
+
+    fn render_nodes<'a>(
+        &self,
+        nodes: &[Node],
+        stack: &mut Vec<Frame<'a>>,
+        out: &mut String,
+    ) -> Result<(), String> {
+        for node in nodes {
+            match node {
+                Node::Text(text) => out.push_str(text),
+                Node::Variable { name, escape } => {
+                    let text = lookup(stack, name).map_or(String::new(), |value| text(&value));
+                    if *escape {
+                        escape_html(&text, out);
+                    } else {
+                        out.push_str(&text);
+                    }
+                }
+                Node::Partial(name) => {
+                    let template = self
+                        .templates
+                        .get(&format!("{}.mustache", name))
+                        .ok_or_else(|| format!("no partial {}", name))?;
+                    self.render_nodes(&template.nodes, stack, out)?;
+                }
+                Node::Section {
+                    name,
+                    inverted: false,
+                    children,
+                } if name.starts_with("lambda.") => {
+                    let mut inner = String::new();
+                    self.render_nodes(children, stack, &mut inner)?;
+                    out.push_str(&lambda(&name["lambda.".len()..], &inner)?);
+                }
+                Node::Section {
+                    name,
+                    inverted,
+                    children,
+                } => {
+                    let value = lookup(stack, name);
+                    let truthy = value.as_deref().is_some_and(is_truthy);
+                    if *inverted {
+                        if !truthy {
+                            self.render_nodes(children, stack, out)?;
+                        }
+                        continue;
+                    }
+                    let Some(value) = value.filter(|_| truthy) else {
+                        continue;
+                    };
+                    match value {
+                        Cow::Borrowed(Value::Array(items)) => {
+                            for (index, item) in items.iter().enumerate() {
+                                stack.push(Frame {
+                                    value: Cow::Borrowed(item),
+                                    item: Some((index, items.len())),
+                                });
+                                let result = self.render_nodes(children, stack, out);
+                                stack.pop();
+                                result?;
+                            }
+                        }
+                        Cow::Borrowed(Value::Bool(true)) | Cow::Owned(Value::Bool(true)) => {
+                            self.render_nodes(children, stack, out)?;
+                        }
+                        value => {
+                            stack.push(Frame { value, item: None });
+                            let result = self.render_nodes(children, stack, out);
+                            stack.pop();
+                            result?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The value of `name` in the innermost section which has it.
+fn lookup<'a>(stack: &[Frame<'a>], name: &str) -> Option<Cow<'a, Value>> {
+    if name == "." {
+        return stack.last().map(|frame| frame.value.clone());
+    }
+    if let Some(key) = name.strip_prefix('-') {
+        let (index, len) = stack.iter().rev().find_map(|frame| frame.item)?;
+        return match key {
+            "first" => Some(Cow::Owned(Value::Bool(index == 0))),
+            "last" => Some(Cow::Owned(Value::Bool(index + 1 == len))),
+            "index" => Some(Cow::Owned(Value::from(index))),
+            _ => None,
+        };
+    }
+
+    let mut keys = name.split('.');
+    let first = keys.next()?;
+    let mut value = stack.iter().rev().find_map(|frame| match &frame.value {
+        Cow::Borrowed(value) => value.get(first).map(Cow::Borrowed),
+        Cow::Owned(value) => value.get(first).cloned().map(Cow::Owned),
+    })?;
+    for key in keys {
+        value = match (value, key) {
+            (value, "size") if value.is_array() => {
+                Cow::Owned(Value::from(value.as_array().map_or(0, Vec::len)))
+            }
+            (Cow::Borrowed(value), key) => Cow::Borrowed(value.get(key)?),
+            (Cow::Owned(value), key) => Cow::Owned(value.get(key)?.clone()),
+        };
+    }
+    Some(value)
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64() != Some(0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(_) => true,
+    }
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Null | Value::Array(_) | Value::Object(_) => String::new(),
+    }
+}
+
+fn escape_html(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            '`' => out.push_str("&#x60;"),
+            '=' => out.push_str("&#x3D;"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// The lambdas of openapi-generator used by the templates.
+fn lambda(name: &str, text: &str) -> Result<String, String> {
+    match name {
+        "lowercase" => Ok(text.to_lowercase()),
+        "uppercase" => Ok(text.to_uppercase()),
+        "snakecase" => Ok(naming::snake_case(text)),
+        "camelcase" => Ok(naming::lower_camel_case(text)),
+        "titlecase" => Ok(text
+            .split(' ')
+            .map(naming::capitalize)
+            .collect::<Vec<_>>()
+            .join(" ")),
+        _ => Err(format!("no lambda {}", name)),
+    }
+}
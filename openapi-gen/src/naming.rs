@@ -0,0 +1,54 @@
+//! Names of the generated items, as openapi-generator derives them from the document.
+
+/// `text` with its first character in upper case.
+pub fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// `snake_case` of a name in any case, e.g. `pet_id` for `petId` and `idempotency_key` for
+/// `Idempotency-Key`.
+pub fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            snake.push('_');
+            continue;
+        }
+        if c.is_ascii_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(char::is_ascii_lowercase);
+            // `petId` and `HTTPServer`, but not `HTTP`
+            if previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    snake
+}
+
+/// `UpperCamelCase` of a name or phrase, e.g. `CreatePets` for `createPets` and
+/// `UnexpectedError` for `unexpected error`.
+pub fn camel_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .map(capitalize)
+        .collect()
+}
+
+/// `lowerCamelCase` of a name or phrase, e.g. `listPets` for `list_pets`.
+pub fn lower_camel_case(name: &str) -> String {
+    let camel = camel_case(name);
+    let mut chars = camel.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,424 @@
+//! Template variables of the operations of the document, as those of a `CodegenOperation` of
+//! openapi-generator with the vendor extensions of its rust-axum generator.
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Map, Value as Json};
+use serde_yaml::Value;
+
+use crate::{naming, spec::Spec};
+
+/// Keys of the operations of a path item and their methods.
+const METHODS: [(&str, &str); 8] = [
+    ("get", "GET"),
+    ("put", "PUT"),
+    ("post", "POST"),
+    ("delete", "DELETE"),
+    ("options", "OPTIONS"),
+    ("head", "HEAD"),
+    ("patch", "PATCH"),
+    ("trace", "TRACE"),
+];
+
+/// `apiInfo` of the templates: the operations grouped by their first tag, in the order of the
+/// tags and their `operationId`s.
+pub fn api_info(spec: &Spec) -> Result<Json, String> {
+    let mut apis: BTreeMap<String, Vec<Json>> = BTreeMap::new();
+    for (path, item) in paths(spec) {
+        for (key, method) in METHODS {
+            let operation = &item[key];
+            if operation.is_null() {
+                continue;
+            }
+            let tag = operation["tags"][0].as_str().unwrap_or("default");
+            let context = operation_context(spec, path, item, method, operation)
+                .map_err(|e| format!("{} {}: {}", method, path, e))?;
+            apis.entry(tag.to_string()).or_default().push(context);
+        }
+    }
+
+    let apis: Vec<Json> = apis
+        .into_values()
+        .map(|mut operations| {
+            operations.sort_by(|a, b| a["operationId"].as_str().cmp(&b["operationId"].as_str()));
+            json!({ "operations": { "operation": operations } })
+        })
+        .collect();
+    Ok(json!({ "apis": apis }))
+}
+
+/// `pathMethodOps` of the templates: the methods and handlers of each path, in axum syntax.
+pub fn path_method_ops(spec: &Spec) -> Result<Json, String> {
+    let mut paths_ops: BTreeMap<String, Vec<Json>> = BTreeMap::new();
+    for (path, item) in paths(spec) {
+        for (key, method) in METHODS {
+            let operation = &item[key];
+            if operation.is_null() {
+                continue;
+            }
+            let operation_id = operation["operationId"]
+                .as_str()
+                .ok_or_else(|| format!("{} {}: `operationId` is missing", method, path))?;
+            paths_ops
+                .entry(axum_path(path))
+                .or_default()
+                .push(json!({ "method": key, "operationID": naming::snake_case(operation_id) }));
+        }
+    }
+    Ok(paths_ops
+        .into_iter()
+        .map(|(path, operations)| json!({ "path": path, "methodOperations": operations }))
+        .collect())
+}
+
+fn paths(spec: &Spec) -> Vec<(&str, &Value)> {
+    spec.doc["paths"]
+        .as_mapping()
+        .into_iter()
+        .flatten()
+        .filter_map(|(path, item)| Some((path.as_str()?, spec.resolve(item))))
+        .collect()
+}
+
+/// `path` with axum's `:name` path parameters, e.g. `/pets/:pet_id` for `/pets/{petId}`.
+fn axum_path(path: &str) -> String {
+    let mut axum = String::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        axum.push_str(&rest[..start]);
+        axum.push(':');
+        axum.push_str(&naming::snake_case(&rest[start + 1..start + end]));
+        rest = &rest[start + end + 1..];
+    }
+    axum.push_str(rest);
+    axum
+}
+
+fn operation_context(
+    spec: &Spec,
+    path: &str,
+    item: &Value,
+    method: &str,
+    operation: &Value,
+) -> Result<Json, String> {
+    let operation_id = operation["operationId"]
+        .as_str()
+        .ok_or("`operationId` is missing")?;
+    let camel_id = naming::camel_case(operation_id);
+
+    let mut params: BTreeMap<&str, Vec<Json>> = BTreeMap::new();
+    for parameter in parameters(spec, item, operation) {
+        let name = parameter["name"]
+            .as_str()
+            .ok_or("parameter without `name`")?;
+        let location = parameter["in"].as_str().unwrap_or_default();
+        let required = parameter["required"]
+            .as_bool()
+            .unwrap_or(location == "path");
+        let mut param = spec
+            .property(&parameter["schema"], name, required)
+            .map_err(|e| format!("parameter {}: {}", name, e))?;
+        if let Some(description) = parameter["description"].as_str() {
+            param.insert("description".to_string(), Json::from(description));
+        }
+        // header and cookie values can't be null, so their fields are never `Nullable`
+        if matches!(location, "header" | "cookie") {
+            param.insert("isNullable".to_string(), Json::from(false));
+        }
+        // the struct of the parameters of its location, named as the model of a property is, for
+        // the validators rendered for both
+        param.insert(
+            "classname".to_string(),
+            Json::from(format!(
+                "{}{}Params",
+                camel_id,
+                naming::camel_case(location)
+            )),
+        );
+        params
+            .entry(location)
+            .or_default()
+            .push(Json::Object(param));
+    }
+
+    let mut vendor_extensions = extensions(operation);
+    // rendered as the `usize` of a constant, so it can't be any other YAML value
+    if let Some(limit) = vendor_extensions.get("x-body-limit") {
+        if !limit.is_u64() {
+            return Err(format!(
+                "`x-body-limit` must be a non-negative integer, not {}",
+                limit
+            ));
+        }
+    }
+    vendor_extensions.insert(
+        "x-operation-id".to_string(),
+        Json::from(naming::snake_case(operation_id)),
+    );
+
+    let body = &spec.resolve(&operation["requestBody"])["content"];
+    let consumes: Vec<&str> = media_types(body);
+    let mut body_param = Json::Null;
+    let mut form_params = Vec::new();
+    if let Some(&media_type) = consumes.first() {
+        // the first media type decides how the body is read; `RequestBody` decodes the others
+        let kind = match media_type {
+            "multipart/form-data" => "multipart",
+            "multipart/related" => "multipart-related",
+            "application/x-www-form-urlencoded" => "form-urlencoded",
+            "text/plain" => "plain-text",
+            _ => "json",
+        };
+        vendor_extensions.insert(format!("x-consumes-{}", kind), Json::from(true));
+
+        let required = spec.resolve(&operation["requestBody"])["required"]
+            .as_bool()
+            .unwrap_or(false);
+        let schema = &body[media_type]["schema"];
+        if kind == "multipart" {
+            form_params = form_params_of(spec, schema, &body[media_type]["encoding"])?;
+        } else {
+            let mut param = spec
+                .property(schema, "body", required)
+                .map_err(|e| format!("request body: {}", e))?;
+            param.insert("description".to_string(), Json::Null);
+            body_param = Json::Object(param);
+        }
+    }
+
+    let mut produces: Vec<&str> = Vec::new();
+    let mut responses = Vec::new();
+    for (code, response) in operation["responses"].as_mapping().into_iter().flatten() {
+        let code = match code {
+            Value::String(code) => code.clone(),
+            Value::Number(code) => code.to_string(),
+            _ => continue,
+        };
+        let response = spec.resolve(response);
+        for media_type in media_types(&response["content"]) {
+            if !produces.contains(&media_type) {
+                produces.push(media_type);
+            }
+        }
+        responses.push(
+            response_context(spec, &code, response)
+                .map_err(|e| format!("response {}: {}", code, e))?,
+        );
+    }
+
+    let param_list = |location: &str| params.get(location).cloned().unwrap_or_default();
+    Ok(json!({
+        "operationId": camel_id,
+        "operationIdCamelCase": camel_id,
+        "nickname": naming::snake_case(operation_id),
+        "httpMethod": method,
+        "path": path,
+        "summary": operation["summary"].as_str(),
+        "notes": operation["description"].as_str(),
+        "isDeprecated": operation["deprecated"].as_bool().unwrap_or(false),
+        "headerParams": param_list("header"),
+        "cookieParams": param_list("cookie"),
+        "pathParams": param_list("path"),
+        "queryParams": param_list("query"),
+        "formParams": form_params,
+        "bodyParams": if body_param.is_null() { vec![] } else { vec![body_param.clone()] },
+        "bodyParam": body_param,
+        "hasConsumes": !consumes.is_empty(),
+        "consumes": consumes.iter().map(|m| json!({ "mediaType": m })).collect::<Vec<_>>(),
+        "hasProduces": !produces.is_empty(),
+        "produces": produces.iter().map(|m| json!({ "mediaType": m })).collect::<Vec<_>>(),
+        "responses": responses,
+        "vendorExtensions": vendor_extensions,
+    }))
+}
+
+/// Parameters of the operation and of its path item, resolved, the operation's overriding
+/// those of the path item with the same name and location.
+fn parameters<'a>(spec: &'a Spec, item: &'a Value, operation: &'a Value) -> Vec<&'a Value> {
+    let own: Vec<&Value> = operation["parameters"]
+        .as_sequence()
+        .into_iter()
+        .flatten()
+        .map(|parameter| spec.resolve(parameter))
+        .collect();
+    let inherited: Vec<&Value> = item["parameters"]
+        .as_sequence()
+        .into_iter()
+        .flatten()
+        .map(|parameter| spec.resolve(parameter))
+        .filter(|parameter| {
+            !own.iter()
+                .any(|own| own["name"] == parameter["name"] && own["in"] == parameter["in"])
+        })
+        .collect();
+    inherited.into_iter().chain(own).collect()
+}
+
+/// The properties of a `multipart/form-data` body, with the media types of their `encoding`.
+fn form_params_of(spec: &Spec, schema: &Value, encoding: &Value) -> Result<Vec<Json>, String> {
+    let schema = spec.resolve(schema);
+    let required = required_names(schema);
+    let mut params = Vec::new();
+    for (name, property) in schema["properties"].as_mapping().into_iter().flatten() {
+        let Some(name) = name.as_str() else {
+            continue;
+        };
+        let mut param = spec
+            .property(property, name, required.contains(&name))
+            .map_err(|e| format!("form property {}: {}", name, e))?;
+        if let Some(content_type) = encoding[name]["contentType"].as_str() {
+            // also on the items, for the parts of arrays
+            if let Some(Json::Object(items)) = param.get_mut("items") {
+                items.insert("contentType".to_string(), Json::from(content_type));
+            }
+            param.insert("contentType".to_string(), Json::from(content_type));
+        }
+        params.push(Json::Object(param));
+    }
+    Ok(params)
+}
+
+fn response_context(spec: &Spec, code: &str, response: &Value) -> Result<Json, String> {
+    let is_default = code == "default";
+    let message = response["description"].as_str().unwrap_or_default();
+    let code = if is_default { "0" } else { code };
+
+    let mut vendor_extensions = extensions(response);
+    vendor_extensions.insert(
+        "x-response-id".to_string(),
+        Json::from(format!("Status{}_{}", code, naming::camel_case(message))),
+    );
+
+    let mut headers = Vec::new();
+    for (name, header) in response["headers"].as_mapping().into_iter().flatten() {
+        let Some(name) = name.as_str() else {
+            continue;
+        };
+        let header = spec.resolve(header);
+        let required = header["required"].as_bool().unwrap_or(false);
+        let mut context = spec
+            .property(&header["schema"], name, required)
+            .map_err(|e| format!("header {}: {}", name, e))?;
+        context.insert(
+            "description".to_string(),
+            header["description"]
+                .as_str()
+                .map_or(Json::Null, Json::from),
+        );
+        headers.push(Json::Object(context));
+    }
+
+    let mut context = Map::new();
+    if let Some(&media_type) = media_types(&response["content"]).first() {
+        let schema = &response["content"][media_type]["schema"];
+        let property = spec.property(schema, "body", true)?;
+        let kind = if property["isFile"] == true || property["isByteArray"] == true {
+            "bytes"
+        } else {
+            match media_type {
+                "text/plain" => "plain-text",
+                "application/x-www-form-urlencoded" => "form-urlencoded",
+                _ => "json",
+            }
+        };
+        vendor_extensions.insert(format!("x-produces-{}", kind), Json::from(true));
+        vendor_extensions.insert("x-mime-type".to_string(), Json::from(media_type));
+        if property["isArray"] == true {
+            vendor_extensions.insert(
+                "x-xml-wrapper".to_string(),
+                Json::from(xml_wrapper(spec, &response["content"])),
+            );
+        }
+        for key in [
+            "dataType",
+            "isArray",
+            "isMap",
+            "isModel",
+            "items",
+            "minItems",
+            "maxItems",
+            "uniqueItems",
+        ] {
+            context.insert(key.to_string(), property[key].clone());
+        }
+    } else {
+        for key in ["dataType", "items", "minItems", "maxItems"] {
+            context.insert(key.to_string(), Json::Null);
+        }
+        for key in ["isArray", "isMap", "isModel", "uniqueItems"] {
+            context.insert(key.to_string(), Json::from(false));
+        }
+    }
+    context.insert("code".to_string(), Json::from(code));
+    context.insert("message".to_string(), Json::from(message));
+    context.insert("isDefault".to_string(), Json::from(is_default));
+    context.insert("headers".to_string(), Json::from(headers));
+    context.insert(
+        "vendorExtensions".to_string(),
+        Json::Object(vendor_extensions),
+    );
+    Ok(Json::Object(context))
+}
+
+/// Name of the element wrapping the items of an array body in XML: the `xml.name` of the schema
+/// of the XML media type of `content` (or of the first media type, if none is XML), else the name
+/// of the schema it refers to, else `items`.
+fn xml_wrapper(spec: &Spec, content: &Value) -> String {
+    let media_types = media_types(content);
+    let media_type = media_types
+        .iter()
+        .find(|media_type| {
+            let media_type = media_type.to_ascii_lowercase();
+            media_type == "application/xml"
+                || media_type == "text/xml"
+                || media_type.ends_with("+xml")
+        })
+        .or(media_types.first());
+    let schema = &content[media_type.copied().unwrap_or_default()]["schema"];
+    let reference = schema["$ref"].as_str().and_then(|reference| {
+        reference
+            .strip_prefix("#/components/schemas/")
+            .map(|name| name.to_string())
+    });
+    spec.resolve(schema)["xml"]["name"]
+        .as_str()
+        .map(str::to_string)
+        .or(reference)
+        .unwrap_or_else(|| "items".to_string())
+}
+
+/// Media types of a `content` object, in document order.
+fn media_types(content: &Value) -> Vec<&str> {
+    content
+        .as_mapping()
+        .into_iter()
+        .flatten()
+        .filter_map(|(media_type, _)| media_type.as_str())
+        .collect()
+}
+
+/// Names of the `required` properties of an object schema.
+pub fn required_names(schema: &Value) -> Vec<&str> {
+    schema["required"]
+        .as_sequence()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect()
+}
+
+/// The `x-` extensions of an object of the document, as template variables.
+pub fn extensions(value: &Value) -> Map<String, Json> {
+    value
+        .as_mapping()
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, value)| {
+            let key = key.as_str().filter(|key| key.starts_with("x-"))?;
+            Some((key.to_string(), serde_json::to_value(value).ok()?))
+        })
+        .collect()
+}
@@ -0,0 +1,288 @@
+//! The OpenAPI document, and the Rust types and template variables of its schemas.
+
+use serde_json::{json, Map, Value as Json};
+use serde_yaml::Value;
+
+use crate::naming;
+
+/// Words which can't be field names, escaped as raw identifiers.
+const KEYWORDS: [&str; 39] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "type", "where",
+];
+
+/// An OpenAPI document, kept in the order of its YAML so that the properties of schemas and the
+/// operations of paths are generated in document order.
+#[derive(Debug, Clone)]
+pub struct Spec {
+    pub doc: Value,
+}
+
+impl Spec {
+    pub fn from_yaml(yaml: &str) -> Result<Self, String> {
+        let doc: Value = serde_yaml::from_str(yaml).map_err(|e| e.to_string())?;
+        if !doc["paths"].is_mapping() {
+            return Err("not an OpenAPI document: `paths` is missing".to_string());
+        }
+        Ok(Spec { doc })
+    }
+
+    /// The value `value` refers to with a local `$ref`, or `value` itself. Unresolvable
+    /// references give `null`.
+    pub fn resolve<'a>(&'a self, mut value: &'a Value) -> &'a Value {
+        // bounded, so reference cycles end
+        for _ in 0..32 {
+            let Some(reference) = value["$ref"].as_str() else {
+                return value;
+            };
+            value = reference
+                .strip_prefix("#/")
+                .map_or(&Value::Null, |pointer| {
+                    pointer.split('/').fold(&self.doc, |value, key| {
+                        &value[key.replace("~1", "/").as_str()]
+                    })
+                });
+        }
+        &Value::Null
+    }
+
+    /// Names and schemas of `components/schemas`, in document order.
+    pub fn schemas(&self) -> Vec<(&str, &Value)> {
+        self.doc["components"]["schemas"]
+            .as_mapping()
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, schema)| Some((name.as_str()?, schema)))
+            .collect()
+    }
+
+    /// Whether the schema `name` of `components/schemas` is generated as a model. Arrays,
+    /// maps and primitives are not, and are used as the types they alias instead.
+    pub fn is_model(&self, name: &str) -> bool {
+        let schema = &self.doc["components"]["schemas"][name];
+        if schema.is_null() {
+            return false;
+        }
+        if !schema["enum"].is_null()
+            || !schema["allOf"].is_null()
+            || !schema["oneOf"].is_null()
+            || !schema["anyOf"].is_null()
+        {
+            return true;
+        }
+        let is_object = schema["type"].as_str() == Some("object") || schema["type"].is_null();
+        is_object
+            && schema["properties"]
+                .as_mapping()
+                .is_some_and(|p| !p.is_empty())
+    }
+
+    /// Name of the model `schema` refers to, if it is a reference to one.
+    pub fn model_name<'a>(&self, schema: &'a Value) -> Option<&'a str> {
+        let name = schema["$ref"]
+            .as_str()?
+            .strip_prefix("#/components/schemas/")?;
+        self.is_model(name).then_some(name)
+    }
+
+    /// The Rust type of values of `schema`.
+    pub fn data_type(&self, schema: &Value) -> Result<String, String> {
+        if let Some(name) = self.model_name(schema) {
+            return Ok(format!("models::{}", naming::camel_case(name)));
+        }
+        if let Some(reference) = schema["$ref"].as_str() {
+            let target = self.resolve(schema);
+            if target.is_null() {
+                return Err(format!("unresolvable reference {}", reference));
+            }
+            return self.data_type(target);
+        }
+        if let Some([schema]) = schema["allOf"].as_sequence().map(Vec::as_slice) {
+            return self.data_type(schema);
+        }
+
+        let format = schema["format"].as_str().unwrap_or_default();
+        let data_type = match schema["type"].as_str() {
+            Some("integer") => match format {
+                "int64" => "i64",
+                "uint32" => "u32",
+                "uint64" => "u64",
+                _ => "i32",
+            }
+            .to_string(),
+            Some("number") => match format {
+                "float" => "f32",
+                _ => "f64",
+            }
+            .to_string(),
+            Some("boolean") => "bool".to_string(),
+            Some("string") => match format {
+                "date-time" => "chrono::DateTime::<chrono::Utc>",
+                "date" => "chrono::naive::NaiveDate",
+                "uuid" => "uuid::Uuid",
+                "byte" | "binary" => "ByteArray",
+                _ => "String",
+            }
+            .to_string(),
+            Some("array") => format!("Vec<{}>", self.data_type(&schema["items"])?),
+            Some("object") | None => match self.map_value_schema(schema) {
+                Some(value) => format!(
+                    "std::collections::HashMap<String, {}>",
+                    self.data_type(value)?
+                ),
+                None if has_properties(schema) => {
+                    return Err("inline object schemas with properties are not supported, \
+                                move them to `components/schemas`"
+                        .to_string())
+                }
+                None => "crate::types::Object".to_string(),
+            },
+            Some(other) => return Err(format!("unsupported schema type {}", other)),
+        };
+        Ok(data_type)
+    }
+
+    /// Schema of the values of a map without properties, if `schema` is one.
+    fn map_value_schema<'a>(&self, schema: &'a Value) -> Option<&'a Value> {
+        let value = &schema["additionalProperties"];
+        (value.is_mapping() && !has_properties(schema)).then_some(value)
+    }
+
+    /// Template variables of a property, parameter, header or body of type `schema`, as those
+    /// of a `CodegenProperty` of openapi-generator.
+    ///
+    /// Every variable the templates use is set, `null` if it doesn't apply, as mustache looks up
+    /// missing names in the enclosing sections.
+    pub fn property(
+        &self,
+        schema: &Value,
+        base_name: &str,
+        required: bool,
+    ) -> Result<Map<String, Json>, String> {
+        let model = self.model_name(schema);
+        let resolved = match model {
+            Some(_) => schema,
+            None => self.resolve(schema),
+        };
+        let name = field_name(base_name);
+        let data_type = self.data_type(schema)?;
+        let format = resolved["format"].as_str().unwrap_or_default();
+        let is_array = model.is_none() && resolved["type"].as_str() == Some("array");
+        let map_value = match model {
+            Some(_) => None,
+            None => self.map_value_schema(resolved),
+        };
+        let is_free_form = model.is_none()
+            && matches!(resolved["type"].as_str(), Some("object") | None)
+            && resolved["$ref"].is_null()
+            && !has_properties(resolved)
+            && map_value.is_none()
+            && resolved["enum"].is_null()
+            && resolved["allOf"].is_null();
+
+        let items = if is_array {
+            Json::Object(self.property(&resolved["items"], base_name, true)?)
+        } else if let Some(value) = map_value {
+            Json::Object(self.property(value, base_name, true)?)
+        } else {
+            Json::Null
+        };
+        let number = |key: &str| match &resolved[key] {
+            Value::Number(n) => Json::from(n.to_string()),
+            _ => Json::Null,
+        };
+        let count = |key: &str| resolved[key].as_u64().map_or(Json::Null, Json::from);
+        let flag = |key: &str| resolved[key].as_bool().unwrap_or(false);
+        let xml = &resolved["xml"];
+
+        let Json::Object(mut property) = json!({
+            "name": name,
+            "paramName": name,
+            "baseName": base_name,
+            "nameInLowerCase": base_name.to_lowercase(),
+            "description": resolved["description"].as_str(),
+            "dataType": data_type,
+            "required": required,
+            "defaultValue": if required { Json::Null } else { Json::from("None") },
+            "isNullable": flag("nullable"),
+            "isModel": model.is_some(),
+            "isArray": is_array,
+            "isMap": map_value.is_some(),
+            "isFreeFormObject": is_free_form,
+            "isString": resolved["type"].as_str() == Some("string") && format != "binary",
+            "isFile": format == "binary",
+            "isByteArray": format == "byte",
+            "isEmail": format == "email",
+            "isUri": format == "uri",
+            "isUuid": format == "uuid",
+            "isEnum": model.is_none() && resolved["enum"].is_sequence(),
+            "allowableValues": match resolved["enum"].as_sequence() {
+                Some(values) if model.is_none() => json!({ "values": scalars(values) }),
+                _ => Json::Null,
+            },
+            "minLength": count("minLength"),
+            "maxLength": count("maxLength"),
+            "pattern": resolved["pattern"].as_str(),
+            "minimum": number("minimum"),
+            "maximum": number("maximum"),
+            "exclusiveMinimum": flag("exclusiveMinimum"),
+            "exclusiveMaximum": flag("exclusiveMaximum"),
+            "minItems": count("minItems"),
+            "maxItems": count("maxItems"),
+            "uniqueItems": flag("uniqueItems"),
+            "items": items,
+            "xmlName": xml["name"].as_str(),
+            "isXmlAttribute": xml["attribute"].as_bool().unwrap_or(false),
+            "isXmlWrapped": xml["wrapped"].as_bool().unwrap_or(false),
+            "contentType": Json::Null,
+            "vendorExtensions": {},
+        }) else {
+            unreachable!()
+        };
+        let has_validation = [
+            "minLength",
+            "maxLength",
+            "pattern",
+            "minimum",
+            "maximum",
+            "minItems",
+            "maxItems",
+        ]
+        .iter()
+        .any(|key| !property[*key].is_null());
+        property.insert("hasValidation".to_string(), Json::from(has_validation));
+        Ok(property)
+    }
+}
+
+/// Whether `schema` declares properties.
+pub fn has_properties(schema: &Value) -> bool {
+    schema["properties"]
+        .as_mapping()
+        .is_some_and(|properties| !properties.is_empty())
+}
+
+/// Name of the field (or variable) for the property or parameter `base_name`.
+pub fn field_name(base_name: &str) -> String {
+    let name = naming::snake_case(base_name);
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{}", name)
+    } else {
+        name
+    }
+}
+
+/// Text of the scalar `values`, e.g. of an `enum`.
+pub fn scalars(values: &[Value]) -> Vec<String> {
+    values
+        .iter()
+        .filter_map(|value| match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            Value::Bool(b) => Some(b.to_string()),
+            _ => None,
+        })
+        .collect()
+}
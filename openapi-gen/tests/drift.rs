@@ -0,0 +1,147 @@
+//! Checked-in output which differs from the generated one, because the document changed or
+//! the output was edited.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use openapi_gen::{
+    drift::{self, Cause},
+    Config,
+};
+
+const SPEC: &str = r#"
+openapi: 3.0.0
+info: { title: Pets, version: 1.0.0 }
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        '200': { description: Pets }
+"#;
+
+const TEMPLATE: &str = "\
+GENERATOR_INPUT_HASH = \"{{{generatorInputHash}}}\"
+{{#apiInfo.apis}}
+{{#operations.operation}}
+{{{operationId}}} {{{httpMethod}}} {{{path}}}
+{{/operations.operation}}
+{{/apiInfo.apis}}
+";
+
+const CONFIG: &str = "\
+generatorName: rust-axum
+inputSpec: spec.yaml
+outputDir: out
+templateDir: templates
+files:
+  operations.mustache:
+    templateType: SupportingFiles
+    destinationFilename: operations.txt
+";
+
+/// A directory with a document, a template rendering its operations, and their output.
+struct Project(PathBuf);
+
+impl Project {
+    fn generated() -> Self {
+        static PROJECTS: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "openapi-gen-drift-{}-{}",
+            std::process::id(),
+            PROJECTS.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(dir.join("templates")).unwrap();
+        fs::create_dir_all(dir.join("out")).unwrap();
+        fs::write(dir.join("spec.yaml"), SPEC).unwrap();
+        fs::write(dir.join("generator.yaml"), CONFIG).unwrap();
+        fs::write(dir.join("templates/operations.mustache"), TEMPLATE).unwrap();
+        // only the template above is rendered
+        fs::write(
+            dir.join("out/.openapi-generator-ignore"),
+            "/Cargo.toml\n/README.md\n/.gitignore\nsrc/**\n",
+        )
+        .unwrap();
+
+        let project = Project(dir);
+        openapi_gen::write(&project.config()).unwrap();
+        project
+    }
+
+    fn config(&self) -> Config {
+        Config::from_file(&self.0.join("generator.yaml")).unwrap()
+    }
+
+    fn path(&self, path: &str) -> PathBuf {
+        self.0.join(path)
+    }
+}
+
+impl Drop for Project {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn replace(path: &Path, from: &str, to: &str) {
+    let text = fs::read_to_string(path).unwrap();
+    assert!(text.contains(from));
+    fs::write(path, text.replace(from, to)).unwrap();
+}
+
+#[test]
+fn generated_output_has_no_drift() {
+    let project = Project::generated();
+    let output = fs::read_to_string(project.path("out/operations.txt")).unwrap();
+    assert!(output.contains("ListPets GET /pets"));
+    assert_eq!(drift::check(&project.config()).unwrap(), None);
+}
+
+#[test]
+fn document_changed_without_regenerating_is_stale() {
+    let project = Project::generated();
+    replace(&project.path("spec.yaml"), "get:", "post:");
+
+    let drift = drift::check(&project.config()).unwrap().unwrap();
+    assert_eq!(drift.cause, Cause::Stale);
+    assert_eq!(drift.diffs.len(), 1);
+    assert!(drift.diffs[0].contains("\n-ListPets GET /pets\n"));
+    assert!(drift.diffs[0].contains("\n+ListPets POST /pets\n"));
+    assert!(drift
+        .to_string()
+        .contains("spec.yaml, the templates, the generator or rustfmt.toml changed"));
+
+    openapi_gen::write(&project.config()).unwrap();
+    assert_eq!(drift::check(&project.config()).unwrap(), None);
+}
+
+#[test]
+fn template_changed_without_regenerating_is_stale() {
+    let project = Project::generated();
+    replace(
+        &project.path("templates/operations.mustache"),
+        "{{{path}}}",
+        "at {{{path}}}",
+    );
+
+    let drift = drift::check(&project.config()).unwrap().unwrap();
+    assert_eq!(drift.cause, Cause::Stale);
+}
+
+#[test]
+fn edited_output_is_edited() {
+    let project = Project::generated();
+    replace(&project.path("out/operations.txt"), "GET", "PUT");
+
+    let drift = drift::check(&project.config()).unwrap().unwrap();
+    assert_eq!(drift.cause, Cause::Edited);
+    assert!(drift.diffs[0].starts_with(&format!(
+        "--- {0}\n+++ {0} (generated)\n@@ -1,2 +1,2 @@\n",
+        project.path("out/operations.txt").display()
+    )));
+    assert!(drift.diffs[0].contains("-ListPets PUT /pets\n+ListPets GET /pets\n"));
+    assert!(drift.to_string().contains("generated files were edited"));
+}
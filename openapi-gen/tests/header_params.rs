@@ -0,0 +1,121 @@
+//! Required header and cookie parameters rendered by the server template of the repository,
+//! which the document of the repository doesn't have.
+
+use std::{
+    fs,
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use openapi_gen::Config;
+
+const SPEC: &str = r#"
+openapi: 3.0.0
+info: { title: Tags, version: 1.0.0 }
+paths:
+  /tags:
+    get:
+      operationId: listTags
+      parameters:
+        - name: X-Tenant
+          in: header
+          required: true
+          schema: { type: string }
+        - name: X-Trace
+          in: header
+          schema: { type: string }
+        - name: session
+          in: cookie
+          required: true
+          schema: { type: string }
+      responses:
+        '200': { description: Tags }
+"#;
+
+/// `src/server/mod.rs` of a crate generated from `SPEC`.
+fn server() -> String {
+    static RENDERS: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "openapi-gen-header-params-{}-{}",
+        std::process::id(),
+        RENDERS.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("spec.yaml"), SPEC).unwrap();
+    let templates = Path::new(env!("CARGO_MANIFEST_DIR")).join("../templates");
+    let config = format!(
+        "generatorName: rust-axum\ninputSpec: spec.yaml\noutputDir: out\ntemplateDir: {}\n",
+        templates.display()
+    );
+    let mut config = Config::from_yaml(&config, &dir).unwrap();
+    config
+        .files
+        .retain(|(template, _)| template == "server-mod.mustache");
+
+    let files = openapi_gen::generate(&config);
+    fs::remove_dir_all(&dir).unwrap();
+    files.unwrap().remove(0).contents
+}
+
+/// The `match` of the handler of `listTags` on the value of the parameter `variable`.
+fn parameter_match<'a>(server: &'a str, variable: &str) -> &'a str {
+    let start = server
+        .find(&format!("let {} = match", variable))
+        .unwrap_or_else(|| panic!("no match on {}:\n{}", variable, server));
+    let end = start + server[start..].find("\n        };").unwrap();
+    &server[start..end]
+}
+
+/// The branch of a parameter `match` for an absent parameter.
+fn missing_branch(parameter_match: &str) -> &str {
+    let start = parameter_match.find("None =>").unwrap();
+    &parameter_match[start..]
+}
+
+#[test]
+fn missing_required_headers_are_rejected_with_400() {
+    let server = server();
+
+    let missing = missing_branch(parameter_match(&server, "header_x_tenant"));
+    assert!(
+        missing.contains("return Response::builder()"),
+        "{}",
+        missing
+    );
+    assert!(
+        missing.contains(".status(StatusCode::BAD_REQUEST)"),
+        "{}",
+        missing
+    );
+    assert!(
+        missing.contains(r#"Body::from("Missing required header X-Tenant")"#),
+        "{}",
+        missing
+    );
+
+    let missing = missing_branch(parameter_match(&server, "header_x_trace"));
+    assert_eq!(missing, "None => None,");
+}
+
+#[test]
+fn missing_required_cookies_are_rejected_with_400() {
+    let server = server();
+
+    let missing = missing_branch(parameter_match(&server, "cookie_session"));
+    assert!(
+        missing.contains("return Response::builder()"),
+        "{}",
+        missing
+    );
+    assert!(
+        missing.contains(".status(StatusCode::BAD_REQUEST)"),
+        "{}",
+        missing
+    );
+    assert!(
+        missing.contains(r#"Body::from("Missing required cookie session")"#),
+        "{}",
+        missing
+    );
+    assert!(server.contains("session: cookie_session,"), "{}", server);
+}
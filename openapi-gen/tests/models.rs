@@ -0,0 +1,66 @@
+//! Vendor extensions of models the templates render as Rust code.
+
+use openapi_gen::{models, Spec};
+
+const SPEC: &str = "
+openapi: 3.0.0
+info: { title: Pets, version: 1.0.0 }
+paths: {}
+components:
+  schemas:
+    Error:
+      type: object
+      required: [code]
+      properties:
+        code: { type: integer }
+        message: { type: string }
+        kind: { type: string, enum: [client, server] }
+    Pet:
+      type: object
+      properties:
+        tags: { type: array, items: { type: string } }
+    Owner:
+      type: object
+      properties:
+        pet: { $ref: '#/components/schemas/Error' }
+    Labels:
+      type: object
+      properties:
+        name: { type: string }
+      additionalProperties: { type: string }
+    Extra:
+      type: object
+      properties:
+        attributes: { type: object }
+    Tag:
+      type: object
+      properties:
+        name: { type: string, nullable: true }
+";
+
+#[test]
+fn only_flat_models_are_query_encodable() {
+    let models = models::models(&Spec::from_yaml(SPEC).unwrap()).unwrap();
+    let encodable: Vec<_> = models
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|model| {
+            (
+                model["model"]["classname"].as_str().unwrap(),
+                model["model"]["vendorExtensions"]["x-query-encodable"] == true,
+            )
+        })
+        .collect();
+    assert_eq!(
+        encodable,
+        [
+            ("Error", true),
+            ("Extra", false),
+            ("Labels", false),
+            ("Owner", false),
+            ("Pet", false),
+            ("Tag", false),
+        ]
+    );
+}
@@ -0,0 +1,97 @@
+//! Rendering of templates as openapi-generator's jmustache renders them.
+
+use std::{
+    fs,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use openapi_gen::mustache::Templates;
+use serde_json::json;
+
+fn render(template: &str, context: serde_json::Value) -> String {
+    static RENDERS: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "openapi-gen-mustache-{}-{}",
+        std::process::id(),
+        RENDERS.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("test.mustache"), template).unwrap();
+    fs::write(dir.join("partial.mustache"), "[{{name}}]\n").unwrap();
+    let rendered = Templates::from_dir(&dir)
+        .unwrap()
+        .render("test.mustache", &context);
+    fs::remove_dir_all(&dir).unwrap();
+    rendered.unwrap()
+}
+
+#[test]
+fn names_are_looked_up_in_enclosing_sections() {
+    let context = json!({
+        "name": "Pet",
+        "description": "Pets",
+        "vars": [{ "name": "id" }, { "name": "tag", "description": null }],
+    });
+    assert_eq!(
+        render("{{#vars}}{{name}}: {{description}};{{/vars}}", context),
+        "id: Pets;tag: ;"
+    );
+}
+
+#[test]
+fn standalone_section_lines_are_dropped() {
+    let template = "start\n  {{#items}}\n  - {{.}}\n  {{/items}}\n{{! comment }}\nend\n";
+    assert_eq!(
+        render(template, json!({ "items": ["a", "b"] })),
+        "start\n  - a\n  - b\nend\n"
+    );
+}
+
+#[test]
+fn partial_lines_are_kept() {
+    assert_eq!(
+        render("  {{>partial}}\nend", json!({ "name": "Pet" })),
+        "  [Pet]\n\nend"
+    );
+}
+
+#[test]
+fn list_items_know_their_position() {
+    assert_eq!(
+        render(
+            "{{items.size}}: {{#items}}{{-index}}={{.}}{{^-last}}, {{/-last}}{{/items}}",
+            json!({ "items": ["a", "b", "c"] }),
+        ),
+        "3: 0=a, 1=b, 2=c"
+    );
+}
+
+#[test]
+fn variables_are_html_escaped_unless_triple() {
+    assert_eq!(
+        render("{{x}} {{{x}}} {{&x}}", json!({ "x": "<a href=\"b\">" })),
+        "&lt;a href&#x3D;&quot;b&quot;&gt; <a href=\"b\"> <a href=\"b\">"
+    );
+}
+
+#[test]
+fn empty_lists_and_nulls_are_falsy_but_empty_maps_are_not() {
+    assert_eq!(
+        render(
+            "{{#list}}list{{/list}}{{^none}}none{{/none}}{{#map}}map{{/map}}",
+            json!({ "list": [], "none": null, "map": {} }),
+        ),
+        "nonemap"
+    );
+}
+
+#[test]
+fn lambdas_transform_their_content() {
+    assert_eq!(
+        render(
+            "{{#lambda.uppercase}}{{id}}{{/lambda.uppercase}} {{#lambda.camelcase}}{{id}}{{/lambda.camelcase}} {{#lambda.titlecase}}{{t}}{{/lambda.titlecase}}",
+            json!({ "id": "list_pets", "t": "i64" }),
+        ),
+        "LIST_PETS listPets I64"
+    );
+}
@@ -0,0 +1,80 @@
+//! Vendor extensions of operations the templates render as Rust code.
+
+use openapi_gen::{operations, Spec};
+
+/// A document whose `createPets` operation has `x-body-limit: <limit>`.
+fn spec(limit: &str) -> Spec {
+    Spec::from_yaml(&format!(
+        "
+openapi: 3.0.0
+info: {{ title: Pets, version: 1.0.0 }}
+paths:
+  /pets:
+    post:
+      operationId: createPets
+      x-body-limit: {}
+      responses:
+        '201': {{ description: Created }}
+",
+        limit
+    ))
+    .unwrap()
+}
+
+#[test]
+fn body_limits_are_integers() {
+    let apis = operations::api_info(&spec("16384")).unwrap();
+    let operation = &apis["apis"][0]["operations"]["operation"][0];
+    assert_eq!(operation["vendorExtensions"]["x-body-limit"], 16384);
+
+    for limit in ["16 * 1024", "'16384'", "-1", "1.5", "{ bytes: 16384 }"] {
+        let error = operations::api_info(&spec(limit)).unwrap_err();
+        assert!(
+            error.starts_with("POST /pets: `x-body-limit` must be a non-negative integer"),
+            "{}: {}",
+            limit,
+            error
+        );
+    }
+}
+
+#[test]
+fn xml_wrappers_of_arrays_are_named_by_their_schema() {
+    let spec = |xml: &str| {
+        Spec::from_yaml(&format!(
+            "
+openapi: 3.0.0
+info: {{ title: Pets, version: 1.0.0 }}
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        '200':
+          description: The pets
+          content:
+            application/json:
+              schema: {{ $ref: '#/components/schemas/Pets' }}
+            application/xml:
+              schema: {{ $ref: '#/components/schemas/Pets' }}
+components:
+  schemas:
+    Pets:
+      type: array
+      items: {{ type: string }}
+      {}
+",
+            xml
+        ))
+        .unwrap()
+    };
+    let wrapper = |spec: &Spec| {
+        let apis = operations::api_info(spec).unwrap();
+        apis["apis"][0]["operations"]["operation"][0]["responses"][0]["vendorExtensions"]
+            ["x-xml-wrapper"]
+            .clone()
+    };
+
+    assert_eq!(wrapper(&spec("xml: { name: pets, wrapped: true }")), "pets");
+    assert_eq!(wrapper(&spec("")), "Pets");
+}
@@ -0,0 +1,99 @@
+//! Validation of path parameters rendered by the models template of the repository, for
+//! parameters the document of the repository doesn't have.
+
+use std::{
+    fs,
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use openapi_gen::Config;
+
+const SPEC: &str = r#"
+openapi: 3.0.0
+info: { title: Tags, version: 1.0.0 }
+paths:
+  /tags/{tagId}/{slug}:
+    get:
+      operationId: showTag
+      parameters:
+        - name: tagId
+          in: path
+          required: true
+          schema: { type: string, format: uuid }
+        - name: slug
+          in: path
+          required: true
+          schema: { type: string, pattern: '^[a-z-]+$' }
+      responses:
+        '200': { description: Tag }
+"#;
+
+/// `src/models.rs` of a crate generated from `SPEC`.
+fn models() -> String {
+    static RENDERS: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "openapi-gen-path-params-{}-{}",
+        std::process::id(),
+        RENDERS.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("spec.yaml"), SPEC).unwrap();
+    let templates = Path::new(env!("CARGO_MANIFEST_DIR")).join("../templates");
+    let config = format!(
+        "generatorName: rust-axum\ninputSpec: spec.yaml\noutputDir: out\ntemplateDir: {}\n",
+        templates.display()
+    );
+    let mut config = Config::from_yaml(&config, &dir).unwrap();
+    config
+        .files
+        .retain(|(template, _)| template == "models.mustache");
+
+    let files = openapi_gen::generate(&config);
+    fs::remove_dir_all(&dir).unwrap();
+    files.unwrap().remove(0).contents
+}
+
+/// The lines of the `PathParams` struct of `showTag`.
+fn path_params(models: &str) -> &str {
+    let start = models.find("pub struct ShowTagPathParams").unwrap();
+    let end = start + models[start..].find("\n}").unwrap();
+    &models[start..end]
+}
+
+#[test]
+fn uuid_path_parameters_are_typed_and_not_matched() {
+    let models = models();
+    let params = path_params(&models);
+
+    assert!(params.contains("pub tag_id: uuid::Uuid,"), "{}", params);
+    assert!(
+        params.contains(r#"proptest(strategy = "arbitrary_uuid()")"#),
+        "{}",
+        params
+    );
+    assert!(
+        !models.contains("RE_SHOWTAGPATHPARAMS_TAG_ID"),
+        "{}",
+        models
+    );
+}
+
+#[test]
+fn patterned_path_parameters_are_matched() {
+    let models = models();
+    let params = path_params(&models);
+
+    assert!(
+        params.contains(r#"regex = "RE_SHOWTAGPATHPARAMS_SLUG""#),
+        "{}",
+        params
+    );
+    assert!(
+        models.contains(
+            r#"static ref RE_SHOWTAGPATHPARAMS_SLUG: regex::Regex = regex::Regex::new(r"^[a-z-]+$").unwrap();"#
+        ),
+        "{}",
+        models
+    );
+}
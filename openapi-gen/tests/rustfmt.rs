@@ -0,0 +1,82 @@
+//! Output is stale when the `rustfmt` configuration changes, not when the version of `rustfmt`
+//! does. In a binary of its own, as it sets `RUSTFMT` for the whole process.
+#![cfg(unix)]
+
+use std::{fs, os::unix::fs::PermissionsExt};
+
+use openapi_gen::{
+    drift::{self, Cause},
+    Config,
+};
+
+const SPEC: &str = "
+openapi: 3.0.0
+info: { title: Pets, version: 1.0.0 }
+paths: {}
+";
+
+const CONFIG: &str = "\
+generatorName: rust-axum
+inputSpec: spec.yaml
+outputDir: out
+templateDir: templates
+files:
+  lib.mustache:
+    templateType: SupportingFiles
+    destinationFilename: lib.rs
+";
+
+#[test]
+fn rustfmt_version_is_not_an_input_but_its_configuration_is() {
+    let dir = std::env::temp_dir().join(format!("openapi-gen-rustfmt-{}", std::process::id()));
+    fs::create_dir_all(dir.join("templates")).unwrap();
+    fs::create_dir_all(dir.join("out")).unwrap();
+    fs::write(dir.join("spec.yaml"), SPEC).unwrap();
+    fs::write(dir.join("generator.yaml"), CONFIG).unwrap();
+    fs::write(
+        dir.join("templates/lib.mustache"),
+        "pub const GENERATOR_INPUT_HASH: &str = \"{{{generatorInputHash}}}\";\n\
+         pub const GENERATOR_VERSION: &str = \"{{{generatorVersion}}}\";\n\
+         pub fn answer() -> u8 { 42 }\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("out/.openapi-generator-ignore"),
+        "/Cargo.toml\n/README.md\n/.gitignore\nsrc/**\n",
+    )
+    .unwrap();
+    // formats as rustfmt does, under another version
+    let other = dir.join("rustfmt");
+    fs::write(
+        &other,
+        "#!/bin/sh\n\
+         if [ \"$1\" = --version ]; then echo 'rustfmt 0.0.0'; else exec rustfmt \"$@\"; fi\n",
+    )
+    .unwrap();
+    fs::set_permissions(&other, fs::Permissions::from_mode(0o755)).unwrap();
+    let config = Config::from_file(&dir.join("generator.yaml")).unwrap();
+    assert_eq!(config.rustfmt_config, None);
+
+    openapi_gen::write(&config).unwrap();
+    let lib = fs::read_to_string(dir.join("out/lib.rs")).unwrap();
+    let version = format!(
+        "pub const GENERATOR_VERSION: &str = \"openapi-gen {}\";",
+        env!("CARGO_PKG_VERSION")
+    );
+    assert!(lib.contains(&version), "{}", lib);
+    assert_eq!(drift::check(&config).unwrap(), None);
+
+    std::env::set_var("RUSTFMT", &other);
+    let drift = drift::check(&config).unwrap();
+    std::env::remove_var("RUSTFMT");
+    assert_eq!(drift, None);
+
+    fs::write(dir.join("rustfmt.toml"), "hard_tabs = true\n").unwrap();
+    let config = Config::from_file(&dir.join("generator.yaml")).unwrap();
+    assert_eq!(config.rustfmt_config, Some(dir.join("rustfmt.toml")));
+    let drift = drift::check(&config).unwrap().unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(drift.cause, Cause::Stale);
+    assert!(drift.diffs[0].contains("+\t42"), "{}", drift);
+}
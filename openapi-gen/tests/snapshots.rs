@@ -0,0 +1,24 @@
+//! The checked-in `petstore-api` is what the generator renders from the documents of the
+//! repository, file for file.
+
+use std::path::Path;
+
+use openapi_gen::{drift, Config};
+
+fn assert_generated(config: &str) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let config = Config::from_file(&root.join(config)).unwrap();
+    if let Some(drift) = drift::check(&config).unwrap() {
+        panic!("{}", drift);
+    }
+}
+
+#[test]
+fn crate_root_is_generated_from_version_1() {
+    assert_generated("generator.yaml");
+}
+
+#[test]
+fn v2_module_is_generated_from_version_2() {
+    assert_generated("generator-v2.yaml");
+}
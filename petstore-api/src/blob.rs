@@ -0,0 +1,224 @@
+//! Local filesystem storage for the binary payloads of the API, e.g. uploaded photos.
+//!
+//! [`BlobStore::put`] streams an upload such as [`crate::types::UploadedFile::data`] to disk,
+//! and [`BlobStore::get`] streams it back as a [`BinaryBody`], honouring `Range` requests.
+
+use std::{
+    io::{self, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use bytes::Bytes;
+use futures::Stream;
+use tokio::{
+    fs,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
+use tokio_util::io::{ReaderStream, StreamReader};
+
+use crate::types::BinaryBody;
+
+const CONTENT_TYPE_FILE: &str = "content-type";
+const DATA_FILE: &str = "data";
+
+/// Store of binary blobs, e.g. uploaded files, in a directory of the local filesystem.
+///
+/// Every blob lives in its own directory below the root, named after its key, next to the
+/// media type it was stored with. Blobs are streamed in and out and never held in memory.
+#[derive(Debug, Clone)]
+pub struct BlobStore {
+    root: PathBuf,
+}
+
+/// A blob read from a [`BlobStore`], as requested by a `Range` header.
+#[derive(Debug, PartialEq)]
+pub enum Download {
+    /// The whole blob.
+    Full { body: BinaryBody, len: u64 },
+    /// The requested range of the blob; `content_range` is the value of the `Content-Range`
+    /// header.
+    Partial {
+        body: BinaryBody,
+        len: u64,
+        content_range: String,
+    },
+    /// The requested range lies outside the blob; `content_range` is `bytes */<size>`.
+    Unsatisfiable { content_range: String },
+}
+
+impl BlobStore {
+    /// Keep blobs below `root`, which is created on the first upload.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        BlobStore { root: root.into() }
+    }
+
+    /// Keep blobs in the directory named by the `BLOB_DIR` environment variable, `./blobs` by
+    /// default.
+    pub fn from_env() -> Self {
+        BlobStore::new(std::env::var_os("BLOB_DIR").unwrap_or_else(|| "blobs".into()))
+    }
+
+    /// Directory the blobs are kept in.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Store the blob `key`, replacing any previous one, and return its size.
+    ///
+    /// The blob is written to a temporary file first, so readers never see a partial upload.
+    /// An error from `data` discards the upload and is returned as is.
+    pub async fn put<S>(&self, key: &str, content_type: Option<&str>, data: S) -> io::Result<u64>
+    where
+        S: Stream<Item = io::Result<Bytes>> + Send,
+    {
+        let dir = self.dir(key)?;
+        fs::create_dir_all(&dir).await?;
+
+        let suffix = uuid::Uuid::new_v4().simple().to_string();
+        let tmp_data = dir.join(format!(".{}.{}", DATA_FILE, suffix));
+        let tmp_content_type = dir.join(format!(".{}.{}", CONTENT_TYPE_FILE, suffix));
+
+        let written = async {
+            let mut file = fs::File::create(&tmp_data).await?;
+            let mut reader = StreamReader::new(Box::pin(data));
+            let len = tokio::io::copy(&mut reader, &mut file).await?;
+            file.sync_all().await?;
+            fs::write(&tmp_content_type, content_type.unwrap_or_default()).await?;
+            // `get` opens the data before it reads the media type, so the data is replaced first
+            fs::rename(&tmp_data, dir.join(DATA_FILE)).await?;
+            fs::rename(&tmp_content_type, dir.join(CONTENT_TYPE_FILE)).await?;
+            Ok(len)
+        }
+        .await;
+
+        if written.is_err() {
+            let _ = fs::remove_file(&tmp_data).await;
+            let _ = fs::remove_file(&tmp_content_type).await;
+        }
+        written
+    }
+
+    /// Read the blob `key`, or the part of it requested by the value of a `Range` header.
+    ///
+    /// Only single byte ranges are served; any other `Range` header is ignored as permitted
+    /// by RFC 9110, and the whole blob is returned. Returns `None` if there is no such blob.
+    pub async fn get(&self, key: &str, range: Option<&str>) -> io::Result<Option<Download>> {
+        let dir = self.dir(key)?;
+        let mut file = match fs::File::open(dir.join(DATA_FILE)).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let size = file.metadata().await?.len();
+        let content_type = match fs::read_to_string(dir.join(CONTENT_TYPE_FILE)).await {
+            Ok(content_type) if !content_type.is_empty() => Some(content_type),
+            Ok(_) => None,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+
+        let (start, end) = match range.map(|range| parse_range(range, size)) {
+            None | Some(ByteRange::Full) => {
+                let body = body(file, size, content_type);
+                return Ok(Some(Download::Full { body, len: size }));
+            }
+            Some(ByteRange::Unsatisfiable) => {
+                return Ok(Some(Download::Unsatisfiable {
+                    content_range: format!("bytes */{}", size),
+                }))
+            }
+            Some(ByteRange::Partial(start, end)) => (start, end),
+        };
+
+        file.seek(SeekFrom::Start(start)).await?;
+        let len = end - start + 1;
+        Ok(Some(Download::Partial {
+            body: body(file, len, content_type),
+            len,
+            content_range: format!("bytes {}-{}/{}", start, end, size),
+        }))
+    }
+
+    /// Remove the blob `key`, if there is one.
+    pub async fn delete(&self, key: &str) -> io::Result<()> {
+        match fs::remove_dir_all(self.dir(key)?).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    fn dir(&self, key: &str) -> io::Result<PathBuf> {
+        let valid = !key.is_empty()
+            && !key.starts_with('.')
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid blob key {:?}", key),
+            ));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+fn body(file: fs::File, len: u64, content_type: Option<String>) -> BinaryBody {
+    let body = BinaryBody::stream(ReaderStream::new(file.take(len)));
+    match content_type {
+        Some(content_type) => body.with_content_type(content_type),
+        None => body,
+    }
+}
+
+/// A `Range` header resolved against the size of a blob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// Serve the whole blob.
+    Full,
+    /// Serve the bytes from the first to the second position, inclusive.
+    Partial(u64, u64),
+    /// No byte of the range lies within the blob.
+    Unsatisfiable,
+}
+
+/// Resolve the value of a `Range` header, e.g. `bytes=0-499`, `bytes=500-` or `bytes=-500`,
+/// against a blob of `size` bytes.
+///
+/// Malformed headers, other units and multiple ranges resolve to [`ByteRange::Full`].
+pub fn parse_range(range: &str, size: u64) -> ByteRange {
+    let Some((unit, spec)) = range.trim().split_once('=') else {
+        return ByteRange::Full;
+    };
+    if !unit.trim().eq_ignore_ascii_case("bytes") || spec.contains(',') {
+        return ByteRange::Full;
+    }
+    let Some((first, last)) = spec.trim().split_once('-') else {
+        return ByteRange::Full;
+    };
+    let (first, last) = (first.trim(), last.trim());
+
+    let parse = |v: &str| v.parse::<u64>().ok();
+    match (first.is_empty(), last.is_empty()) {
+        // the last `last` bytes
+        (true, false) => match parse(last) {
+            Some(0) => ByteRange::Unsatisfiable,
+            Some(_) if size == 0 => ByteRange::Unsatisfiable,
+            Some(suffix) => ByteRange::Partial(size.saturating_sub(suffix), size - 1),
+            None => ByteRange::Full,
+        },
+        // from `first` to the end
+        (false, true) => match parse(first) {
+            Some(first) if first >= size => ByteRange::Unsatisfiable,
+            Some(first) => ByteRange::Partial(first, size - 1),
+            None => ByteRange::Full,
+        },
+        (false, false) => match (parse(first), parse(last)) {
+            (Some(first), Some(last)) if first > last => ByteRange::Full,
+            (Some(first), Some(_)) if first >= size => ByteRange::Unsatisfiable,
+            (Some(first), Some(last)) => ByteRange::Partial(first, last.min(size - 1)),
+            _ => ByteRange::Full,
+        },
+        (true, true) => ByteRange::Full,
+    }
+}
@@ -0,0 +1,455 @@
+//! In-process response cache for the generated router.
+//!
+//! Successful `GET` responses of operations with an `x-cache` extension are kept in a bounded
+//! LRU [`ResponseCache`] for `ttl` seconds, keyed by path, query, the request headers listed in
+//! `vary` and the [`Principal`] of the request:
+//!
+//! ```yaml
+//! x-cache:
+//!   ttl: 30              # seconds responses are fresh
+//!   vary: [Accept]       # request headers selecting the representation
+//! ```
+//!
+//! Cached responses are sent with `Cache-Control: max-age` (`private` for principals), `Vary`
+//! and `Age`. Successful requests with other methods invalidate the responses cached for their
+//! path, its ancestors and its descendants, e.g. `POST /v1/pets` invalidates `GET /v1/pets`
+//! and `GET /v1/pets/1`. Paths are compared below the base path of their API version, so
+//! versions sharing a cache invalidate each other's responses, e.g. `POST /v2/pets` invalidates
+//! `GET /v1/pets` too.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::Infallible,
+    fmt,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::{Body, HttpBody},
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use futures::{future::BoxFuture, stream, StreamExt};
+use http::{
+    header::{AGE, CACHE_CONTROL, SET_COOKIE, VARY},
+    HeaderMap, HeaderValue, Method, Request, StatusCode,
+};
+use tower::{Layer, Service};
+use tracing::warn;
+
+use crate::security::Principal;
+
+/// Caching policy of an operation, as declared by its `x-cache` extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachePolicy {
+    pub ttl: Duration,
+    /// Lowercase names of the request headers the responses vary by.
+    pub vary: &'static [&'static str],
+}
+
+impl CachePolicy {
+    /// Cache responses for `ttl`, separately for each value of the `vary` request headers.
+    pub const fn new(ttl: Duration, vary: &'static [&'static str]) -> Self {
+        CachePolicy { ttl, vary }
+    }
+}
+
+/// Number of responses kept by [`ResponseCache::default`].
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+/// Size of the largest response body kept by [`ResponseCache::default`].
+pub const DEFAULT_MAX_ENTRY_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Key {
+    path: String,
+    query: Option<String>,
+    vary: Vec<Vec<HeaderValue>>,
+    principal: Option<String>,
+}
+
+struct Entry {
+    /// Path of the request below the base path of its API version.
+    resource: String,
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+    stored: Instant,
+    ttl: Duration,
+    tick: u64,
+}
+
+#[derive(Default)]
+struct Entries {
+    entries: HashMap<Key, Entry>,
+    /// Keys by the tick they were last used at, least recently used first.
+    lru: BTreeMap<u64, Key>,
+    tick: u64,
+    /// Incremented by each invalidation, to drop responses produced before it.
+    generation: u64,
+}
+
+/// Bounded LRU cache of responses shared by the operations of a router.
+#[derive(Clone)]
+pub struct ResponseCache {
+    entries: Arc<Mutex<Entries>>,
+    capacity: usize,
+    max_entry_size: usize,
+}
+
+impl fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("capacity", &self.capacity)
+            .field("max_entry_size", &self.max_entry_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        ResponseCache::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl ResponseCache {
+    /// Cache of up to `capacity` responses; a `capacity` of 0 disables caching.
+    pub fn new(capacity: usize) -> Self {
+        ResponseCache {
+            entries: Default::default(),
+            capacity,
+            max_entry_size: DEFAULT_MAX_ENTRY_SIZE,
+        }
+    }
+
+    /// Don't keep responses with bodies larger than `max_entry_size` bytes.
+    pub fn with_max_entry_size(mut self, max_entry_size: usize) -> Self {
+        self.max_entry_size = max_entry_size;
+        self
+    }
+
+    /// Number of responses in the cache, including expired ones not evicted yet.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().entries.len()
+    }
+
+    /// Whether the cache holds no responses.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all cached responses.
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.entries.clear();
+        entries.lru.clear();
+        entries.generation += 1;
+    }
+
+    /// Layer caching the responses of an operation as declared by `policy`, and invalidating
+    /// cached responses after successful requests with unsafe methods.
+    pub fn layer(&self, policy: Option<CachePolicy>) -> CacheLayer {
+        CacheLayer {
+            cache: self.clone(),
+            policy,
+            base_path: "",
+        }
+    }
+
+    fn generation(&self) -> u64 {
+        self.entries.lock().unwrap().generation
+    }
+
+    /// Fresh response cached for `key`, with its age.
+    fn get(&self, key: &Key) -> Option<(Response, Duration)> {
+        let mut guard = self.entries.lock().unwrap();
+        let entries = &mut *guard;
+        let entry = entries.entries.get_mut(key)?;
+        let age = entry.stored.elapsed();
+        if age >= entry.ttl {
+            let tick = entry.tick;
+            entries.entries.remove(key);
+            entries.lru.remove(&tick);
+            return None;
+        }
+
+        entries.tick += 1;
+        entries.lru.remove(&entry.tick);
+        entry.tick = entries.tick;
+        entries.lru.insert(entry.tick, key.clone());
+
+        let mut response = Response::new(Body::from(entry.body.clone()));
+        *response.status_mut() = entry.status;
+        *response.headers_mut() = entry.headers.clone();
+        Some((response, age))
+    }
+
+    /// Cache the response of a request made at `generation`, unless the cache was invalidated
+    /// since.
+    fn insert(&self, generation: u64, key: Key, mut entry: Entry) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.generation != generation {
+            return;
+        }
+        if let Some(previous) = entries.entries.remove(&key) {
+            entries.lru.remove(&previous.tick);
+        }
+        while entries.entries.len() >= self.capacity {
+            let Some((_, oldest)) = entries.lru.pop_first() else {
+                break;
+            };
+            entries.entries.remove(&oldest);
+        }
+        entries.tick += 1;
+        entry.tick = entries.tick;
+        entries.lru.insert(entry.tick, key.clone());
+        entries.entries.insert(key, entry);
+    }
+
+    /// Drop the responses cached for `resource`, its ancestors and its descendants, in any API
+    /// version.
+    fn invalidate(&self, resource: &str) {
+        let mut guard = self.entries.lock().unwrap();
+        let entries = &mut *guard;
+        entries.generation += 1;
+        let lru = &mut entries.lru;
+        entries.entries.retain(|_, entry| {
+            let related = is_related(&entry.resource, resource);
+            if related {
+                lru.remove(&entry.tick);
+            }
+            !related
+        });
+    }
+}
+
+/// Whether one of the paths `a` and `b` is the other or one of its ancestors.
+fn is_related(a: &str, b: &str) -> bool {
+    let a = a.trim_end_matches('/');
+    let b = b.trim_end_matches('/');
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    long.strip_prefix(short)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// `path` below `base_path`, or `path` if it isn't below it.
+fn resource<'a>(path: &'a str, base_path: &str) -> &'a str {
+    match path.strip_prefix(base_path) {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => rest,
+        _ => path,
+    }
+}
+
+/// Whether the `Cache-Control` headers of `headers` contain one of `directives`.
+fn has_directive(headers: &HeaderMap, directives: &[&str]) -> bool {
+    headers
+        .get_all(CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|directive| directive.split('=').next().unwrap_or_default().trim())
+        .any(|directive| {
+            directives
+                .iter()
+                .any(|expected| directive.eq_ignore_ascii_case(expected))
+        })
+}
+
+/// Layer created by [`ResponseCache::layer`].
+#[derive(Debug, Clone)]
+pub struct CacheLayer {
+    cache: ResponseCache,
+    policy: Option<CachePolicy>,
+    base_path: &'static str,
+}
+
+impl CacheLayer {
+    /// Compare the paths of the requests below `base_path`, the base path of their API
+    /// version, to invalidate the responses of the other versions sharing the cache.
+    pub fn base_path(mut self, base_path: &'static str) -> Self {
+        self.base_path = base_path;
+        self
+    }
+}
+
+impl<S> Layer<S> for CacheLayer {
+    type Service = CacheService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CacheService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service created by [`CacheLayer`].
+#[derive(Debug, Clone)]
+pub struct CacheService<S> {
+    inner: S,
+    layer: CacheLayer,
+}
+
+impl<S> Service<Request<Body>> for CacheService<S>
+where
+    S: Service<Request<Body>, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let cache = self.layer.cache.clone();
+        let method = request.method().clone();
+        let path = request.uri().path().to_string();
+        let resource = resource(&path, self.layer.base_path).to_string();
+
+        if !method.is_safe() {
+            let future = self.inner.call(request);
+            return Box::pin(async move {
+                let response = future.await?;
+                if response.status().is_success() {
+                    cache.invalidate(&resource);
+                }
+                Ok(response)
+            });
+        }
+
+        let policy = match self.layer.policy {
+            Some(policy) if method == Method::GET => policy,
+            _ => return Box::pin(self.inner.call(request)),
+        };
+
+        let headers = request.headers();
+        let principal = request
+            .extensions()
+            .get::<Principal>()
+            .map(|Principal(principal)| principal.clone());
+        let key = Key {
+            path,
+            query: request.uri().query().map(str::to_string),
+            vary: policy
+                .vary
+                .iter()
+                .map(|name| headers.get_all(*name).iter().cloned().collect())
+                .collect(),
+            principal,
+        };
+        let revalidate = has_directive(headers, &["no-cache", "no-store"]);
+        let store = !has_directive(headers, &["no-store"]);
+
+        if !revalidate {
+            if let Some((mut response, age)) = cache.get(&key) {
+                response.headers_mut().insert(AGE, age.as_secs().into());
+                return Box::pin(async move { Ok(response) });
+            }
+        }
+
+        let generation = cache.generation();
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let response = future.await?;
+            if response.status() != StatusCode::OK
+                || response.headers().contains_key(SET_COOKIE)
+                || has_directive(response.headers(), &["no-store", "private", "no-cache"])
+            {
+                return Ok(response);
+            }
+
+            let (mut parts, body) = response.into_parts();
+            if !parts.headers.contains_key(CACHE_CONTROL) {
+                let cache_control = match key.principal {
+                    Some(_) => format!("private, max-age={}", policy.ttl.as_secs()),
+                    None => format!("max-age={}", policy.ttl.as_secs()),
+                };
+                if let Ok(value) = HeaderValue::from_str(&cache_control) {
+                    parts.headers.insert(CACHE_CONTROL, value);
+                }
+            }
+            if !policy.vary.is_empty() {
+                if let Ok(value) = HeaderValue::from_str(&policy.vary.join(", ")) {
+                    parts.headers.append(VARY, value);
+                }
+            }
+            if !store {
+                parts.headers.insert(AGE, 0.into());
+                return Ok(Response::from_parts(parts, body));
+            }
+
+            let mut entry = Entry {
+                resource,
+                status: parts.status,
+                headers: parts.headers.clone(),
+                body: Bytes::new(),
+                stored: Instant::now(),
+                ttl: policy.ttl,
+                tick: 0,
+            };
+            parts.headers.insert(AGE, 0.into());
+
+            // bodies of a known size are cached at once and sent as they were, so the layers
+            // outside still see their size, e.g. the contract check, which skips streamed bodies
+            let max_entry_size = cache.max_entry_size;
+            match body.size_hint().exact() {
+                Some(size) if size > max_entry_size as u64 => {
+                    return Ok(Response::from_parts(parts, body));
+                }
+                Some(_) => {
+                    return Ok(match axum::body::to_bytes(body, max_entry_size).await {
+                        Ok(body) => {
+                            entry.body = body.clone();
+                            cache.insert(generation, key, entry);
+                            Response::from_parts(parts, Body::from(body))
+                        }
+                        Err(e) => {
+                            warn!(error = ?e, "Unable to read the response body to cache");
+                            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                        }
+                    });
+                }
+                None => {}
+            }
+
+            // keep streamed bodies while they are sent, and cache the response once complete
+            let pending = Some((cache, key, entry));
+            let body = stream::unfold(
+                (body.into_data_stream(), Vec::new(), pending),
+                move |(mut stream, mut buffer, mut pending)| async move {
+                    match stream.next().await {
+                        Some(Ok(chunk)) => {
+                            if pending.is_some() {
+                                if buffer.len() + chunk.len() > max_entry_size {
+                                    pending = None;
+                                    buffer = Vec::new();
+                                } else {
+                                    buffer.extend_from_slice(&chunk);
+                                }
+                            }
+                            Some((Ok(chunk), (stream, buffer, pending)))
+                        }
+                        Some(Err(err)) => Some((Err(err), (stream, Vec::new(), None))),
+                        None => {
+                            if let Some((cache, key, mut entry)) = pending {
+                                entry.body = buffer.into();
+                                cache.insert(generation, key, entry);
+                            }
+                            None
+                        }
+                    }
+                },
+            );
+            // fused, as compression polls the body once more after its end
+            Ok(Response::from_parts(parts, Body::from_stream(body.fuse())))
+        })
+    }
+}
@@ -0,0 +1,514 @@
+use std::{
+    convert::Infallible,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::{
+    body::{Body, HttpBody},
+    extract::Request,
+    response::Response,
+    Router,
+};
+use http::{header::CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use jsonschema::{Draft, JSONSchema};
+use serde_json::{json, Value};
+use tower::{Layer, Service, ServiceExt};
+use tracing::{error, warn};
+
+pub use crate::SPEC;
+
+/// What to do with a response that does not conform to the contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Log each violation and pass the response through unchanged.
+    Log,
+    /// Replace the response with a `500 Internal Server Error` listing the violations.
+    Fail,
+}
+
+/// A single difference between a live response and the contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub method: Method,
+    pub path: String,
+    pub status: StatusCode,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} -> {}: {}",
+            self.method, self.path, self.status, self.message
+        )
+    }
+}
+
+struct HeaderContract {
+    name: HeaderName,
+    required: bool,
+    kind: Option<String>,
+    schema: JSONSchema,
+}
+
+struct ResponseContract {
+    headers: Vec<HeaderContract>,
+    content: Vec<(String, Option<JSONSchema>)>,
+}
+
+struct OperationContract {
+    method: Method,
+    path: regex::Regex,
+    responses: Vec<(Option<u16>, ResponseContract)>,
+}
+
+/// Response contract compiled from an OpenAPI document.
+pub struct Contract {
+    operations: Vec<OperationContract>,
+}
+
+impl Contract {
+    /// Compile the contract from the embedded [`SPEC`].
+    pub fn new() -> Result<Self, String> {
+        Self::from_yaml(SPEC)
+    }
+
+    /// Compile the contract from an OpenAPI document in YAML (or JSON) form.
+    pub fn from_yaml(spec: &str) -> Result<Self, String> {
+        let doc: Value = serde_yaml::from_str(spec).map_err(|e| e.to_string())?;
+        let components = doc.get("components").cloned().unwrap_or(Value::Null);
+        let base_path = doc
+            .pointer("/servers/0/url")
+            .and_then(Value::as_str)
+            .map(server_base_path)
+            .unwrap_or_default();
+
+        let mut operations = Vec::new();
+        for (path, item) in doc
+            .get("paths")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            let path_regex = path_regex(&base_path, path)?;
+            for (method, operation) in item.as_object().into_iter().flatten() {
+                let Ok(method) = method.to_uppercase().parse::<Method>() else {
+                    continue;
+                };
+                let mut responses = Vec::new();
+                for (code, response) in operation
+                    .get("responses")
+                    .and_then(Value::as_object)
+                    .into_iter()
+                    .flatten()
+                {
+                    let code = match code.as_str() {
+                        "default" => None,
+                        code => Some(code.parse::<u16>().map_err(|e| {
+                            format!(
+                                "Invalid response code {} for {} {}: {}",
+                                code, method, path, e
+                            )
+                        })?),
+                    };
+                    responses.push((code, response_contract(&components, response)?));
+                }
+                operations.push(OperationContract {
+                    method,
+                    path: path_regex.clone(),
+                    responses,
+                });
+            }
+        }
+
+        Ok(Contract { operations })
+    }
+
+    /// Check a response against the contract.
+    ///
+    /// Requests which do not match any operation in the document are not checked.
+    pub fn check(
+        &self,
+        method: &Method,
+        path: &str,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Vec<Violation> {
+        self.check_parts(method, path, status, headers, Some(body))
+    }
+
+    /// Check a response against the contract, or only its status and headers without `body`.
+    fn check_parts(
+        &self,
+        method: &Method,
+        path: &str,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: Option<&[u8]>,
+    ) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let mut violation = |message: String| {
+            violations.push(Violation {
+                method: method.clone(),
+                path: path.to_string(),
+                status,
+                message,
+            })
+        };
+
+        let Some(operation) = self
+            .operations
+            .iter()
+            .find(|op| op.method == method && op.path.is_match(path))
+        else {
+            return violations;
+        };
+
+        let response = operation
+            .responses
+            .iter()
+            .find(|(code, _)| *code == Some(status.as_u16()))
+            .or_else(|| operation.responses.iter().find(|(code, _)| code.is_none()));
+        let Some((_, response)) = response else {
+            violation(format!("Undocumented status code {}", status.as_u16()));
+            return violations;
+        };
+
+        for header in &response.headers {
+            let Some(value) = headers.get(&header.name) else {
+                if header.required {
+                    violation(format!("Missing required header {}", header.name));
+                }
+                continue;
+            };
+            let Ok(value) = value.to_str() else {
+                violation(format!("Header {} is not valid text", header.name));
+                continue;
+            };
+            let value = header_value(header.kind.as_deref(), value);
+            let errors = match header.schema.validate(&value) {
+                Ok(()) => Vec::new(),
+                Err(errors) => errors.map(|e| e.to_string()).collect(),
+            };
+            for error in errors {
+                violation(format!("Header {}: {}", header.name, error));
+            }
+        }
+
+        if response.content.is_empty() {
+            if body.is_some_and(|body| !body.is_empty()) {
+                violation("Unexpected response body".to_string());
+            }
+            return violations;
+        }
+
+        let content_type = headers
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(';').next())
+            .map(str::trim);
+        let Some(content_type) = content_type else {
+            violation("Missing Content-Type".to_string());
+            return violations;
+        };
+        let Some((_, schema)) = response
+            .content
+            .iter()
+            .find(|(media_type, _)| media_type.eq_ignore_ascii_case(content_type))
+        else {
+            violation(format!("Undocumented Content-Type {}", content_type));
+            return violations;
+        };
+
+        if let (Some(schema), Some(body)) = (schema, body) {
+            match serde_json::from_slice::<Value>(body) {
+                Ok(instance) => {
+                    let errors = match schema.validate(&instance) {
+                        Ok(()) => Vec::new(),
+                        Err(errors) => errors
+                            .map(|e| format!("Body at '{}': {}", e.instance_path, e))
+                            .collect(),
+                    };
+                    errors.into_iter().for_each(&mut violation);
+                }
+                Err(e) => violation(format!("Body is not valid JSON: {}", e)),
+            }
+        }
+
+        violations
+    }
+}
+
+fn server_base_path(url: &str) -> String {
+    let path = match url.find("://") {
+        Some(scheme) => {
+            let rest = &url[scheme + 3..];
+            rest.find('/').map(|p| &rest[p..]).unwrap_or("")
+        }
+        None => url,
+    };
+    path.trim_end_matches('/').to_string()
+}
+
+fn path_regex(base_path: &str, path: &str) -> Result<regex::Regex, String> {
+    let mut pattern = format!("^{}", regex::escape(base_path));
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        pattern.push('/');
+        if segment.starts_with('{') && segment.ends_with('}') {
+            pattern.push_str("[^/]+");
+        } else {
+            pattern.push_str(&regex::escape(segment));
+        }
+    }
+    pattern.push('$');
+    regex::Regex::new(&pattern).map_err(|e| e.to_string())
+}
+
+fn compile_schema(components: &Value, schema: &Value) -> Result<JSONSchema, String> {
+    // Keep the components next to the schema so that "#/components/..." references resolve.
+    let root = json!({
+        "components": components,
+        "allOf": [schema],
+    });
+    JSONSchema::options()
+        .with_draft(Draft::Draft4)
+        .compile(&root)
+        .map_err(|e| e.to_string())
+}
+
+fn response_contract(components: &Value, response: &Value) -> Result<ResponseContract, String> {
+    let mut headers = Vec::new();
+    for (name, header) in response
+        .get("headers")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        let schema = header.get("schema").cloned().unwrap_or(json!({}));
+        headers.push(HeaderContract {
+            name: HeaderName::try_from(name.as_str()).map_err(|e| e.to_string())?,
+            required: header
+                .get("required")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            kind: schema
+                .get("type")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            schema: compile_schema(components, &schema)?,
+        });
+    }
+
+    let mut content = Vec::new();
+    for (media_type, media) in response
+        .get("content")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        let schema = match media.get("schema") {
+            Some(schema) if is_json(media_type) => Some(compile_schema(components, schema)?),
+            _ => None,
+        };
+        content.push((media_type.clone(), schema));
+    }
+
+    Ok(ResponseContract { headers, content })
+}
+
+fn is_json(media_type: &str) -> bool {
+    let media_type = media_type.to_ascii_lowercase();
+    media_type == "application/json" || media_type.ends_with("+json")
+}
+
+fn header_value(kind: Option<&str>, value: &str) -> Value {
+    match kind {
+        Some("integer") | Some("number") => serde_json::from_str::<serde_json::Number>(value)
+            .map(Value::Number)
+            .unwrap_or_else(|_| Value::String(value.to_string())),
+        Some("boolean") => value
+            .parse::<bool>()
+            .map(Value::Bool)
+            .unwrap_or_else(|_| Value::String(value.to_string())),
+        _ => Value::String(value.to_string()),
+    }
+}
+
+/// Tower layer which validates every response against a [`Contract`].
+#[derive(Clone)]
+pub struct ContractLayer {
+    contract: Arc<Contract>,
+    mode: Mode,
+}
+
+impl ContractLayer {
+    pub fn new(contract: Contract, mode: Mode) -> Self {
+        ContractLayer {
+            contract: Arc::new(contract),
+            mode,
+        }
+    }
+}
+
+impl Default for ContractLayer {
+    /// Validate against the embedded [`SPEC`], logging any drift.
+    fn default() -> Self {
+        ContractLayer::new(
+            Contract::new().expect("Embedded OpenAPI document is invalid"),
+            Mode::Log,
+        )
+    }
+}
+
+impl<S> Layer<S> for ContractLayer {
+    type Service = ContractService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ContractService {
+            inner,
+            contract: self.contract.clone(),
+            mode: self.mode,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ContractService<S> {
+    inner: S,
+    contract: Arc<Contract>,
+    mode: Mode,
+}
+
+impl<S> Service<Request> for ContractService<S>
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let method = req.method().clone();
+        let path = req.uri().path().to_string();
+        let contract = self.contract.clone();
+        let mode = self.mode;
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let response = inner.call(req).await?;
+            let (parts, body) = response.into_parts();
+
+            // only JSON bodies have a schema; others, e.g. file downloads and NDJSON, are passed
+            // through without buffering them, and so are streamed bodies, whose length isn't known
+            // up front
+            let json = parts
+                .headers
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.split(';').next())
+                .is_none_or(|v| is_json(v.trim()));
+            let streamed = body.size_hint().exact().is_none();
+            if !json || streamed {
+                let violations =
+                    contract.check_parts(&method, &path, parts.status, &parts.headers, None);
+                for violation in &violations {
+                    warn!(%violation, "Response does not conform to the OpenAPI contract");
+                }
+                return Ok(match mode {
+                    Mode::Fail if !violations.is_empty() => violations_response(&violations),
+                    _ => Response::from_parts(parts, body),
+                });
+            }
+
+            let body = match axum::body::to_bytes(body, usize::MAX).await {
+                Ok(body) => body,
+                Err(e) => {
+                    // the headers describe the lost body, so none of the response is kept
+                    error!(error = ?e, "Unable to buffer response body for contract validation");
+                    let mut response = Response::new(Body::empty());
+                    *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+                    return Ok(response);
+                }
+            };
+
+            let violations = contract.check(&method, &path, parts.status, &parts.headers, &body);
+            if violations.is_empty() {
+                return Ok(Response::from_parts(parts, Body::from(body)));
+            }
+
+            for violation in &violations {
+                warn!(%violation, "Response does not conform to the OpenAPI contract");
+            }
+            match mode {
+                Mode::Log => Ok(Response::from_parts(parts, Body::from(body))),
+                Mode::Fail => Ok(violations_response(&violations)),
+            }
+        })
+    }
+}
+
+/// `500 Internal Server Error` listing `violations`, to replace a response in [`Mode::Fail`].
+fn violations_response(violations: &[Violation]) -> Response {
+    let message = violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut response = Response::new(Body::from(message));
+    *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+    response.headers_mut().insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static("text/plain; charset=utf-8"),
+    );
+    response
+}
+
+/// Send each request through `router` and panic if any response does not conform to the
+/// embedded [`SPEC`].
+///
+/// Intended for tests which replay a recorded set of requests against an `Api` implementation.
+pub async fn assert_conformance<I>(router: Router, requests: I)
+where
+    I: IntoIterator<Item = Request<Body>>,
+{
+    let contract = Contract::new().expect("Embedded OpenAPI document is invalid");
+    let mut violations = Vec::new();
+
+    for request in requests {
+        let method = request.method().clone();
+        let path = request.uri().path().to_string();
+        let response = router
+            .clone()
+            .oneshot(request)
+            .await
+            .unwrap_or_else(|e| match e {});
+        let (parts, body) = response.into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX)
+            .await
+            .expect("Unable to read response body");
+        violations.extend(contract.check(&method, &path, parts.status, &parts.headers, &body));
+    }
+
+    if !violations.is_empty() {
+        panic!(
+            "Responses do not conform to the OpenAPI contract:\n{}",
+            violations
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+}
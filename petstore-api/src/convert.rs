@@ -0,0 +1,204 @@
+//! Conversions between the models and other structs with the same fields, like database
+//! entities, enabled by the `conversion` feature.
+//!
+//! Both sides derive [`frunk::LabelledGeneric`], as the models do, and fields are matched by
+//! name, so a field of the target which the source doesn't have is a compile error of the
+//! conversion. Fields of the target which the source doesn't have are taken from a second struct
+//! with [`convert_with`]. [`convert`] drops the fields of the source which the target doesn't
+//! have; [`split`] puts them into a second struct instead, and doesn't build unless that struct
+//! lists all of them, so no field is lost without being named. The values of fields are
+//! converted with [`Coerce`], which covers identical types, integers of other widths, which fail
+//! when out of range, and nullable properties to and from the `Option` of a nullable column.
+//!
+//! ```
+//! # use petstore_api::{convert, models::Pet, types::Nullable};
+//! #[derive(frunk::LabelledGeneric)]
+//! struct PetEntity {
+//!     id: i32,
+//!     name: String,
+//!     tag: Option<String>,
+//! }
+//!
+//! #[derive(frunk::LabelledGeneric, Default)]
+//! struct Unstored {
+//!     attributes: Option<petstore_api::types::Object>,
+//! }
+//!
+//! let pet = Pet {
+//!     tag: Some(Nullable::Present("dog".to_string())),
+//!     ..Pet::new(1, "Rex".to_string())
+//! };
+//! let (entity, unstored): (PetEntity, Unstored) = convert::split(pet.clone()).unwrap();
+//! assert_eq!((entity.id, entity.tag.as_deref()), (1, Some("dog")));
+//! assert!(unstored.attributes.is_none());
+//!
+//! let converted: Pet = convert::convert_with(entity, Unstored::default()).unwrap();
+//! assert_eq!(converted, pet);
+//!
+//! let too_large = Pet::new(i64::MAX, "Rex".to_string());
+//! let error = convert::convert::<_, PetEntity, _>(too_large).err().unwrap();
+//! assert_eq!(error.field, "id");
+//! ```
+
+use std::{fmt, ops::Add};
+
+use frunk::{
+    hlist::{HCons, HNil},
+    labelled::{field_with_name, ByNameFieldPlucker, Field},
+    LabelledGeneric,
+};
+
+use crate::types::Nullable;
+
+/// Failure to convert the value of a field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    /// Name of the field.
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Convert `src` into the struct `Dst` with fields of the same names.
+pub fn convert<Src, Dst, Indices>(src: Src) -> Result<Dst, Error>
+where
+    Src: LabelledGeneric,
+    Dst: LabelledGeneric,
+    Src::Repr: CoerceFields<Dst::Repr, Indices>,
+{
+    let (repr, _) = LabelledGeneric::into(src).coerce_fields()?;
+    Ok(<Dst as LabelledGeneric>::from(repr))
+}
+
+/// Convert `src` into the struct `Dst`, and the fields `Dst` doesn't have into `Rest`, which has
+/// to have all of them.
+pub fn split<Src, Dst, Rest, Indices, RestIndices>(src: Src) -> Result<(Dst, Rest), Error>
+where
+    Src: LabelledGeneric,
+    Dst: LabelledGeneric,
+    Rest: LabelledGeneric,
+    Src::Repr: CoerceFields<Dst::Repr, Indices>,
+    <Src::Repr as CoerceFields<Dst::Repr, Indices>>::Remainder:
+        CoerceFields<Rest::Repr, RestIndices, Remainder = HNil>,
+{
+    let (repr, remainder) = LabelledGeneric::into(src).coerce_fields()?;
+    let (rest, HNil) = remainder.coerce_fields()?;
+    Ok((
+        <Dst as LabelledGeneric>::from(repr),
+        <Rest as LabelledGeneric>::from(rest),
+    ))
+}
+
+/// Convert `src` into the struct `Dst`, taking the fields `src` doesn't have from `rest`.
+pub fn convert_with<Src, Rest, Dst, Indices>(src: Src, rest: Rest) -> Result<Dst, Error>
+where
+    Src: LabelledGeneric,
+    Rest: LabelledGeneric,
+    Dst: LabelledGeneric,
+    Src::Repr: Add<Rest::Repr>,
+    <Src::Repr as Add<Rest::Repr>>::Output: CoerceFields<Dst::Repr, Indices>,
+{
+    let (repr, _) = (LabelledGeneric::into(src) + LabelledGeneric::into(rest)).coerce_fields()?;
+    Ok(<Dst as LabelledGeneric>::from(repr))
+}
+
+/// Conversion of the value of a field into the type of the field of the same name.
+pub trait Coerce<T> {
+    fn coerce(self) -> Result<T, String>;
+}
+
+impl<T> Coerce<T> for T {
+    fn coerce(self) -> Result<T, String> {
+        Ok(self)
+    }
+}
+
+macro_rules! coerce_integers {
+    ($($from:ty => $($to:ty),+;)+) => {
+        $($(
+            impl Coerce<$to> for $from {
+                fn coerce(self) -> Result<$to, String> {
+                    <$to>::try_from(self).map_err(|e| e.to_string())
+                }
+            }
+        )+)+
+    };
+}
+
+coerce_integers! {
+    i16 => i32, i64;
+    i32 => i16, i64;
+    i64 => i16, i32;
+}
+
+impl Coerce<f64> for f32 {
+    fn coerce(self) -> Result<f64, String> {
+        Ok(self.into())
+    }
+}
+
+/// A nullable property becomes a nullable column, which is NULL if the property is absent or
+/// `null`, like [`crate::types::into_nullable_column`].
+impl<T> Coerce<Option<T>> for Option<Nullable<T>> {
+    fn coerce(self) -> Result<Option<T>, String> {
+        Ok(self.and_then(Nullable::into_option))
+    }
+}
+
+/// A nullable column becomes a nullable property, which is `null` if the column is NULL, like
+/// [`crate::types::from_nullable_column`].
+impl<T> Coerce<Option<Nullable<T>>> for Option<T> {
+    fn coerce(self) -> Result<Option<Nullable<T>>, String> {
+        Ok(Some(Nullable::from_option(self)))
+    }
+}
+
+/// Conversion of the labelled representation of a struct into the representation `Target` of
+/// another, field by field, keeping the fields `Target` doesn't have as `Remainder`. `Indices`
+/// locate the fields of `Target` in `Self` and are inferred.
+pub trait CoerceFields<Target, Indices> {
+    type Remainder;
+
+    fn coerce_fields(self) -> Result<(Target, Self::Remainder), Error>;
+}
+
+impl<Source> CoerceFields<HNil, HNil> for Source {
+    type Remainder = Source;
+
+    fn coerce_fields(self) -> Result<(HNil, Source), Error> {
+        Ok((HNil, self))
+    }
+}
+
+impl<Source, Name, T, Tail, Index, TailIndices>
+    CoerceFields<HCons<Field<Name, T>, Tail>, HCons<Index, TailIndices>> for Source
+where
+    Source: ByNameFieldPlucker<Name, Index>,
+    Source::TargetValue: Coerce<T>,
+    Source::Remainder: CoerceFields<Tail, TailIndices>,
+{
+    type Remainder = <Source::Remainder as CoerceFields<Tail, TailIndices>>::Remainder;
+
+    fn coerce_fields(self) -> Result<(HCons<Field<Name, T>, Tail>, Self::Remainder), Error> {
+        let (field, remainder) = self.pluck_by_name();
+        let value = field.value.coerce().map_err(|message| Error {
+            field: field.name,
+            message,
+        })?;
+        let (tail, remainder) = remainder.coerce_fields()?;
+        Ok((
+            HCons {
+                head: field_with_name(field.name, value),
+                tail,
+            },
+            remainder,
+        ))
+    }
+}
@@ -0,0 +1,136 @@
+//! `Deprecation` and `Sunset` headers announcing the retirement of an API version.
+//!
+//! [`crate::server::Builder::deprecation`] sets them on every response of the operations of a
+//! version served next to its successor:
+//! - `Deprecation` ([RFC 9745](https://www.rfc-editor.org/rfc/rfc9745)) with the date the
+//!   version was deprecated, as `@<seconds since the epoch>`,
+//! - `Sunset` ([RFC 8594](https://www.rfc-editor.org/rfc/rfc8594)) with the date it stops being
+//!   served, as an HTTP date,
+//! - `Link` to the documentation of the deprecation and of the migration.
+
+use std::{
+    convert::Infallible,
+    task::{Context, Poll},
+};
+
+use axum::{body::Body, response::Response};
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use http::{header::LINK, HeaderName, HeaderValue, Request};
+use tower::{Layer, Service};
+
+/// Header with the date an API version was deprecated.
+pub const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+
+/// Header with the date an API version stops being served.
+pub const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Deprecation of an API version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deprecation {
+    pub since: DateTime<Utc>,
+    pub sunset: Option<DateTime<Utc>>,
+    pub link: Option<String>,
+}
+
+impl Deprecation {
+    /// Version deprecated `since`, without a date to stop serving it.
+    pub fn new(since: DateTime<Utc>) -> Self {
+        Deprecation {
+            since,
+            sunset: None,
+            link: None,
+        }
+    }
+
+    /// Stop serving the version at `sunset`.
+    pub fn sunset(mut self, sunset: DateTime<Utc>) -> Self {
+        self.sunset = Some(sunset);
+        self
+    }
+
+    /// Link the responses to the documentation of the deprecation at `link`.
+    pub fn link(mut self, link: impl Into<String>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+
+    /// Headers announcing the deprecation.
+    pub fn headers(&self) -> Vec<(HeaderName, HeaderValue)> {
+        let mut headers = vec![(DEPRECATION, format!("@{}", self.since.timestamp()))];
+        if let Some(sunset) = self.sunset {
+            headers.push((
+                SUNSET,
+                sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+            ));
+        }
+        if let Some(link) = &self.link {
+            headers.push((LINK, format!("<{}>; rel=\"deprecation\"", link)));
+            if self.sunset.is_some() {
+                headers.push((LINK, format!("<{}>; rel=\"sunset\"", link)));
+            }
+        }
+        headers
+            .into_iter()
+            .filter_map(|(name, value)| Some((name, HeaderValue::try_from(value).ok()?)))
+            .collect()
+    }
+
+    /// Layer setting the [`Deprecation::headers`] on responses.
+    pub fn layer(&self) -> DeprecationLayer {
+        DeprecationLayer {
+            headers: self.headers(),
+        }
+    }
+}
+
+/// Layer created by [`Deprecation::layer`].
+#[derive(Debug, Clone)]
+pub struct DeprecationLayer {
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+impl<S> Layer<S> for DeprecationLayer {
+    type Service = DeprecationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DeprecationService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service created by [`DeprecationLayer`].
+#[derive(Debug, Clone)]
+pub struct DeprecationService<S> {
+    inner: S,
+    layer: DeprecationLayer,
+}
+
+impl<S> Service<Request<Body>> for DeprecationService<S>
+where
+    S: Service<Request<Body>, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let future = self.inner.call(request);
+        let headers = self.layer.headers.clone();
+
+        Box::pin(async move {
+            let mut response = future.await?;
+            for (name, value) in headers {
+                response.headers_mut().append(name, value);
+            }
+            Ok(response)
+        })
+    }
+}
@@ -7,7 +7,7 @@
     unused_extern_crates,
     non_camel_case_types
 )]
-#![allow(unused_imports, unused_attributes)]
+#![allow(unused_attributes)]
 #![allow(clippy::derive_partial_eq_without_eq, clippy::disallowed_names)]
 
 use async_trait::async_trait;
@@ -17,19 +17,45 @@ use bytes::Bytes;
 use http::Method;
 use serde::{Deserialize, Serialize};
 
-use types::*;
+use crate::types::*;
 
 pub const BASE_PATH: &str = "/v1";
 pub const API_VERSION: &str = "1.0.0";
 
+/// SHA-256 of the OpenAPI document, templates, options and generator this crate was generated
+/// from, for `openapi-gen --check` to tell stale files from edited ones.
+pub const GENERATOR_INPUT_HASH: &str =
+    "3fe9026c51e4af55001bb9e314ada6e52e697723f5b1006f1ea11b8912fd371e";
+
+/// Version of the generator, covered by [`GENERATOR_INPUT_HASH`] with the `rustfmt`
+/// configuration formatting its output.
+pub const GENERATOR_VERSION: &str = "openapi-gen 1.0.0";
+
+/// The OpenAPI document this crate was generated from.
+pub const SPEC: &str = include_str!("../api/openapi.yaml");
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[must_use]
 #[allow(clippy::large_enum_variant)]
 pub enum CreatePetsResponse {
     /// Null response
-    Status201_NullResponse,
+    Status201_NullResponse {
+        headers: models::CreatePets201ResponseHeaders,
+    },
+    /// Invalid pet
+    Status400_InvalidPet {
+        body: models::Error,
+        headers: models::CreatePets400ResponseHeaders,
+    },
+    /// Too many requests
+    Status429_TooManyRequests {
+        headers: models::CreatePets429ResponseHeaders,
+    },
     /// unexpected error
-    Status0_UnexpectedError(models::Error),
+    Status0_UnexpectedError {
+        body: models::Error,
+        headers: models::CreatePetsDefaultResponseHeaders,
+    },
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -38,11 +64,14 @@ pub enum CreatePetsResponse {
 pub enum ListPetsResponse {
     /// A paged array of pets
     Status200_APagedArrayOfPets {
-        body: Vec<models::Pet>,
-        x_next: Option<String>,
+        body: ArrayBody<models::Pet>,
+        headers: models::ListPets200ResponseHeaders,
     },
     /// unexpected error
-    Status0_UnexpectedError(models::Error),
+    Status0_UnexpectedError {
+        body: models::Error,
+        headers: models::ListPetsDefaultResponseHeaders,
+    },
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -50,9 +79,101 @@ pub enum ListPetsResponse {
 #[allow(clippy::large_enum_variant)]
 pub enum ShowPetByIdResponse {
     /// Expected response to a valid request
-    Status200_ExpectedResponseToAValidRequest(models::Pet),
+    Status200_ExpectedResponseToAValidRequest {
+        body: models::Pet,
+        headers: models::ShowPetById200ResponseHeaders,
+    },
+    /// Pet not found
+    Status404_PetNotFound {
+        body: models::Error,
+        headers: models::ShowPetById404ResponseHeaders,
+    },
+    /// unexpected error
+    Status0_UnexpectedError {
+        body: models::Error,
+        headers: models::ShowPetByIdDefaultResponseHeaders,
+    },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum ShowPetPhotoResponse {
+    /// The photo
+    Status200_ThePhoto {
+        body: BinaryBody,
+        headers: models::ShowPetPhoto200ResponseHeaders,
+    },
+    /// A range of the photo
+    Status206_ARangeOfThePhoto {
+        body: BinaryBody,
+        headers: models::ShowPetPhoto206ResponseHeaders,
+    },
+    /// Pet or photo not found
+    Status404_PetOrPhotoNotFound {
+        body: models::Error,
+        headers: models::ShowPetPhoto404ResponseHeaders,
+    },
+    /// Range not satisfiable
+    Status416_RangeNotSatisfiable {
+        headers: models::ShowPetPhoto416ResponseHeaders,
+    },
+    /// unexpected error
+    Status0_UnexpectedError {
+        body: models::Error,
+        headers: models::ShowPetPhotoDefaultResponseHeaders,
+    },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum UploadPetDocumentsResponse {
+    /// Documents stored
+    Status201_DocumentsStored {
+        body: ArrayBody<models::Document>,
+        headers: models::UploadPetDocuments201ResponseHeaders,
+    },
+    /// Invalid upload
+    Status400_InvalidUpload {
+        body: models::Error,
+        headers: models::UploadPetDocuments400ResponseHeaders,
+    },
+    /// Pet not found
+    Status404_PetNotFound {
+        body: models::Error,
+        headers: models::UploadPetDocuments404ResponseHeaders,
+    },
+    /// unexpected error
+    Status0_UnexpectedError {
+        body: models::Error,
+        headers: models::UploadPetDocumentsDefaultResponseHeaders,
+    },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum UploadPetPhotoResponse {
+    /// Photo stored
+    Status204_PhotoStored {
+        headers: models::UploadPetPhoto204ResponseHeaders,
+    },
+    /// Invalid upload
+    Status400_InvalidUpload {
+        body: models::Error,
+        headers: models::UploadPetPhoto400ResponseHeaders,
+    },
+    /// Pet not found
+    Status404_PetNotFound {
+        body: models::Error,
+        headers: models::UploadPetPhoto404ResponseHeaders,
+    },
     /// unexpected error
-    Status0_UnexpectedError(models::Error),
+    Status0_UnexpectedError {
+        body: models::Error,
+        headers: models::UploadPetPhotoDefaultResponseHeaders,
+    },
 }
 
 /// API
@@ -67,6 +188,8 @@ pub trait Api {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        identity: Option<ClientIdentity>,
+        header_params: models::CreatePetsHeaderParams,
         body: models::Pet,
     ) -> Result<CreatePetsResponse, String>;
 
@@ -78,6 +201,8 @@ pub trait Api {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        identity: Option<ClientIdentity>,
+        cookie_params: models::ListPetsCookieParams,
         query_params: models::ListPetsQueryParams,
     ) -> Result<ListPetsResponse, String>;
 
@@ -89,15 +214,95 @@ pub trait Api {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        identity: Option<ClientIdentity>,
         path_params: models::ShowPetByIdPathParams,
     ) -> Result<ShowPetByIdResponse, String>;
+
+    /// Download the photo of a pet.
+    ///
+    /// ShowPetPhoto - GET /v1/pets/{petId}/photo
+    async fn show_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<ClientIdentity>,
+        header_params: models::ShowPetPhotoHeaderParams,
+        path_params: models::ShowPetPhotoPathParams,
+    ) -> Result<ShowPetPhotoResponse, String>;
+
+    /// Upload documents of a pet.
+    ///
+    /// UploadPetDocuments - POST /v1/pets/{petId}/documents
+    async fn upload_pet_documents(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<ClientIdentity>,
+        path_params: models::UploadPetDocumentsPathParams,
+        body: models::UploadPetDocumentsForm,
+    ) -> Result<UploadPetDocumentsResponse, String>;
+
+    /// Upload the photo of a pet.
+    ///
+    /// UploadPetPhoto - POST /v1/pets/{petId}/photo
+    async fn upload_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<ClientIdentity>,
+        path_params: models::UploadPetPhotoPathParams,
+        body: FileUpload,
+    ) -> Result<UploadPetPhotoResponse, String>;
 }
 
 #[cfg(feature = "server")]
 pub mod server;
 
+#[cfg(feature = "server")]
+pub mod blob;
+
+#[cfg(feature = "server")]
+pub mod cache;
+
+#[cfg(feature = "server")]
+pub mod contract;
+
+#[cfg(feature = "conversion")]
+pub mod convert;
+
+#[cfg(feature = "server")]
+pub mod deprecation;
+
+#[cfg(feature = "server")]
+pub mod security;
+
+#[cfg(feature = "server")]
+pub mod serve;
+
+#[cfg(feature = "server")]
+pub mod telemetry;
+
 pub mod models;
+pub mod style;
 pub mod types;
+pub mod xml;
 
 #[cfg(feature = "server")]
 pub(crate) mod header;
+
+/// API version 1.0.0 of the crate root, next to the other versions.
+pub mod v1 {
+    pub use crate::{
+        models, Api, CreatePetsResponse, ListPetsResponse, ShowPetByIdResponse,
+        ShowPetPhotoResponse, UploadPetDocumentsResponse, UploadPetPhotoResponse, API_VERSION,
+        BASE_PATH, SPEC,
+    };
+
+    #[cfg(feature = "server")]
+    pub use crate::server;
+}
+
+pub mod v2;
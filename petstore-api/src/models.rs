@@ -5,10 +5,103 @@ use validator::Validate;
 
 #[cfg(feature = "server")]
 use crate::header;
-use crate::{models, types::*};
+use crate::models;
+use crate::{style, types::*, xml};
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct CreatePetsHeaderParams {
+    /// Key identifying retries of the same request
+    #[validate(length(min = 1, max = 64))]
+    pub idempotency_key: Option<String>,
+}
+
+/// Headers of the 201 response of CreatePets.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CreatePets201ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl CreatePets201ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 400 response of CreatePets.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CreatePets400ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl CreatePets400ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 429 response of CreatePets.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CreatePets429ResponseHeaders {
+    /// Seconds until the next request is accepted
+    #[serde(rename = "Retry-After")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<i32>,
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl CreatePets429ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            retry_after: None,
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the default response of CreatePets.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CreatePetsDefaultResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl CreatePetsDefaultResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct ListPetsCookieParams {
+    /// Session of the client
+    #[validate(length(max = 128))]
+    pub session: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
 pub struct ListPetsQueryParams {
     /// How many items to return at one time (max 100)
     #[serde(rename = "limit")]
@@ -17,130 +110,1216 @@ pub struct ListPetsQueryParams {
     pub limit: Option<i32>,
 }
 
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
-#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct ShowPetByIdPathParams {
-    /// The id of the pet to retrieve
-    pub pet_id: String,
+/// Headers of the 200 response of ListPets.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ListPets200ResponseHeaders {
+    /// A link to the next page of responses
+    #[serde(rename = "x-next")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x_next: Option<String>,
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ListPets200ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            x_next: None,
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the default response of ListPets.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ListPetsDefaultResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ListPetsDefaultResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct ShowPetByIdPathParams {
+    /// The id of the pet to retrieve
+    #[validate(range(min = 1))]
+    pub pet_id: i64,
+}
+
+/// Headers of the 200 response of ShowPetById.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetById200ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetById200ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 404 response of ShowPetById.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetById404ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetById404ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the default response of ShowPetById.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetByIdDefaultResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetByIdDefaultResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct ShowPetPhotoHeaderParams {
+    /// Byte range of the photo to return, e.g. `bytes=0-1023`
+    #[validate(length(max = 128))]
+    pub range: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct ShowPetPhotoPathParams {
+    /// The id of the pet
+    #[validate(range(min = 1))]
+    pub pet_id: i64,
+}
+
+/// Headers of the 200 response of ShowPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetPhoto200ResponseHeaders {
+    /// Size of the photo in bytes
+    #[serde(rename = "Content-Length")]
+    pub content_length: i64,
+    /// Unit of the ranges which can be requested
+    #[serde(rename = "Accept-Ranges")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_ranges: Option<String>,
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetPhoto200ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(content_length: i64) -> Self {
+        Self {
+            content_length,
+            accept_ranges: None,
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 206 response of ShowPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetPhoto206ResponseHeaders {
+    /// Size of the range in bytes
+    #[serde(rename = "Content-Length")]
+    pub content_length: i64,
+    /// Position of the range within the photo
+    #[serde(rename = "Content-Range")]
+    pub content_range: String,
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetPhoto206ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(content_length: i64, content_range: String) -> Self {
+        Self {
+            content_length,
+            content_range,
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 404 response of ShowPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetPhoto404ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetPhoto404ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 416 response of ShowPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetPhoto416ResponseHeaders {
+    /// Size of the photo, as `bytes */size`
+    #[serde(rename = "Content-Range")]
+    pub content_range: String,
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetPhoto416ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(content_range: String) -> Self {
+        Self {
+            content_range,
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the default response of ShowPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetPhotoDefaultResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetPhotoDefaultResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct UploadPetDocumentsPathParams {
+    /// The id of the pet
+    #[validate(range(min = 1))]
+    pub pet_id: i64,
+}
+
+/// The `multipart/form-data` request body of UploadPetDocuments.
+#[derive(Debug, Clone, PartialEq, validator::Validate)]
+pub struct UploadPetDocumentsForm {
+    /// What the documents are about
+    #[validate(length(max = 200))]
+    pub description: Option<String>,
+    /// Photos of the pet, at most 2 MiB each
+    #[validate(length(min = 1, max = 5))]
+    pub photos: Vec<FilePart>,
+    /// Vaccination certificate, at most 1 MiB
+    pub certificate: Option<FilePart>,
+}
+
+/// Headers of the 201 response of UploadPetDocuments.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetDocuments201ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetDocuments201ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 400 response of UploadPetDocuments.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetDocuments400ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetDocuments400ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 404 response of UploadPetDocuments.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetDocuments404ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetDocuments404ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the default response of UploadPetDocuments.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetDocumentsDefaultResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetDocumentsDefaultResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct UploadPetPhotoPathParams {
+    /// The id of the pet
+    #[validate(range(min = 1))]
+    pub pet_id: i64,
+}
+
+/// Headers of the 204 response of UploadPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetPhoto204ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetPhoto204ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 400 response of UploadPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetPhoto400ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetPhoto400ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 404 response of UploadPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetPhoto404ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetPhoto404ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the default response of UploadPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetPhotoDefaultResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetPhotoDefaultResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct Cat {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "name")]
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+
+    #[serde(rename = "tag")]
+    #[validate(custom = "validate_nullable_cat_tag")]
+    #[serde(deserialize_with = "deserialize_optional_nullable")]
+    #[serde(default = "default_optional_nullable")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<Nullable<String>>,
+
+    /// Free-form attributes of the pet
+    #[serde(rename = "attributes")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<crate::types::Object>,
+
+    #[serde(rename = "hunts")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hunts: Option<bool>,
+}
+
+#[allow(clippy::unnecessary_cast)]
+fn validate_nullable_cat_tag(
+    value: &Nullable<String>,
+) -> std::result::Result<(), validator::ValidationError> {
+    let Nullable::Present(value) = value else {
+        return std::result::Result::Ok(());
+    };
+    if !validator::validate_length(value, None, Some(50), None) {
+        return Err(validator::ValidationError::new("length"));
+    }
+    std::result::Result::Ok(())
+}
+
+impl Cat {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(id: i64, name: String) -> Cat {
+        Cat {
+            id,
+            name,
+            tag: None,
+            attributes: None,
+            hunts: None,
+        }
+    }
+}
+
+/// Converts the Cat value to the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/, which is also the value part of the
+/// `style=form, explode=false` query parameter representation
+impl std::fmt::Display for Cat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &style::to_string(self, style::Style::Simple, false)
+                .expect("Cat is always serializable"),
+        )
+    }
+}
+
+/// Converts the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/ to a Cat value
+impl std::str::FromStr for Cat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        style::from_str(s, style::Style::Simple, false).map_err(|e| e.to_string())
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<Cat> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<Cat>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<Cat>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for Cat - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Cat> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => match <Cat as std::str::FromStr>::from_str(value) {
+                std::result::Result::Ok(value) => {
+                    std::result::Result::Ok(header::IntoHeaderValue(value))
+                }
+                std::result::Result::Err(err) => std::result::Result::Err(format!(
+                    "Unable to convert header value '{}' into Cat - {}",
+                    value, err
+                )),
+            },
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+/// Representation of Cat values in XML, following the `xml` objects of the schema
+impl xml::XmlSchema for Cat {
+    fn xml() -> xml::Xml {
+        xml::Xml {
+            name: "Cat",
+            namespace: None,
+            prefix: None,
+            kind: xml::Kind::Object(&[
+                xml::Property {
+                    name: "id",
+                    xml_name: "id",
+                    attribute: true,
+                    array: None,
+                    schema: <i64 as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "name",
+                    xml_name: "name",
+                    attribute: false,
+                    array: None,
+                    schema: <String as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "tag",
+                    xml_name: "tag",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<Nullable<String>> as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "attributes",
+                    xml_name: "attributes",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<crate::types::Object> as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "hunts",
+                    xml_name: "hunts",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<bool> as xml::XmlSchema>::xml,
+                },
+            ]),
+        }
+    }
+}
+
+/// Pet which is a dog, a cat, or both
+/// Any of:
+/// - `models::Dog`
+/// - `models::Cat`
+///
+/// The value may be valid for several of the schemas, so it is kept as is rather than read as
+/// one of them, and validated against each. The methods named after the schemas read it as one.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Companion(serde_json::Value);
+
+impl Companion {
+    /// The value as a `models::Dog`, if it deserializes into one.
+    pub fn dog(&self) -> Option<models::Dog> {
+        serde_json::from_value(self.0.clone()).ok()
+    }
+
+    /// The value as a `models::Cat`, if it deserializes into one.
+    pub fn cat(&self) -> Option<models::Cat> {
+        serde_json::from_value(self.0.clone()).ok()
+    }
+
+    /// Add the representation of a schema the value is valid for: the properties of objects
+    /// are merged, other values are kept from the first schema.
+    fn merge(value: Option<serde_json::Value>, member: serde_json::Value) -> serde_json::Value {
+        match (value, member) {
+            (Some(serde_json::Value::Object(mut entries)), serde_json::Value::Object(member)) => {
+                entries.extend(member);
+                serde_json::Value::Object(entries)
+            }
+            (Some(value), _) => value,
+            (None, member) => member,
+        }
+    }
+}
+
+impl From<models::Dog> for Companion {
+    fn from(value: models::Dog) -> Self {
+        Companion(serde_json::to_value(value).expect("models::Dog is always serializable"))
+    }
+}
+
+impl From<models::Cat> for Companion {
+    fn from(value: models::Cat) -> Self {
+        Companion(serde_json::to_value(value).expect("models::Cat is always serializable"))
+    }
+}
+
+impl validator::Validate for Companion {
+    fn validate(&self) -> std::result::Result<(), validator::ValidationErrors> {
+        if let std::option::Option::Some(value) = self.dog() {
+            if value.validate().is_ok() {
+                return std::result::Result::Ok(());
+            }
+        }
+        if let std::option::Option::Some(value) = self.cat() {
+            if value.validate().is_ok() {
+                return std::result::Result::Ok(());
+            }
+        }
+        let mut errors = validator::ValidationErrors::new();
+        errors.add("Companion", validator::ValidationError::new("any_of"));
+        std::result::Result::Err(errors)
+    }
+}
+
+/// Converts the Companion value to the `style=simple, explode=false` representation
+/// specified in https://swagger.io/docs/specification/serialization/
+impl std::fmt::Display for Companion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &style::to_string(self, style::Style::Simple, false)
+                .expect("Companion is always serializable"),
+        )
+    }
+}
+
+/// Converts the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/ to a Companion value, with
+/// what each of the schemas reads from it
+impl std::str::FromStr for Companion {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut value = None;
+        if let std::result::Result::Ok(member) = s.parse::<models::Dog>() {
+            let member = serde_json::to_value(member).map_err(|e| e.to_string())?;
+            value = Some(Companion::merge(value, member));
+        }
+        if let std::result::Result::Ok(member) = s.parse::<models::Cat>() {
+            let member = serde_json::to_value(member).map_err(|e| e.to_string())?;
+            value = Some(Companion::merge(value, member));
+        }
+        value
+            .map(Companion)
+            .ok_or_else(|| format!("Value not valid for Companion: {}", s))
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<Companion> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<Companion>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<Companion>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for Companion - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Companion> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <Companion as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        "Unable to convert header value '{}' into Companion - {}",
+                        value, err
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+/// Representation of Companion values in XML, following the `xml` objects of the schema
+impl xml::XmlSchema for Companion {
+    fn xml() -> xml::Xml {
+        xml::Xml {
+            name: "Companion",
+            namespace: None,
+            prefix: None,
+            kind: xml::Kind::Any,
+        }
+    }
+}
+
+/// A file stored with a pet
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct Document {
+    /// Name of the form part the file was sent in
+    #[serde(rename = "part")]
+    pub part: String,
+
+    #[serde(rename = "fileName")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+
+    #[serde(rename = "contentType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+
+    #[serde(rename = "size")]
+    #[validate(range(min = 0))]
+    pub size: i64,
+}
+
+impl Document {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(part: String, size: i64) -> Document {
+        Document {
+            part,
+            file_name: None,
+            content_type: None,
+            size,
+        }
+    }
+}
+
+/// Converts the Document value to the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/, which is also the value part of the
+/// `style=form, explode=false` query parameter representation
+impl std::fmt::Display for Document {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &style::to_string(self, style::Style::Simple, false)
+                .expect("Document is always serializable"),
+        )
+    }
+}
+
+/// Converts the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/ to a Document value
+impl std::str::FromStr for Document {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        style::from_str(s, style::Style::Simple, false).map_err(|e| e.to_string())
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<Document> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<Document>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<Document>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for Document - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Document> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <Document as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        "Unable to convert header value '{}' into Document - {}",
+                        value, err
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+/// Representation of Document values in XML, following the `xml` objects of the schema
+impl xml::XmlSchema for Document {
+    fn xml() -> xml::Xml {
+        xml::Xml {
+            name: "Document",
+            namespace: None,
+            prefix: None,
+            kind: xml::Kind::Object(&[
+                xml::Property {
+                    name: "part",
+                    xml_name: "part",
+                    attribute: false,
+                    array: None,
+                    schema: <String as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "fileName",
+                    xml_name: "fileName",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<String> as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "contentType",
+                    xml_name: "contentType",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<String> as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "size",
+                    xml_name: "size",
+                    attribute: false,
+                    array: None,
+                    schema: <i64 as xml::XmlSchema>::xml,
+                },
+            ]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct Dog {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "name")]
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+
+    #[serde(rename = "tag")]
+    #[validate(custom = "validate_nullable_dog_tag")]
+    #[serde(deserialize_with = "deserialize_optional_nullable")]
+    #[serde(default = "default_optional_nullable")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<Nullable<String>>,
+
+    /// Free-form attributes of the pet
+    #[serde(rename = "attributes")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<crate::types::Object>,
+
+    #[serde(rename = "bark")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bark: Option<bool>,
+}
+
+#[allow(clippy::unnecessary_cast)]
+fn validate_nullable_dog_tag(
+    value: &Nullable<String>,
+) -> std::result::Result<(), validator::ValidationError> {
+    let Nullable::Present(value) = value else {
+        return std::result::Result::Ok(());
+    };
+    if !validator::validate_length(value, None, Some(50), None) {
+        return Err(validator::ValidationError::new("length"));
+    }
+    std::result::Result::Ok(())
+}
+
+impl Dog {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(id: i64, name: String) -> Dog {
+        Dog {
+            id,
+            name,
+            tag: None,
+            attributes: None,
+            bark: None,
+        }
+    }
+}
+
+/// Converts the Dog value to the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/, which is also the value part of the
+/// `style=form, explode=false` query parameter representation
+impl std::fmt::Display for Dog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &style::to_string(self, style::Style::Simple, false)
+                .expect("Dog is always serializable"),
+        )
+    }
+}
+
+/// Converts the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/ to a Dog value
+impl std::str::FromStr for Dog {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        style::from_str(s, style::Style::Simple, false).map_err(|e| e.to_string())
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<Dog> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<Dog>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<Dog>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for Dog - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Dog> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => match <Dog as std::str::FromStr>::from_str(value) {
+                std::result::Result::Ok(value) => {
+                    std::result::Result::Ok(header::IntoHeaderValue(value))
+                }
+                std::result::Result::Err(err) => std::result::Result::Err(format!(
+                    "Unable to convert header value '{}' into Dog - {}",
+                    value, err
+                )),
+            },
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+/// Representation of Dog values in XML, following the `xml` objects of the schema
+impl xml::XmlSchema for Dog {
+    fn xml() -> xml::Xml {
+        xml::Xml {
+            name: "Dog",
+            namespace: None,
+            prefix: None,
+            kind: xml::Kind::Object(&[
+                xml::Property {
+                    name: "id",
+                    xml_name: "id",
+                    attribute: true,
+                    array: None,
+                    schema: <i64 as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "name",
+                    xml_name: "name",
+                    attribute: false,
+                    array: None,
+                    schema: <String as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "tag",
+                    xml_name: "tag",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<Nullable<String>> as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "attributes",
+                    xml_name: "attributes",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<crate::types::Object> as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "bark",
+                    xml_name: "bark",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<bool> as xml::XmlSchema>::xml,
+                },
+            ]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct Error {
+    #[serde(rename = "code")]
+    pub code: i32,
+
+    #[serde(rename = "message")]
+    pub message: String,
+
+    /// Id of the request, as in its `X-Request-Id` header
+    #[serde(rename = "requestId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl Error {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(code: i32, message: String) -> Error {
+        Error {
+            code,
+            message,
+            request_id: None,
+        }
+    }
+}
+
+/// Converts the Error value to the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/, which is also the value part of the
+/// `style=form, explode=false` query parameter representation
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &style::to_string(self, style::Style::Simple, false)
+                .expect("Error is always serializable"),
+        )
+    }
+}
+
+/// Converts the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/ to a Error value
+impl std::str::FromStr for Error {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        style::from_str(s, style::Style::Simple, false).map_err(|e| e.to_string())
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<Error> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<Error>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<Error>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for Error - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Error> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => match <Error as std::str::FromStr>::from_str(value) {
+                std::result::Result::Ok(value) => {
+                    std::result::Result::Ok(header::IntoHeaderValue(value))
+                }
+                std::result::Result::Err(err) => std::result::Result::Err(format!(
+                    "Unable to convert header value '{}' into Error - {}",
+                    value, err
+                )),
+            },
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+/// Representation of Error values in XML, following the `xml` objects of the schema
+impl xml::XmlSchema for Error {
+    fn xml() -> xml::Xml {
+        xml::Xml {
+            name: "error",
+            namespace: None,
+            prefix: None,
+            kind: xml::Kind::Object(&[
+                xml::Property {
+                    name: "code",
+                    xml_name: "code",
+                    attribute: false,
+                    array: None,
+                    schema: <i32 as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "message",
+                    xml_name: "message",
+                    attribute: false,
+                    array: None,
+                    schema: <String as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "requestId",
+                    xml_name: "requestId",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<String> as xml::XmlSchema>::xml,
+                },
+            ]),
+        }
+    }
 }
 
+/// Owner of pets, with any other properties of the owner kept as is
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct Error {
-    #[serde(rename = "code")]
-    pub code: i32,
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct Owner {
+    #[serde(rename = "name")]
+    #[validate(length(min = 1))]
+    pub name: String,
 
-    #[serde(rename = "message")]
-    pub message: String,
+    /// Pets of the owner by their names
+    #[serde(rename = "pets")]
+    #[validate(custom = "validate_map_values")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pets: Option<std::collections::HashMap<String, models::Pet>>,
+
+    /// Properties not declared in the schema
+    #[serde(flatten)]
+    pub additional_properties: std::collections::HashMap<String, crate::types::Object>,
 }
 
-impl Error {
+impl Owner {
     #[allow(clippy::new_without_default, clippy::too_many_arguments)]
-    pub fn new(code: i32, message: String) -> Error {
-        Error { code, message }
+    pub fn new(name: String) -> Owner {
+        Owner {
+            name,
+            pets: None,
+            additional_properties: std::collections::HashMap::new(),
+        }
     }
 }
 
-/// Converts the Error value to the Query Parameters representation (style=form, explode=false)
-/// specified in https://swagger.io/docs/specification/serialization/
-/// Should be implemented in a serde serializer
-impl std::string::ToString for Error {
-    fn to_string(&self) -> String {
-        let params: Vec<Option<String>> = vec![
-            Some("code".to_string()),
-            Some(self.code.to_string()),
-            Some("message".to_string()),
-            Some(self.message.to_string()),
-        ];
-
-        params.into_iter().flatten().collect::<Vec<_>>().join(",")
+/// Converts the Owner value to the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/, which is also the value part of the
+/// `style=form, explode=false` query parameter representation
+impl std::fmt::Display for Owner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &style::to_string(self, style::Style::Simple, false)
+                .expect("Owner is always serializable"),
+        )
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a Error value
-/// as specified in https://swagger.io/docs/specification/serialization/
-/// Should be implemented in a serde deserializer
-impl std::str::FromStr for Error {
+/// Converts the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/ to a Owner value
+impl std::str::FromStr for Owner {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        /// An intermediate representation of the struct to use for parsing.
-        #[derive(Default)]
-        #[allow(dead_code)]
-        struct IntermediateRep {
-            pub code: Vec<i32>,
-            pub message: Vec<String>,
-        }
-
-        let mut intermediate_rep = IntermediateRep::default();
-
-        // Parse into intermediate representation
-        let mut string_iter = s.split(',');
-        let mut key_result = string_iter.next();
-
-        while key_result.is_some() {
-            let val = match string_iter.next() {
-                Some(x) => x,
-                None => {
-                    return std::result::Result::Err(
-                        "Missing value while parsing Error".to_string(),
-                    )
-                }
-            };
-
-            if let Some(key) = key_result {
-                #[allow(clippy::match_single_binding)]
-                match key {
-                    #[allow(clippy::redundant_clone)]
-                    "code" => intermediate_rep.code.push(
-                        <i32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
-                    ),
-                    #[allow(clippy::redundant_clone)]
-                    "message" => intermediate_rep.message.push(
-                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
-                    ),
-                    _ => {
-                        return std::result::Result::Err(
-                            "Unexpected key while parsing Error".to_string(),
-                        )
-                    }
-                }
-            }
-
-            // Get the next key
-            key_result = string_iter.next();
-        }
-
-        // Use the intermediate representation to return the struct
-        std::result::Result::Ok(Error {
-            code: intermediate_rep
-                .code
-                .into_iter()
-                .next()
-                .ok_or_else(|| "code missing in Error".to_string())?,
-            message: intermediate_rep
-                .message
-                .into_iter()
-                .next()
-                .ok_or_else(|| "message missing in Error".to_string())?,
-        })
+        style::from_str(s, style::Style::Simple, false).map_err(|e| e.to_string())
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<Error> and HeaderValue
+// Methods for converting between header::IntoHeaderValue<Owner> and HeaderValue
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<header::IntoHeaderValue<Error>> for HeaderValue {
+impl std::convert::TryFrom<header::IntoHeaderValue<Owner>> for HeaderValue {
     type Error = String;
 
     fn try_from(
-        hdr_value: header::IntoHeaderValue<Error>,
+        hdr_value: header::IntoHeaderValue<Owner>,
     ) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for Error - value: {} is invalid {}",
+                "Invalid header value for Owner - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -148,17 +1327,17 @@ impl std::convert::TryFrom<header::IntoHeaderValue<Error>> for HeaderValue {
 }
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Error> {
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Owner> {
     type Error = String;
 
     fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
-            std::result::Result::Ok(value) => match <Error as std::str::FromStr>::from_str(value) {
+            std::result::Result::Ok(value) => match <Owner as std::str::FromStr>::from_str(value) {
                 std::result::Result::Ok(value) => {
                     std::result::Result::Ok(header::IntoHeaderValue(value))
                 }
                 std::result::Result::Err(err) => std::result::Result::Err(format!(
-                    "Unable to convert header value '{}' into Error - {}",
+                    "Unable to convert header value '{}' into Owner - {}",
                     value, err
                 )),
             },
@@ -170,18 +1349,68 @@ impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Error> {
     }
 }
 
+/// Representation of Owner values in XML, following the `xml` objects of the schema
+impl xml::XmlSchema for Owner {
+    fn xml() -> xml::Xml {
+        xml::Xml {
+            name: "Owner",
+            namespace: None,
+            prefix: None,
+            kind: xml::Kind::Object(&[
+                xml::Property {
+                    name: "name",
+                    xml_name: "name",
+                    attribute: false,
+                    array: None,
+                    schema: <String as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "pets",
+                    xml_name: "pets",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<std::collections::HashMap<String, models::Pet>> as xml::XmlSchema>::xml,
+                },
+            ]),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
 pub struct Pet {
     #[serde(rename = "id")]
     pub id: i64,
 
     #[serde(rename = "name")]
+    #[validate(length(min = 1, max = 100))]
     pub name: String,
 
     #[serde(rename = "tag")]
+    #[validate(custom = "validate_nullable_pet_tag")]
+    #[serde(deserialize_with = "deserialize_optional_nullable")]
+    #[serde(default = "default_optional_nullable")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<Nullable<String>>,
+
+    /// Free-form attributes of the pet
+    #[serde(rename = "attributes")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tag: Option<String>,
+    pub attributes: Option<crate::types::Object>,
+}
+
+#[allow(clippy::unnecessary_cast)]
+fn validate_nullable_pet_tag(
+    value: &Nullable<String>,
+) -> std::result::Result<(), validator::ValidationError> {
+    let Nullable::Present(value) = value else {
+        return std::result::Result::Ok(());
+    };
+    if !validator::validate_length(value, None, Some(50), None) {
+        return Err(validator::ValidationError::new("length"));
+    }
+    std::result::Result::Ok(())
 }
 
 impl Pet {
@@ -191,100 +1420,30 @@ impl Pet {
             id,
             name,
             tag: None,
+            attributes: None,
         }
     }
 }
 
-/// Converts the Pet value to the Query Parameters representation (style=form, explode=false)
-/// specified in https://swagger.io/docs/specification/serialization/
-/// Should be implemented in a serde serializer
-impl std::string::ToString for Pet {
-    fn to_string(&self) -> String {
-        let params: Vec<Option<String>> = vec![
-            Some("id".to_string()),
-            Some(self.id.to_string()),
-            Some("name".to_string()),
-            Some(self.name.to_string()),
-            self.tag
-                .as_ref()
-                .map(|tag| ["tag".to_string(), tag.to_string()].join(",")),
-        ];
-
-        params.into_iter().flatten().collect::<Vec<_>>().join(",")
-    }
-}
-
-/// Converts Query Parameters representation (style=form, explode=false) to a Pet value
-/// as specified in https://swagger.io/docs/specification/serialization/
-/// Should be implemented in a serde deserializer
+/// Converts the Pet value to the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/, which is also the value part of the
+/// `style=form, explode=false` query parameter representation
+impl std::fmt::Display for Pet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &style::to_string(self, style::Style::Simple, false)
+                .expect("Pet is always serializable"),
+        )
+    }
+}
+
+/// Converts the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/ to a Pet value
 impl std::str::FromStr for Pet {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        /// An intermediate representation of the struct to use for parsing.
-        #[derive(Default)]
-        #[allow(dead_code)]
-        struct IntermediateRep {
-            pub id: Vec<i64>,
-            pub name: Vec<String>,
-            pub tag: Vec<String>,
-        }
-
-        let mut intermediate_rep = IntermediateRep::default();
-
-        // Parse into intermediate representation
-        let mut string_iter = s.split(',');
-        let mut key_result = string_iter.next();
-
-        while key_result.is_some() {
-            let val = match string_iter.next() {
-                Some(x) => x,
-                None => {
-                    return std::result::Result::Err("Missing value while parsing Pet".to_string())
-                }
-            };
-
-            if let Some(key) = key_result {
-                #[allow(clippy::match_single_binding)]
-                match key {
-                    #[allow(clippy::redundant_clone)]
-                    "id" => intermediate_rep.id.push(
-                        <i64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
-                    ),
-                    #[allow(clippy::redundant_clone)]
-                    "name" => intermediate_rep.name.push(
-                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
-                    ),
-                    #[allow(clippy::redundant_clone)]
-                    "tag" => intermediate_rep.tag.push(
-                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
-                    ),
-                    _ => {
-                        return std::result::Result::Err(
-                            "Unexpected key while parsing Pet".to_string(),
-                        )
-                    }
-                }
-            }
-
-            // Get the next key
-            key_result = string_iter.next();
-        }
-
-        // Use the intermediate representation to return the struct
-        std::result::Result::Ok(Pet {
-            id: intermediate_rep
-                .id
-                .into_iter()
-                .next()
-                .ok_or_else(|| "id missing in Pet".to_string())?,
-            name: intermediate_rep
-                .name
-                .into_iter()
-                .next()
-                .ok_or_else(|| "name missing in Pet".to_string())?,
-            tag: intermediate_rep.tag.into_iter().next(),
-        })
+        style::from_str(s, style::Style::Simple, false).map_err(|e| e.to_string())
     }
 }
 
@@ -328,3 +1487,502 @@ impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Pet> {
         }
     }
 }
+
+/// Representation of Pet values in XML, following the `xml` objects of the schema
+impl xml::XmlSchema for Pet {
+    fn xml() -> xml::Xml {
+        xml::Xml {
+            name: "pet",
+            namespace: None,
+            prefix: None,
+            kind: xml::Kind::Object(&[
+                xml::Property {
+                    name: "id",
+                    xml_name: "id",
+                    attribute: true,
+                    array: None,
+                    schema: <i64 as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "name",
+                    xml_name: "name",
+                    attribute: false,
+                    array: None,
+                    schema: <String as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "tag",
+                    xml_name: "tag",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<Nullable<String>> as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "attributes",
+                    xml_name: "attributes",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<crate::types::Object> as xml::XmlSchema>::xml,
+                },
+            ]),
+        }
+    }
+}
+
+/// Pet referred to by its id or by its name
+/// One of the following, the first one the value deserializes into:
+/// - `i64`
+/// - `String`
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+#[cfg_attr(feature = "conversion", derive(frunk_enum_derive::LabelledGenericEnum))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub enum PetReference {
+    I64(i64),
+    String(String),
+}
+
+impl validator::Validate for PetReference {
+    fn validate(&self) -> std::result::Result<(), validator::ValidationErrors> {
+        match self {
+            PetReference::I64(_) => std::result::Result::Ok(()),
+            PetReference::String(_) => std::result::Result::Ok(()),
+        }
+    }
+}
+
+/// Converts the PetReference value to the representation of its variant
+impl std::fmt::Display for PetReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PetReference::I64(value) => std::fmt::Display::fmt(value, f),
+            PetReference::String(value) => std::fmt::Display::fmt(value, f),
+        }
+    }
+}
+
+/// Converts the representation of the first variant it parses into to a PetReference value
+impl std::str::FromStr for PetReference {
+    type Err = String;
+
+    #[allow(irrefutable_let_patterns)]
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if let std::result::Result::Ok(value) = s.parse::<i64>() {
+            return std::result::Result::Ok(PetReference::I64(value));
+        }
+        if let std::result::Result::Ok(value) = s.parse::<String>() {
+            return std::result::Result::Ok(PetReference::String(value));
+        }
+        std::result::Result::Err(format!("Value not valid for PetReference: {}", s))
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<PetReference> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<PetReference>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<PetReference>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for PetReference - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<PetReference> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <PetReference as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        "Unable to convert header value '{}' into PetReference - {}",
+                        value, err
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+/// Representation of PetReference values in XML, following the `xml` objects of the schema
+impl xml::XmlSchema for PetReference {
+    fn xml() -> xml::Xml {
+        xml::Xml {
+            name: "PetReference",
+            namespace: None,
+            prefix: None,
+            kind: xml::Kind::Any,
+        }
+    }
+}
+
+/// Pet of a known kind, tagged by its `petType`
+/// One of the following, internally tagged by the `petType` property:
+/// - `dog`: [`Dog`]
+/// - `cat`: [`Cat`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "conversion", derive(frunk_enum_derive::LabelledGenericEnum))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub enum PetVariant {
+    Dog(models::Dog),
+    Cat(models::Cat),
+}
+
+impl PetVariant {
+    /// Name of the discriminator property.
+    pub const DISCRIMINATOR: &'static str = "petType";
+
+    /// Value of the discriminator property for this variant.
+    pub fn discriminator(&self) -> &'static str {
+        match self {
+            PetVariant::Dog(_) => "dog",
+            PetVariant::Cat(_) => "cat",
+        }
+    }
+}
+
+// The variants are (de)serialized through `serde_json::Value` rather than with
+// `#[serde(tag = ...)]`, as their schemas may declare the discriminator property too.
+impl serde::Serialize for PetVariant {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        let value = match self {
+            PetVariant::Dog(value) => serde_json::to_value(value),
+            PetVariant::Cat(value) => serde_json::to_value(value),
+        };
+        let mut value = value.map_err(serde::ser::Error::custom)?;
+        let std::option::Option::Some(object) = value.as_object_mut() else {
+            return std::result::Result::Err(serde::ser::Error::custom(
+                "PetVariant variants must be objects",
+            ));
+        };
+        object.insert(Self::DISCRIMINATOR.to_string(), self.discriminator().into());
+        serde::Serialize::serialize(&value, serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PetVariant {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let value = <serde_json::Value as serde::Deserialize>::deserialize(deserializer)?;
+        let discriminator = value
+            .get(Self::DISCRIMINATOR)
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| serde::de::Error::missing_field(Self::DISCRIMINATOR))?
+            .to_string();
+        match discriminator.as_str() {
+            "dog" => serde_json::from_value(value).map(PetVariant::Dog),
+            "cat" => serde_json::from_value(value).map(PetVariant::Cat),
+            other => {
+                return std::result::Result::Err(serde::de::Error::unknown_variant(
+                    other,
+                    &["dog", "cat"],
+                ))
+            }
+        }
+        .map_err(serde::de::Error::custom)
+    }
+}
+
+impl validator::Validate for PetVariant {
+    fn validate(&self) -> std::result::Result<(), validator::ValidationErrors> {
+        match self {
+            PetVariant::Dog(value) => value.validate(),
+            PetVariant::Cat(value) => value.validate(),
+        }
+    }
+}
+
+/// Converts the PetVariant value to the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/, which is also the value part of the
+/// `style=form, explode=false` query parameter representation
+impl std::fmt::Display for PetVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &style::to_string(self, style::Style::Simple, false)
+                .expect("PetVariant is always serializable"),
+        )
+    }
+}
+
+/// Converts the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/ to a PetVariant value
+impl std::str::FromStr for PetVariant {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        #[derive(serde::Deserialize)]
+        struct Discriminator {
+            #[serde(rename = "petType")]
+            value: String,
+        }
+
+        let discriminator: Discriminator =
+            style::from_str(s, style::Style::Simple, false).map_err(|e| e.to_string())?;
+        match discriminator.value.as_str() {
+            "dog" => s.parse().map(PetVariant::Dog),
+            "cat" => s.parse().map(PetVariant::Cat),
+            other => std::result::Result::Err(format!("Unknown PetVariant variant {}", other)),
+        }
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<PetVariant> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<PetVariant>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<PetVariant>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for PetVariant - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<PetVariant> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <PetVariant as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        "Unable to convert header value '{}' into PetVariant - {}",
+                        value, err
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+/// Representation of PetVariant values in XML, following the `xml` objects of the schema
+impl xml::XmlSchema for PetVariant {
+    fn xml() -> xml::Xml {
+        xml::Xml {
+            name: "PetVariant",
+            namespace: None,
+            prefix: None,
+            kind: xml::Kind::Any,
+        }
+    }
+}
+
+/// Pet of a known kind, its `kind` next to the pet in `pet`
+/// One of the following, adjacently tagged by the `kind` property, with the
+/// variant in the `pet` property:
+/// - `dog`: [`Dog`]
+/// - `cat`: [`Cat`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "conversion", derive(frunk_enum_derive::LabelledGenericEnum))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub enum TaggedPet {
+    Dog(models::Dog),
+    Cat(models::Cat),
+}
+
+impl TaggedPet {
+    /// Name of the discriminator property.
+    pub const DISCRIMINATOR: &'static str = "kind";
+
+    /// Name of the property holding the variant.
+    pub const CONTENT: &'static str = "pet";
+
+    /// Value of the discriminator property for this variant.
+    pub fn discriminator(&self) -> &'static str {
+        match self {
+            TaggedPet::Dog(_) => "dog",
+            TaggedPet::Cat(_) => "cat",
+        }
+    }
+}
+
+// The variants are (de)serialized through `serde_json::Value`, as those of internally tagged
+// enums are.
+impl serde::Serialize for TaggedPet {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        let value = match self {
+            TaggedPet::Dog(value) => serde_json::to_value(value),
+            TaggedPet::Cat(value) => serde_json::to_value(value),
+        };
+        let mut object = serde_json::Map::new();
+        object.insert(Self::DISCRIMINATOR.to_string(), self.discriminator().into());
+        object.insert(
+            Self::CONTENT.to_string(),
+            value.map_err(serde::ser::Error::custom)?,
+        );
+        serde::Serialize::serialize(&object, serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for TaggedPet {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let value = <serde_json::Value as serde::Deserialize>::deserialize(deserializer)?;
+        let discriminator = value
+            .get(Self::DISCRIMINATOR)
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| serde::de::Error::missing_field(Self::DISCRIMINATOR))?
+            .to_string();
+        let value = value
+            .get(Self::CONTENT)
+            .cloned()
+            .ok_or_else(|| serde::de::Error::missing_field(Self::CONTENT))?;
+        match discriminator.as_str() {
+            "dog" => serde_json::from_value(value).map(TaggedPet::Dog),
+            "cat" => serde_json::from_value(value).map(TaggedPet::Cat),
+            other => {
+                return std::result::Result::Err(serde::de::Error::unknown_variant(
+                    other,
+                    &["dog", "cat"],
+                ))
+            }
+        }
+        .map_err(serde::de::Error::custom)
+    }
+}
+
+impl validator::Validate for TaggedPet {
+    fn validate(&self) -> std::result::Result<(), validator::ValidationErrors> {
+        match self {
+            TaggedPet::Dog(value) => value.validate(),
+            TaggedPet::Cat(value) => value.validate(),
+        }
+    }
+}
+
+/// Converts the TaggedPet value to the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/, which is also the value part of the
+/// `style=form, explode=false` query parameter representation
+impl std::fmt::Display for TaggedPet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &style::to_string(self, style::Style::Simple, false)
+                .expect("TaggedPet is always serializable"),
+        )
+    }
+}
+
+/// Converts the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/ to a TaggedPet value
+impl std::str::FromStr for TaggedPet {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        #[derive(serde::Deserialize)]
+        struct Discriminator {
+            #[serde(rename = "kind")]
+            value: String,
+            /// The variant, in its own representation.
+            #[serde(rename = "pet")]
+            content: String,
+        }
+
+        let discriminator: Discriminator =
+            style::from_str(s, style::Style::Simple, false).map_err(|e| e.to_string())?;
+        let s = discriminator.content.as_str();
+        match discriminator.value.as_str() {
+            "dog" => s.parse().map(TaggedPet::Dog),
+            "cat" => s.parse().map(TaggedPet::Cat),
+            other => std::result::Result::Err(format!("Unknown TaggedPet variant {}", other)),
+        }
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<TaggedPet> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<TaggedPet>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<TaggedPet>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for TaggedPet - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TaggedPet> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <TaggedPet as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        "Unable to convert header value '{}' into TaggedPet - {}",
+                        value, err
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+/// Representation of TaggedPet values in XML, following the `xml` objects of the schema
+impl xml::XmlSchema for TaggedPet {
+    fn xml() -> xml::Xml {
+        xml::Xml {
+            name: "TaggedPet",
+            namespace: None,
+            prefix: None,
+            kind: xml::Kind::Any,
+        }
+    }
+}
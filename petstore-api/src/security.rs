@@ -0,0 +1,407 @@
+//! CORS, security headers and rate limiting for the generated router.
+//!
+//! The layers are installed by [`crate::server::Builder`]:
+//! - [`cors`] presets a `tower_http` [`CorsLayer`] with the methods and response headers of the
+//!   OpenAPI document,
+//! - [`SecurityHeadersLayer`] sets the standard security headers on every response,
+//! - [`RateLimiter`] limits the requests of each client to an operation with a token bucket,
+//!   as declared by the `x-rate-limit` extension of the operation:
+//!
+//! ```yaml
+//! x-rate-limit:
+//!   requests: 10 # bucket capacity
+//!   period: 60   # seconds to refill the whole bucket
+//! ```
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    fmt,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use axum::{body::Body, extract::ConnectInfo, response::Response};
+use futures::future::BoxFuture;
+use http::{
+    header::{
+        ACCEPT, AGE, CONTENT_SECURITY_POLICY, CONTENT_TYPE, LINK, REFERRER_POLICY, RETRY_AFTER,
+        STRICT_TRANSPORT_SECURITY, X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS,
+    },
+    HeaderName, HeaderValue, Method, Request, StatusCode,
+};
+use tower::{Layer, Service};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::{
+    deprecation::{DEPRECATION, SUNSET},
+    telemetry::X_REQUEST_ID,
+};
+
+/// Methods of the operations in the OpenAPI document, one per operation.
+pub const OPERATION_METHODS: &[Method] = &[
+    Method::GET,
+    Method::POST,
+    Method::GET,
+    Method::POST,
+    Method::GET,
+    Method::POST,
+];
+
+/// Response headers declared in the OpenAPI document, one per response declaring them.
+pub const RESPONSE_HEADERS: &[&str] = &[
+    "retry-after",
+    "x-next",
+    "content-length",
+    "accept-ranges",
+    "content-length",
+    "content-range",
+    "content-range",
+];
+
+/// Request headers declared in the OpenAPI document, one per operation parameter.
+pub const REQUEST_HEADERS: &[&str] = &["idempotency-key", "range"];
+
+/// Header with the capacity of the rate limit of the operation.
+pub const X_RATE_LIMIT_LIMIT: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+
+/// Header with the requests left in the rate limit of the operation.
+pub const X_RATE_LIMIT_REMAINING: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+
+/// CORS layer allowing `origins` to call the operations of the OpenAPI document.
+pub fn cors(origins: impl Into<AllowOrigin>) -> CorsLayer {
+    let mut methods = Vec::new();
+    for method in OPERATION_METHODS {
+        if !methods.contains(method) {
+            methods.push(method.clone());
+        }
+    }
+
+    let mut allowed = vec![
+        ACCEPT,
+        CONTENT_TYPE,
+        X_REQUEST_ID,
+        HeaderName::from_static("traceparent"),
+        HeaderName::from_static("tracestate"),
+    ];
+    for name in REQUEST_HEADERS {
+        let name = HeaderName::from_static(name);
+        if !allowed.contains(&name) {
+            allowed.push(name);
+        }
+    }
+
+    let mut exposed = vec![
+        AGE,
+        DEPRECATION,
+        SUNSET,
+        LINK,
+        X_REQUEST_ID,
+        X_RATE_LIMIT_LIMIT,
+        X_RATE_LIMIT_REMAINING,
+    ];
+    for name in RESPONSE_HEADERS {
+        let name = HeaderName::from_static(name);
+        if !exposed.contains(&name) {
+            exposed.push(name);
+        }
+    }
+
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods(methods)
+        .allow_headers(allowed)
+        .expose_headers(exposed)
+        .max_age(Duration::from_secs(60 * 60))
+}
+
+/// [`cors`] for the origins of the comma separated `CORS_ALLOWED_ORIGINS` environment variable,
+/// or `None` if it isn't set.
+pub fn cors_from_env() -> Result<Option<CorsLayer>, String> {
+    let Ok(origins) = std::env::var("CORS_ALLOWED_ORIGINS") else {
+        return Ok(None);
+    };
+    let origins = origins
+        .split(',')
+        .map(|origin| {
+            let origin = origin.trim();
+            HeaderValue::from_str(origin).map_err(|_| format!("Invalid CORS origin '{}'", origin))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Some(cors(origins)))
+}
+
+/// Layer setting the standard security headers on responses, unless already set.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SecurityHeadersLayer;
+
+impl<S> Layer<S> for SecurityHeadersLayer {
+    type Service = SecurityHeadersService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SecurityHeadersService { inner }
+    }
+}
+
+/// Service created by [`SecurityHeadersLayer`].
+#[derive(Debug, Clone)]
+pub struct SecurityHeadersService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for SecurityHeadersService<S>
+where
+    S: Service<Request<Body>, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let mut response = future.await?;
+            let headers = response.headers_mut();
+            for (name, value) in [
+                (X_CONTENT_TYPE_OPTIONS, "nosniff"),
+                (X_FRAME_OPTIONS, "DENY"),
+                (REFERRER_POLICY, "no-referrer"),
+                (
+                    STRICT_TRANSPORT_SECURITY,
+                    "max-age=63072000; includeSubDomains",
+                ),
+                (
+                    CONTENT_SECURITY_POLICY,
+                    "default-src 'none'; frame-ancestors 'none'",
+                ),
+            ] {
+                headers
+                    .entry(name)
+                    .or_insert(HeaderValue::from_static(value));
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Token bucket rate limit: bursts of up to `requests`, refilled over `period`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub period: Duration,
+}
+
+impl RateLimit {
+    /// Limit of `requests` per `period`.
+    pub const fn new(requests: u32, period: Duration) -> Self {
+        RateLimit { requests, period }
+    }
+}
+
+/// Authenticated principal of a request.
+///
+/// Authentication middleware inserts it as request extension, to rate limit the principal
+/// instead of the client IP.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Principal(pub String);
+
+/// Key of the client a request is counted against: its [`Principal`], or its IP if the router
+/// is served by [`crate::serve::serve`] or with `into_make_service_with_connect_info::<SocketAddr>`.
+pub fn client_key(request: &Request<Body>) -> String {
+    if let Some(Principal(principal)) = request.extensions().get::<Principal>() {
+        return format!("principal:{}", principal);
+    }
+    match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+        None => "unknown".to_string(),
+    }
+}
+
+type KeyFn = dyn Fn(&Request<Body>) -> String + Send + Sync;
+
+/// Number of buckets above which full buckets are dropped.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    period: Duration,
+}
+
+/// Rate limiter shared by the operations of a router.
+#[derive(Clone)]
+pub struct RateLimiter {
+    buckets: Arc<Mutex<HashMap<(&'static str, String), Bucket>>>,
+    key: Arc<KeyFn>,
+    default_limit: Option<RateLimit>,
+}
+
+impl fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("default_limit", &self.default_limit)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        RateLimiter {
+            buckets: Default::default(),
+            key: Arc::new(client_key),
+            default_limit: None,
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Rate limiter keyed by [`client_key`], limiting only operations with `x-rate-limit`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count requests against the key computed by `key` instead of [`client_key`].
+    pub fn with_key<F>(mut self, key: F) -> Self
+    where
+        F: Fn(&Request<Body>) -> String + Send + Sync + 'static,
+    {
+        self.key = Arc::new(key);
+        self
+    }
+
+    /// Limit operations without `x-rate-limit` to `limit`.
+    pub fn with_default_limit(mut self, limit: RateLimit) -> Self {
+        self.default_limit = Some(limit);
+        self
+    }
+
+    /// Layer limiting the requests to the operation `operation_id` to `limit`, or the default
+    /// limit.
+    pub fn layer(&self, operation_id: &'static str, limit: Option<RateLimit>) -> RateLimitLayer {
+        RateLimitLayer {
+            limiter: self.clone(),
+            operation_id,
+            limit: limit.or(self.default_limit),
+        }
+    }
+
+    /// Take a token from the bucket of `key`, returning the tokens left, or the time until the
+    /// next token if the bucket is empty.
+    fn acquire(
+        &self,
+        operation_id: &'static str,
+        key: String,
+        limit: RateLimit,
+    ) -> Result<u32, Duration> {
+        let capacity = f64::from(limit.requests);
+        let refill_per_sec = capacity / limit.period.as_secs_f64();
+        let now = Instant::now();
+
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= PRUNE_THRESHOLD {
+            // buckets idle for a whole period are full again
+            buckets.retain(|_, bucket| now.duration_since(bucket.updated) < bucket.period);
+        }
+        let bucket = buckets.entry((operation_id, key)).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+            period: limit.period,
+        });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(bucket.tokens as u32)
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / refill_per_sec,
+            ))
+        }
+    }
+}
+
+/// Layer created by [`RateLimiter::layer`].
+#[derive(Debug, Clone)]
+pub struct RateLimitLayer {
+    limiter: RateLimiter,
+    operation_id: &'static str,
+    limit: Option<RateLimit>,
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service created by [`RateLimitLayer`].
+#[derive(Debug, Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    layer: RateLimitLayer,
+}
+
+impl<S> Service<Request<Body>> for RateLimitService<S>
+where
+    S: Service<Request<Body>, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let Some(limit) = self.layer.limit else {
+            return Box::pin(self.inner.call(request));
+        };
+
+        let key = (self.layer.limiter.key)(&request);
+        let acquired = self
+            .layer
+            .limiter
+            .acquire(self.layer.operation_id, key, limit);
+        let remaining = match acquired {
+            Ok(remaining) => remaining,
+            Err(retry_after) => {
+                let mut response = Response::new(Body::empty());
+                *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+                let headers = response.headers_mut();
+                headers.insert(
+                    RETRY_AFTER,
+                    (retry_after.as_secs_f64().ceil() as u64).into(),
+                );
+                headers.insert(X_RATE_LIMIT_LIMIT, limit.requests.into());
+                headers.insert(X_RATE_LIMIT_REMAINING, 0.into());
+                return Box::pin(async move { Ok(response) });
+            }
+        };
+
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let mut response = future.await?;
+            let headers = response.headers_mut();
+            headers.insert(X_RATE_LIMIT_LIMIT, limit.requests.into());
+            headers.insert(X_RATE_LIMIT_REMAINING, remaining.into());
+            Ok(response)
+        })
+    }
+}
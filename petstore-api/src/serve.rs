@@ -0,0 +1,385 @@
+//! Serve the router over HTTP/1.1 and HTTP/2.
+//!
+//! Without TLS, connections speak HTTP/1.1 or h2c with prior knowledge. With TLS, the protocol
+//! is negotiated by ALPN, and certificates are reloaded when their files change. If a client CA
+//! is configured, clients must present a certificate signed by it, and its identity is passed
+//! to the `Api` methods as [`ClientIdentity`] (and used as [`Principal`] for rate limiting).
+//!
+//! [`serve_with_graceful_shutdown`] stops accepting connections once its signal completes, e.g.
+//! [`shutdown_signal`], and returns when the open connections have answered their requests, so
+//! that telemetry can be flushed before the process exits.
+
+use std::{
+    future::Future,
+    io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use axum::{body::Body, extract::ConnectInfo, Router};
+use http::Request;
+use hyper::body::Incoming;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::{
+        conn::auto,
+        graceful::{GracefulShutdown, Watcher},
+    },
+};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::{
+    rustls::{
+        crypto::ring,
+        pki_types::{CertificateDer, PrivateKeyDer},
+        server::WebPkiClientVerifier,
+        RootCertStore, ServerConfig,
+    },
+    TlsAcceptor,
+};
+use tower::ServiceExt;
+use tracing::{debug, error, info, warn};
+use x509_parser::prelude::{FromDer, GeneralName, X509Certificate};
+
+use crate::{security::Principal, types::ClientIdentity};
+
+/// TLS configuration, from PEM files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsConfig {
+    /// Certificate chain of the server.
+    pub cert: PathBuf,
+    /// Private key of the server.
+    pub key: PathBuf,
+    /// CA certificates of the clients. If set, clients must present a certificate.
+    pub client_ca: Option<PathBuf>,
+    /// Interval to check the files for changes.
+    pub reload_interval: Duration,
+    /// Time a client has to complete the TLS handshake before its connection is closed.
+    pub handshake_timeout: Duration,
+}
+
+impl TlsConfig {
+    /// Serve the certificate chain in `cert` with the private key in `key`.
+    pub fn new(cert: impl Into<PathBuf>, key: impl Into<PathBuf>) -> Self {
+        TlsConfig {
+            cert: cert.into(),
+            key: key.into(),
+            client_ca: None,
+            reload_interval: Duration::from_secs(10),
+            handshake_timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Require client certificates signed by the CA certificates in `client_ca`.
+    pub fn with_client_ca(mut self, client_ca: impl Into<PathBuf>) -> Self {
+        self.client_ca = Some(client_ca.into());
+        self
+    }
+
+    /// Check the files for changes every `reload_interval`.
+    pub fn with_reload_interval(mut self, reload_interval: Duration) -> Self {
+        self.reload_interval = reload_interval;
+        self
+    }
+
+    /// Close connections whose TLS handshake takes longer than `handshake_timeout`.
+    pub fn with_handshake_timeout(mut self, handshake_timeout: Duration) -> Self {
+        self.handshake_timeout = handshake_timeout;
+        self
+    }
+
+    /// Read the configuration from `TLS_CERT_FILE`, `TLS_KEY_FILE` and the optional
+    /// `TLS_CLIENT_CA_FILE`, or `None` if TLS is not configured.
+    pub fn from_env() -> Option<Self> {
+        let cert = std::env::var_os("TLS_CERT_FILE")?;
+        let key = std::env::var_os("TLS_KEY_FILE")?;
+        let config = TlsConfig::new(cert, key);
+        Some(match std::env::var_os("TLS_CLIENT_CA_FILE") {
+            Some(client_ca) => config.with_client_ca(client_ca),
+            None => config,
+        })
+    }
+
+    fn files(&self) -> impl Iterator<Item = &Path> {
+        [Some(&self.cert), Some(&self.key), self.client_ca.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(PathBuf::as_path)
+    }
+
+    fn read_files(&self) -> io::Result<Vec<Vec<u8>>> {
+        self.files().map(std::fs::read).collect()
+    }
+
+    /// Build the rustls configuration from the contents of the files.
+    fn server_config(&self, files: &[Vec<u8>]) -> io::Result<ServerConfig> {
+        let provider = Arc::new(ring::default_provider());
+
+        let certs = rustls_pemfile::certs(&mut files[0].as_slice())
+            .collect::<Result<Vec<CertificateDer>, _>>()?;
+        let key: PrivateKeyDer = rustls_pemfile::private_key(&mut files[1].as_slice())?
+            .ok_or_else(|| invalid_data(format!("No private key in {}", self.key.display())))?;
+
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(invalid_data)?;
+        let builder = match files.get(2) {
+            Some(client_ca) => {
+                let mut roots = RootCertStore::empty();
+                for cert in rustls_pemfile::certs(&mut client_ca.as_slice()) {
+                    roots.add(cert?).map_err(invalid_data)?;
+                }
+                let verifier = WebPkiClientVerifier::builder_with_provider(roots.into(), provider)
+                    .build()
+                    .map_err(invalid_data)?;
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        let mut config = builder.with_single_cert(certs, key).map_err(invalid_data)?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(config)
+    }
+}
+
+fn invalid_data(e: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+}
+
+/// TLS acceptor, replaced when the configured files change.
+#[derive(Clone)]
+struct ReloadingAcceptor {
+    acceptor: Arc<RwLock<TlsAcceptor>>,
+    handshake_timeout: Duration,
+}
+
+impl ReloadingAcceptor {
+    /// Load the configuration, and check its files for changes in the background.
+    fn start(config: TlsConfig) -> io::Result<Self> {
+        let mut files = config.read_files()?;
+        let acceptor = TlsAcceptor::from(Arc::new(config.server_config(&files)?));
+        let reloading = ReloadingAcceptor {
+            acceptor: Arc::new(RwLock::new(acceptor)),
+            handshake_timeout: config.handshake_timeout,
+        };
+
+        let shared = Arc::downgrade(&reloading.acceptor);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.reload_interval);
+            loop {
+                interval.tick().await;
+                let Some(shared) = shared.upgrade() else {
+                    return;
+                };
+
+                let changed = match config.read_files() {
+                    Ok(changed) if changed != files => changed,
+                    Ok(_) => continue,
+                    Err(e) => {
+                        warn!(error = %e, "Unable to read TLS files");
+                        continue;
+                    }
+                };
+                match config.server_config(&changed) {
+                    Ok(server_config) => {
+                        *shared.write().unwrap() = TlsAcceptor::from(Arc::new(server_config));
+                        files = changed;
+                        info!("TLS configuration reloaded");
+                    }
+                    // the files may be written one by one, so retry on the next tick
+                    Err(e) => warn!(error = %e, "Invalid TLS configuration"),
+                }
+            }
+        });
+
+        Ok(reloading)
+    }
+
+    fn current(&self) -> TlsAcceptor {
+        self.acceptor.read().unwrap().clone()
+    }
+}
+
+/// Serve `router` on the connections accepted by `listener`, over TLS if `tls` is set.
+///
+/// Requests carry the peer address as `ConnectInfo<SocketAddr>` extension.
+pub async fn serve(
+    listener: TcpListener,
+    router: Router,
+    tls: Option<TlsConfig>,
+) -> io::Result<()> {
+    serve_with_graceful_shutdown(listener, router, tls, std::future::pending()).await
+}
+
+/// Serve `router` as [`serve`] does until `signal` completes, then stop accepting connections,
+/// close the open ones once their requests are answered, and return when they are closed.
+pub async fn serve_with_graceful_shutdown(
+    listener: TcpListener,
+    router: Router,
+    tls: Option<TlsConfig>,
+    signal: impl Future<Output = ()>,
+) -> io::Result<()> {
+    let acceptor = tls.map(ReloadingAcceptor::start).transpose()?;
+    let graceful = GracefulShutdown::new();
+    let mut signal = std::pin::pin!(signal);
+
+    loop {
+        let accepted = tokio::select! {
+            accepted = listener.accept() => accepted,
+            () = &mut signal => break,
+        };
+        let (stream, addr) = match accepted {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                // e.g. out of file descriptors, give connections time to close
+                error!(error = %e, "Unable to accept connection");
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                continue;
+            }
+        };
+
+        let router = router.clone();
+        let acceptor = acceptor
+            .as_ref()
+            .map(|acceptor| (acceptor.current(), acceptor.handshake_timeout));
+        // watched from the start, so that a shutdown during the TLS handshake isn't missed
+        let watcher = graceful.watcher();
+        tokio::spawn(async move {
+            match acceptor {
+                Some((acceptor, handshake_timeout)) => {
+                    serve_tls(acceptor, handshake_timeout, stream, addr, router, watcher).await
+                }
+                None => serve_connection(TokioIo::new(stream), addr, None, router, watcher).await,
+            }
+        });
+    }
+
+    drop(listener);
+    info!(
+        connections = graceful.count(),
+        "Shutting down, waiting for the open connections"
+    );
+    graceful.shutdown().await;
+    Ok(())
+}
+
+/// Complete when the process is asked to stop: on Ctrl+C, or on `SIGTERM` on Unix.
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!(error = %e, "Unable to listen for Ctrl+C");
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(e) => {
+                error!(error = %e, "Unable to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {}
+        () = terminate => {}
+    }
+}
+
+async fn serve_tls(
+    acceptor: TlsAcceptor,
+    handshake_timeout: Duration,
+    stream: TcpStream,
+    addr: SocketAddr,
+    router: Router,
+    watcher: Watcher,
+) {
+    // a client which never completes the handshake would hold the connection forever
+    let stream = match tokio::time::timeout(handshake_timeout, acceptor.accept(stream)).await {
+        Ok(Ok(stream)) => stream,
+        Ok(Err(e)) => {
+            debug!(error = %e, %addr, "TLS handshake failed");
+            return;
+        }
+        Err(_) => {
+            debug!(%addr, "TLS handshake timed out");
+            return;
+        }
+    };
+
+    let identity = match stream.get_ref().1.peer_certificates() {
+        Some([certificate, ..]) => match client_identity(certificate) {
+            Ok(identity) => Some(identity),
+            Err(e) => {
+                debug!(error = %e, %addr, "Invalid client certificate");
+                return;
+            }
+        },
+        _ => None,
+    };
+
+    serve_connection(TokioIo::new(stream), addr, identity, router, watcher).await
+}
+
+async fn serve_connection<IO>(
+    io: TokioIo<IO>,
+    addr: SocketAddr,
+    identity: Option<ClientIdentity>,
+    router: Router,
+    watcher: Watcher,
+) where
+    IO: tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + Unpin + 'static,
+{
+    let service = hyper::service::service_fn(move |request: Request<Incoming>| {
+        let mut request = request.map(Body::new);
+        request.extensions_mut().insert(ConnectInfo(addr));
+        if let Some(identity) = &identity {
+            request
+                .extensions_mut()
+                .insert(Principal(identity.subject.clone()));
+            request.extensions_mut().insert(identity.clone());
+        }
+        router.clone().oneshot(request)
+    });
+
+    let builder = auto::Builder::new(TokioExecutor::new());
+    let connection = builder.serve_connection_with_upgrades(io, service);
+    if let Err(e) = watcher.watch(connection).await {
+        debug!(error = %e, %addr, "Connection failed");
+    }
+}
+
+/// Identity of the client presenting `certificate`.
+fn client_identity(certificate: &CertificateDer) -> Result<ClientIdentity, String> {
+    let (_, parsed) = X509Certificate::from_der(certificate).map_err(|e| e.to_string())?;
+
+    let alt_names = match parsed.subject_alternative_name() {
+        Ok(Some(extension)) => extension
+            .value
+            .general_names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::DNSName(name)
+                | GeneralName::RFC822Name(name)
+                | GeneralName::URI(name) => Some(name.to_string()),
+                _ => None,
+            })
+            .collect(),
+        Ok(None) => Vec::new(),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    Ok(ClientIdentity {
+        subject: parsed.subject().to_string(),
+        alt_names,
+        certificate: certificate.to_vec(),
+    })
+}
@@ -0,0 +1,581 @@
+//! Serde based (de)serialization of parameter values in the `style=simple` and `style=form`
+//! representations specified in https://swagger.io/docs/specification/serialization/
+//!
+//! Reserved characters (`,`, `=`, `&`, `%`, ...) inside values are percent-encoded, so any
+//! value round-trips. Nested objects and arrays are serialized in the same style and then
+//! percent-encoded as a single value. Empty strings in arrays are written `""`, so that `[""]`
+//! isn't read back as `[]`. Free-form values and additional properties have no representation
+//! which keeps their types, so they are rejected when deserializing rather than read as strings.
+//!
+//! Exploded `style=form` arrays repeat the name of the parameter, so they are (de)serialized
+//! with it, by [`to_query`] and [`from_query`].
+//!
+//! ```
+//! # use petstore_api::{models::Pet, style::{self, Style}};
+//! let pet = Pet { id: 1, name: "Rex, the dog".to_string(), tag: None, attributes: None };
+//!
+//! let simple = style::to_string(&pet, Style::Simple, false).unwrap();
+//! assert_eq!(simple, "id,1,name,Rex%2C%20the%20dog");
+//! assert_eq!(style::from_str::<Pet>(&simple, Style::Simple, false).unwrap(), pet);
+//!
+//! let form = style::to_string(&pet, Style::Form, true).unwrap();
+//! assert_eq!(form, "id=1&name=Rex%2C%20the%20dog");
+//! assert_eq!(style::from_str::<Pet>(&form, Style::Form, true).unwrap(), pet);
+//!
+//! let tags = vec!["dog".to_string(), "good, old".to_string()];
+//! let query = style::to_query("tags", &tags, true).unwrap();
+//! assert_eq!(query, "tags=dog&tags=good%2C%20old");
+//! assert_eq!(style::from_query::<Vec<String>>("tags", &query, true).unwrap(), tags);
+//! ```
+
+use std::{borrow::Cow, fmt};
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::{
+    de::{self, DeserializeOwned, IntoDeserializer, Visitor},
+    forward_to_deserialize_any, Serialize,
+};
+use serde_json::Value;
+
+/// Everything but the RFC 3986 unreserved characters is percent-encoded.
+const RESERVED: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Marker for a `null` value. A string with the same content is encoded as `%6Eull`.
+const NULL: &str = "null";
+
+/// Error of exploded `style=form` arrays without the name of their parameter.
+const EXPLODED_ARRAY: &str =
+    "Exploded form arrays repeat the name of the parameter, see `style::to_query`";
+
+/// Marker for an empty string in an array. A string with the same content is encoded as
+/// `%22%22`, as any `"` is.
+const EMPTY: &str = "\"\"";
+
+/// Parameter serialization style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// `style=simple`, used for path and header parameters. Entries are separated by `,`.
+    Simple,
+    /// `style=form`, used for query and cookie parameters. Without explode this is the value
+    /// part of `name=value`, and the same as [`Style::Simple`]; with explode, object properties
+    /// become `key=value` pairs separated by `&`, and array items `name=item` pairs, see
+    /// [`to_query`].
+    Form,
+}
+
+impl Style {
+    fn separator(self, explode: bool) -> char {
+        match (self, explode) {
+            (Style::Form, true) => '&',
+            _ => ',',
+        }
+    }
+}
+
+/// Error raised while (de)serializing a parameter value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error(e.to_string())
+    }
+}
+
+/// Serialize `value` in the given style.
+///
+/// Exploded `style=form` arrays have no representation without the name of the parameter, see
+/// [`to_query`].
+pub fn to_string<T>(value: &T, style: Style, explode: bool) -> Result<String, Error>
+where
+    T: Serialize + ?Sized,
+{
+    let value = serde_json::to_value(value)?;
+    let separator = style.separator(explode).to_string();
+
+    Ok(match value {
+        Value::Array(_) if style == Style::Form && explode => {
+            return Err(Error(EXPLODED_ARRAY.to_string()))
+        }
+        Value::Array(items) => items.iter().map(encode_item).collect::<Vec<_>>().join(","),
+        Value::Object(entries) => entries
+            .iter()
+            .map(|(k, v)| {
+                let key = encode_str(k);
+                let value = encode_nested(v);
+                if explode {
+                    format!("{}={}", key, value)
+                } else {
+                    format!("{},{}", key, value)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(&separator),
+        value => encode_scalar(&value),
+    })
+}
+
+/// Deserialize a value from the given style.
+pub fn from_str<T>(s: &str, style: Style, explode: bool) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(Part {
+        raw: Cow::Borrowed(s),
+        nested: false,
+        separator: style.separator(explode),
+        explode,
+    })
+}
+
+/// Serialize the query parameter `name` with the value `value` in the `style=form`
+/// representation: `name=value`, or with explode, `name=item` pairs for array items (none for
+/// an empty array) and `key=value` pairs for object properties.
+pub fn to_query<T>(name: &str, value: &T, explode: bool) -> Result<String, Error>
+where
+    T: Serialize + ?Sized,
+{
+    let name = encode_str(name);
+    match serde_json::to_value(value)? {
+        Value::Array(items) if explode => Ok(items
+            .iter()
+            .map(|item| format!("{}={}", name, encode_nested(item)))
+            .collect::<Vec<_>>()
+            .join("&")),
+        Value::Object(_) if explode => to_string(value, Style::Form, true),
+        _ => Ok(format!(
+            "{}={}",
+            name,
+            to_string(value, Style::Form, false)?
+        )),
+    }
+}
+
+/// Deserialize the query parameter `name` from the query string `query`, in the `style=form`
+/// representation of [`to_query`]. Exploded objects are read from all pairs of the query, so
+/// they can't be optional.
+pub fn from_query<T>(name: &str, query: &str, explode: bool) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(Query {
+        name,
+        query,
+        explode,
+    })
+}
+
+/// Deserialize an `application/x-www-form-urlencoded` request body, which is the `style=form,
+/// explode=true` representation of its properties, except that `+` also stands for a space.
+pub fn from_form_body<T>(body: &str) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    from_str(&body.replace('+', "%20"), Style::Form, true)
+}
+
+fn encode_str(s: &str) -> String {
+    if s == NULL {
+        return "%6Eull".to_string();
+    }
+    utf8_percent_encode(s, RESERVED).to_string()
+}
+
+fn encode_scalar(value: &Value) -> String {
+    match value {
+        Value::Null => NULL.to_string(),
+        Value::String(s) => encode_str(s),
+        value => value.to_string(),
+    }
+}
+
+fn encode_item(value: &Value) -> String {
+    match value {
+        Value::String(s) if s.is_empty() => EMPTY.to_string(),
+        value => encode_nested(value),
+    }
+}
+
+fn encode_nested(value: &Value) -> String {
+    match value {
+        Value::Array(_) | Value::Object(_) => {
+            let inner = to_string(value, Style::Simple, false).unwrap_or_default();
+            utf8_percent_encode(&inner, RESERVED).to_string()
+        }
+        value => encode_scalar(value),
+    }
+}
+
+fn decode(s: &str) -> Result<Cow<'_, str>, Error> {
+    percent_decode_str(s)
+        .decode_utf8()
+        .map_err(|e| Error(format!("Invalid percent-encoding in '{}': {}", s, e)))
+}
+
+/// A single (possibly composite) value in its encoded form.
+struct Part<'a> {
+    raw: Cow<'a, str>,
+    /// Nested composites are percent-encoded as a whole.
+    nested: bool,
+    separator: char,
+    explode: bool,
+}
+
+impl<'a> Part<'a> {
+    fn nested(raw: &'a str) -> Self {
+        Part {
+            raw: Cow::Borrowed(raw),
+            nested: true,
+            separator: ',',
+            explode: false,
+        }
+    }
+
+    fn scalar(&self) -> Result<Cow<'_, str>, Error> {
+        decode(&self.raw)
+    }
+
+    fn composite(&self) -> Result<Cow<'_, str>, Error> {
+        if self.nested {
+            decode(&self.raw)
+        } else {
+            Ok(Cow::Borrowed(&self.raw))
+        }
+    }
+
+    fn items(&self, separator: char) -> Result<Vec<String>, Error> {
+        let composite = self.composite()?;
+        if composite.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(composite.split(separator).map(str::to_string).collect())
+    }
+
+    fn parse<T>(&self) -> Result<T, Error>
+    where
+        T: std::str::FromStr,
+        T::Err: fmt::Display,
+    {
+        let s = self.scalar()?;
+        s.parse()
+            .map_err(|e| Error(format!("Unable to parse '{}': {}", s, e)))
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> de::Deserializer<'de> for Part<'a> {
+    type Error = Error;
+
+    /// Only reached for values whose type is not known up front, i.e. free-form values and
+    /// additional properties, which can't be told apart from strings.
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.raw == NULL {
+            return visitor.visit_unit();
+        }
+        Err(Error(format!(
+            "Free-form value '{}' has no parameter representation",
+            self.raw
+        )))
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.raw == EMPTY {
+            return visitor.visit_string(String::new());
+        }
+        visitor.visit_string(self.scalar()?.into_owned())
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.raw == NULL {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.explode && self.separator == '&' && !self.nested {
+            return Err(Error(EXPLODED_ARRAY.to_string()));
+        }
+        let items = self.items(',')?;
+        visitor.visit_seq(de::value::SeqDeserializer::new(
+            items.iter().map(|item| Part::nested(item)),
+        ))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let items = self.items(self.separator)?;
+        let mut entries = Vec::new();
+        if self.explode {
+            for item in &items {
+                let (key, value) = item
+                    .split_once('=')
+                    .ok_or_else(|| Error(format!("Missing '=' in '{}'", item)))?;
+                entries.push((key, value));
+            }
+        } else {
+            if items.len() % 2 != 0 {
+                return Err(Error(format!(
+                    "Missing value for key '{}'",
+                    items[items.len() - 1]
+                )));
+            }
+            for pair in items.chunks(2) {
+                entries.push((pair[0].as_str(), pair[1].as_str()));
+            }
+        }
+        visitor.visit_map(de::value::MapDeserializer::new(
+            entries
+                .into_iter()
+                .map(|(k, v)| (Part::nested(k), Part::nested(v))),
+        ))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(self.scalar()?.into_owned().into_deserializer())
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bytes byte_buf unit unit_struct
+    }
+}
+
+impl<'de, 'a> IntoDeserializer<'de, Error> for Part<'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+/// The parameter `name` of a query string.
+struct Query<'a> {
+    name: &'a str,
+    query: &'a str,
+    explode: bool,
+}
+
+impl<'a> Query<'a> {
+    /// The encoded values of the pairs named `name`.
+    fn values(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .filter_map(move |pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (decode(key).ok()? == self.name).then_some(value)
+            })
+    }
+
+    /// The value of the first pair named `name`.
+    fn value(&self) -> Result<Part<'a>, Error> {
+        let value = self
+            .values()
+            .next()
+            .ok_or_else(|| Error(format!("Missing query parameter '{}'", self.name)))?;
+        Ok(Part {
+            raw: Cow::Borrowed(value),
+            nested: false,
+            separator: ',',
+            explode: false,
+        })
+    }
+}
+
+macro_rules! deserialize_value {
+    ($($method:ident($($arg:ident: $type:ty),*),)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $type,)* visitor: V) -> Result<V::Value, Error> {
+                self.value()?.$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> de::Deserializer<'de> for Query<'a> {
+    type Error = Error;
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.values().next().is_none() {
+            visitor.visit_none()
+        } else if self.explode {
+            visitor.visit_some(self)
+        } else {
+            self.value()?.deserialize_option(visitor)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if !self.explode {
+            return self.value()?.deserialize_seq(visitor);
+        }
+        visitor.visit_seq(de::value::SeqDeserializer::new(
+            self.values().map(Part::nested),
+        ))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if !self.explode {
+            return self.value()?.deserialize_map(visitor);
+        }
+        Part {
+            raw: Cow::Borrowed(self.query),
+            nested: false,
+            separator: '&',
+            explode: true,
+        }
+        .deserialize_map(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_map(visitor)
+    }
+
+    deserialize_value! {
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    }
+}
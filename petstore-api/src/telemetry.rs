@@ -0,0 +1,424 @@
+//! Logging, tracing and metrics for the generated operations.
+//!
+//! Every operation handler is wrapped in an [`OperationLayer`], which
+//! - continues the W3C trace context (`traceparent`, `tracestate`) of the request,
+//! - records a `server` span carrying the OpenAPI `operationId`, the request id and the HTTP
+//!   semantic convention attributes,
+//! - counts requests and records their latency, labelled by operation, method and status,
+//! - logs an access log line when the response is ready.
+//!
+//! The [`RequestIdLayer`] in front of the router propagates the `X-Request-Id` of requests, or
+//! generates one, and adds it to the response and to JSON error bodies.
+//!
+//! Logs and spans are handled by the subscriber installed by [`init`]. Metrics are rendered in
+//! the Prometheus text format at `/metrics`, see [`metrics_router`].
+
+use std::{
+    convert::Infallible,
+    sync::OnceLock,
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    response::Response,
+    routing::get,
+    Router,
+};
+use futures::future::BoxFuture;
+use http::{
+    header::{CONTENT_LENGTH, CONTENT_TYPE, USER_AGENT},
+    HeaderName, HeaderValue, Request,
+};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use opentelemetry::{global, trace::TraceError, trace::TracerProvider as _, KeyValue};
+use opentelemetry_http::HeaderExtractor;
+use opentelemetry_sdk::{
+    export::trace::SpanExporter, propagation::TraceContextPropagator, runtime,
+    trace::TracerProvider, Resource,
+};
+use serde_json::Value;
+use tower::{Layer, Service};
+use tracing::{error, field::Empty, Instrument};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+/// Header carrying the request id.
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Property of JSON error bodies carrying the request id.
+const REQUEST_ID_PROPERTY: &str = "requestId";
+
+/// Counter of handled requests.
+pub const REQUESTS_TOTAL: &str = "http_server_requests_total";
+
+/// Histogram of request latencies in seconds.
+pub const REQUEST_DURATION: &str = "http_server_request_duration_seconds";
+
+/// Latency histogram buckets in seconds.
+const DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Output format of the logs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Multi-line, human readable output.
+    #[default]
+    Pretty,
+    /// One JSON object per line, including the fields of the enclosing spans.
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "pretty" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!(
+                "Unknown log format '{}', expected 'pretty' or 'json'",
+                s
+            )),
+        }
+    }
+}
+
+/// Logging configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogConfig {
+    pub format: LogFormat,
+    /// Level filter directives, e.g. `info,petstore_api=debug`.
+    pub filter: String,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        LogConfig {
+            format: LogFormat::default(),
+            filter: "info".to_string(),
+        }
+    }
+}
+
+impl LogConfig {
+    /// Read the configuration from `LOG_FORMAT` (`pretty` or `json`) and `RUST_LOG`.
+    pub fn from_env() -> Result<Self, String> {
+        let default = LogConfig::default();
+        Ok(LogConfig {
+            format: match std::env::var("LOG_FORMAT") {
+                Ok(format) => format.parse()?,
+                Err(_) => default.format,
+            },
+            filter: std::env::var("RUST_LOG").unwrap_or(default.filter),
+        })
+    }
+}
+
+/// Install a `tracing` subscriber logging to stdout as configured by `config`.
+///
+/// If `OTEL_EXPORTER_OTLP_ENDPOINT` is set, spans are also exported over OTLP/gRPC, and the
+/// returned provider should be shut down before exiting to flush them. Must be called within a
+/// Tokio runtime.
+pub fn init(
+    service_name: &'static str,
+    config: &LogConfig,
+) -> Result<Option<TracerProvider>, TraceError> {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let provider = match std::env::var_os("OTEL_EXPORTER_OTLP_ENDPOINT") {
+        Some(_) => {
+            let exporter = opentelemetry_otlp::SpanExporter::builder()
+                .with_tonic()
+                .build()?;
+            Some(tracer_provider(service_name, exporter))
+        }
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(EnvFilter::new(&config.filter))
+        .with(
+            (config.format == LogFormat::Pretty).then(|| tracing_subscriber::fmt::layer().pretty()),
+        )
+        .with((config.format == LogFormat::Json).then(|| {
+            tracing_subscriber::fmt::layer()
+                .json()
+                .flatten_event(true)
+                .with_span_list(true)
+        }))
+        .with(provider.as_ref().map(|provider| {
+            tracing_opentelemetry::layer().with_tracer(provider.tracer(service_name))
+        }))
+        .init();
+
+    Ok(provider)
+}
+
+/// Create a tracer provider exporting the spans of `service_name` in batches to `exporter`.
+pub fn tracer_provider<E>(service_name: &'static str, exporter: E) -> TracerProvider
+where
+    E: SpanExporter + 'static,
+{
+    TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new([KeyValue::new("service.name", service_name)]))
+        .build()
+}
+
+/// Handle of the Prometheus recorder, installed as the global `metrics` recorder on first use.
+pub fn prometheus() -> PrometheusHandle {
+    static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+    HANDLE
+        .get_or_init(|| {
+            let recorder = PrometheusBuilder::new()
+                .set_buckets_for_metric(
+                    Matcher::Full(REQUEST_DURATION.to_string()),
+                    DURATION_BUCKETS,
+                )
+                .expect("Latency buckets are not empty")
+                .build_recorder();
+            let handle = recorder.handle();
+            if let Err(e) = metrics::set_global_recorder(recorder) {
+                tracing::warn!(error = %e, "Metrics are not recorded");
+            }
+            handle
+        })
+        .clone()
+}
+
+/// Router serving the Prometheus metrics at `GET /metrics`.
+pub fn metrics_router() -> Router {
+    let handle = prometheus();
+
+    Router::new().route(
+        "/metrics",
+        get(move || async move {
+            (
+                [(http::header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+                handle.render(),
+            )
+        }),
+    )
+}
+
+/// Layer recording the span and metrics of an operation.
+#[derive(Debug, Clone, Copy)]
+pub struct OperationLayer {
+    operation_id: &'static str,
+    route: &'static str,
+}
+
+impl OperationLayer {
+    /// Layer for the operation `operation_id`, routed at `route`.
+    pub fn new(operation_id: &'static str, route: &'static str) -> Self {
+        OperationLayer {
+            operation_id,
+            route,
+        }
+    }
+}
+
+impl<S> Layer<S> for OperationLayer {
+    type Service = OperationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        OperationService {
+            inner,
+            operation_id: self.operation_id,
+            route: self.route,
+        }
+    }
+}
+
+/// Service created by [`OperationLayer`].
+#[derive(Debug, Clone)]
+pub struct OperationService<S> {
+    inner: S,
+    operation_id: &'static str,
+    route: &'static str,
+}
+
+impl<S> Service<Request<Body>> for OperationService<S>
+where
+    S: Service<Request<Body>, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let operation_id = self.operation_id;
+        let route = self.route;
+        let method = request.method().clone();
+        let request_bytes = request.body().size_hint().exact();
+
+        let span = tracing::info_span!(
+            "HTTP request",
+            otel.name = format!("{} {}", method, self.route),
+            otel.kind = "server",
+            otel.status_code = Empty,
+            openapi.operation_id = operation_id,
+            request_id = request
+                .headers()
+                .get(X_REQUEST_ID)
+                .and_then(|v| v.to_str().ok()),
+            http.request.method = %method,
+            http.route = self.route,
+            url.path = request.uri().path(),
+            url.query = request.uri().query(),
+            user_agent.original = request
+                .headers()
+                .get(USER_AGENT)
+                .and_then(|v| v.to_str().ok()),
+            http.response.status_code = Empty,
+        );
+        let parent = global::get_text_map_propagator(|propagator| {
+            propagator.extract(&HeaderExtractor(request.headers()))
+        });
+        span.set_parent(parent);
+
+        // the clone may not be ready, so keep the ready service for this call
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let start = Instant::now();
+
+        Box::pin(async move {
+            let response = inner.call(request).instrument(span.clone()).await?;
+
+            let status = response.status();
+            span.record("http.response.status_code", i64::from(status.as_u16()));
+            if status.is_server_error() {
+                span.record("otel.status_code", "ERROR");
+            }
+
+            let labels = [
+                ("operation", operation_id.to_string()),
+                ("method", method.to_string()),
+                ("status", status.as_u16().to_string()),
+            ];
+            let latency = start.elapsed();
+            metrics::counter!(REQUESTS_TOTAL, &labels).increment(1);
+            metrics::histogram!(REQUEST_DURATION, &labels).record(latency.as_secs_f64());
+
+            span.in_scope(|| {
+                tracing::info!(
+                    method = %method,
+                    route,
+                    status = status.as_u16(),
+                    latency_ms = latency.as_secs_f64() * 1000.0,
+                    request_bytes,
+                    response_bytes = response.body().size_hint().exact(),
+                    "Request handled"
+                )
+            });
+
+            Ok(response)
+        })
+    }
+}
+
+/// Layer propagating or generating the `X-Request-Id` of requests.
+///
+/// The id is set on the request before it is routed, on the response, and as `requestId` on
+/// JSON object bodies of error responses.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestIdLayer;
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService { inner }
+    }
+}
+
+/// Service created by [`RequestIdLayer`].
+#[derive(Debug, Clone)]
+pub struct RequestIdService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for RequestIdService<S>
+where
+    S: Service<Request<Body>, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<Body>) -> Self::Future {
+        let request_id = match request.headers().get(X_REQUEST_ID) {
+            Some(request_id) if !request_id.is_empty() => request_id.clone(),
+            _ => {
+                let request_id = HeaderValue::try_from(uuid::Uuid::new_v4().to_string())
+                    .expect("UUID is a valid header value");
+                request
+                    .headers_mut()
+                    .insert(X_REQUEST_ID, request_id.clone());
+                request_id
+            }
+        };
+
+        // the clone may not be ready, so keep the ready service for this call
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let response = inner.call(request).await?;
+            let mut response = add_request_id(response, &request_id).await;
+            response.headers_mut().insert(X_REQUEST_ID, request_id);
+            Ok(response)
+        })
+    }
+}
+
+/// Add the request id to the JSON object body of an error response.
+async fn add_request_id(response: Response, request_id: &HeaderValue) -> Response {
+    let is_error = response.status().is_client_error() || response.status().is_server_error();
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    let Ok(request_id) = request_id.to_str() else {
+        return response;
+    };
+    if !is_error || !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            error!(error = %e, "Unable to read error response");
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+
+    let body = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(Value::Object(mut error)) => {
+            error
+                .entry(REQUEST_ID_PROPERTY)
+                .or_insert_with(|| request_id.into());
+            serde_json::to_vec(&error).map(Bytes::from).unwrap_or(bytes)
+        }
+        _ => bytes,
+    };
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(body))
+}
@@ -3,10 +3,128 @@ use std::{mem, str::FromStr};
 use base64::{engine::general_purpose, Engine};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+/// Free-form JSON value, e.g. of an object schema without declared properties.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-#[allow(dead_code)]
 pub struct Object(serde_json::Value);
 
+impl Object {
+    /// Wrap a JSON value.
+    pub fn new(value: serde_json::Value) -> Self {
+        Object(value)
+    }
+
+    /// The wrapped JSON value.
+    pub fn as_value(&self) -> &serde_json::Value {
+        &self.0
+    }
+
+    /// Unwrap the JSON value.
+    pub fn into_value(self) -> serde_json::Value {
+        self.0
+    }
+
+    /// Property `key`, if the value is a JSON object with that property.
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.0.get(key)
+    }
+
+    /// Property `key` converted to `T`, `Ok(None)` if there is no such property.
+    pub fn get_as<T: serde::de::DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, serde_json::Error> {
+        self.get(key).map(|value| T::deserialize(value)).transpose()
+    }
+
+    /// Property `key`, if it is a string.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(serde_json::Value::as_str)
+    }
+
+    /// Property `key`, if it is an integer which fits into an `i64`.
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        self.get(key).and_then(serde_json::Value::as_i64)
+    }
+
+    /// Property `key`, if it is a number.
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.get(key).and_then(serde_json::Value::as_f64)
+    }
+
+    /// Property `key`, if it is a boolean.
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).and_then(serde_json::Value::as_bool)
+    }
+
+    /// Value at the [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901) `pointer`, e.g.
+    /// `/size/height`.
+    pub fn pointer(&self, pointer: &str) -> Option<&serde_json::Value> {
+        self.0.pointer(pointer)
+    }
+
+    /// Mutable value at the JSON Pointer `pointer`.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut serde_json::Value> {
+        self.0.pointer_mut(pointer)
+    }
+
+    /// Value at the JSON Pointer `pointer` converted to `T`, `Ok(None)` if there is no such value.
+    pub fn pointer_as<T: serde::de::DeserializeOwned>(
+        &self,
+        pointer: &str,
+    ) -> Result<Option<T>, serde_json::Error> {
+        self.pointer(pointer)
+            .map(|value| T::deserialize(value))
+            .transpose()
+    }
+
+    /// Set property `key`, replacing the value by an empty JSON object first if it is not one.
+    /// Returns the previous value of the property.
+    pub fn insert<T: Serialize>(
+        &mut self,
+        key: impl Into<String>,
+        value: T,
+    ) -> Result<Option<serde_json::Value>, serde_json::Error> {
+        let value = serde_json::to_value(value)?;
+        if !self.0.is_object() {
+            self.0 = serde_json::Value::Object(serde_json::Map::new());
+        }
+        Ok(self
+            .0
+            .as_object_mut()
+            .and_then(|object| object.insert(key.into(), value)))
+    }
+
+    /// Remove property `key`, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<serde_json::Value> {
+        self.0.as_object_mut().and_then(|object| object.remove(key))
+    }
+}
+
+impl Default for Object {
+    /// An empty JSON object.
+    fn default() -> Self {
+        Object(serde_json::Value::Object(serde_json::Map::new()))
+    }
+}
+
+impl From<serde_json::Value> for Object {
+    fn from(value: serde_json::Value) -> Self {
+        Object(value)
+    }
+}
+
+impl From<serde_json::Map<String, serde_json::Value>> for Object {
+    fn from(object: serde_json::Map<String, serde_json::Value>) -> Self {
+        Object(serde_json::Value::Object(object))
+    }
+}
+
+impl From<Object> for serde_json::Value {
+    fn from(object: Object) -> Self {
+        object.0
+    }
+}
+
 impl validator::Validate for Object {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         Ok(())
@@ -41,6 +159,24 @@ where
     })
 }
 
+/// Converts the value of a Diesel `Nullable` column to an optional nullable property, which is
+/// always present, and `null` if the column is NULL
+pub fn from_nullable_column<T>(value: Option<T>) -> Option<Nullable<T>> {
+    Some(Nullable::from_option(value))
+}
+
+/// Converts an optional nullable property to the value of a Diesel `Nullable` column to insert,
+/// which is NULL if the property is absent or `null`
+pub fn into_nullable_column<T>(value: Option<Nullable<T>>) -> Option<T> {
+    value.and_then(Nullable::into_option)
+}
+
+/// Converts an optional nullable property to a field of a Diesel `AsChangeset` struct, which
+/// leaves the column unchanged if the property is absent, and sets it to NULL if it is `null`
+pub fn into_nullable_changeset<T>(value: Option<Nullable<T>>) -> Option<Option<T>> {
+    value.map(Nullable::into_option)
+}
+
 /// The Nullable type. Represents a value which may be specified as null on an API.
 /// Note that this is distinct from a value that is optional and not present!
 ///
@@ -528,9 +664,50 @@ impl<T> Nullable<T> {
     pub fn take(&mut self) -> Nullable<T> {
         mem::replace(self, Nullable::Null)
     }
+
+    /////////////////////////////////////////////////////////////////////////
+    // Converting to and from Option
+    /////////////////////////////////////////////////////////////////////////
+
+    /// Converts an `Option<T>`, e.g. the value of a Diesel `Nullable` column, to a
+    /// `Nullable<T>`, with `None` as `Null`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use petstore_api::types::Nullable;
+    ///
+    /// assert_eq!(Nullable::from_option(Some(2)), Nullable::Present(2));
+    /// assert_eq!(Nullable::<u32>::from_option(None), Nullable::Null);
+    /// ```
+    #[inline]
+    pub fn from_option(value: Option<T>) -> Nullable<T> {
+        match value {
+            Some(x) => Nullable::Present(x),
+            None => Nullable::Null,
+        }
+    }
+
+    /// Converts the Nullable to an `Option<T>`, with `Null` as `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use petstore_api::types::Nullable;
+    ///
+    /// assert_eq!(Nullable::Present(2).into_option(), Some(2));
+    /// assert_eq!(Nullable::<u32>::Null.into_option(), None);
+    /// ```
+    #[inline]
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Nullable::Present(x) => Some(x),
+            Nullable::Null => None,
+        }
+    }
 }
 
-impl<'a, T: Clone> Nullable<&'a T> {
+impl<T: Clone> Nullable<&T> {
     /// Maps an `Nullable<&T>` to an `Nullable<T>` by cloning the contents of the
     /// Nullable.
     ///
@@ -632,6 +809,16 @@ where
     }
 }
 
+impl<T: validator::Validate> validator::Validate for Nullable<T> {
+    /// `null` is valid, a present value is validated.
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        match self {
+            Nullable::Present(inner) => inner.validate(),
+            Nullable::Null => Ok(()),
+        }
+    }
+}
+
 #[inline(never)]
 #[cold]
 fn expect_failed(msg: &str) -> ! {
@@ -663,3 +850,596 @@ impl<'de> Deserialize<'de> for ByteArray {
         }
     }
 }
+
+/// An array response body, either collected up front or produced item by item.
+///
+/// Streams are sent as they are polled, so large collections never have to be held in memory.
+/// An error from the stream aborts the response.
+pub enum ArrayBody<T> {
+    /// All items are known up front.
+    Vec(Vec<T>),
+    /// Items are produced by a stream.
+    Stream(futures::stream::BoxStream<'static, Result<T, String>>),
+}
+
+impl<T> ArrayBody<T> {
+    /// Wrap a stream of items.
+    pub fn stream<S>(stream: S) -> Self
+    where
+        S: futures::Stream<Item = Result<T, String>> + Send + 'static,
+    {
+        ArrayBody::Stream(Box::pin(stream))
+    }
+}
+
+impl<T> From<Vec<T>> for ArrayBody<T> {
+    fn from(items: Vec<T>) -> Self {
+        ArrayBody::Vec(items)
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for ArrayBody<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        ArrayBody::Vec(iter.into_iter().collect())
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for ArrayBody<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArrayBody::Vec(items) => f.debug_tuple("Vec").field(items).finish(),
+            ArrayBody::Stream(_) => f.write_str("Stream(..)"),
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for ArrayBody<T> {
+    /// Streams are never equal to anything.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ArrayBody::Vec(a), ArrayBody::Vec(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for ArrayBody<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ArrayBody::Vec(items) => items.serialize(serializer),
+            ArrayBody::Stream(_) => Err(serde::ser::Error::custom(
+                "a streaming ArrayBody can only be sent as a response",
+            )),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ArrayBody<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<T>::deserialize(deserializer).map(ArrayBody::Vec)
+    }
+}
+
+/// A binary response body, either held in memory or streamed, e.g. from a file.
+///
+/// Without an explicit content type the response is sent with the first media type the
+/// operation declares for it.
+pub struct BinaryBody {
+    content_type: Option<String>,
+    content: BinaryContent,
+}
+
+enum BinaryContent {
+    Bytes(bytes::Bytes),
+    Stream(futures::stream::BoxStream<'static, std::io::Result<bytes::Bytes>>),
+}
+
+impl BinaryBody {
+    /// Wrap bytes held in memory.
+    pub fn bytes(bytes: impl Into<bytes::Bytes>) -> Self {
+        BinaryBody {
+            content_type: None,
+            content: BinaryContent::Bytes(bytes.into()),
+        }
+    }
+
+    /// Wrap a stream of chunks, which are sent as they are polled.
+    ///
+    /// An error from the stream aborts the response.
+    pub fn stream<S>(stream: S) -> Self
+    where
+        S: futures::Stream<Item = std::io::Result<bytes::Bytes>> + Send + 'static,
+    {
+        BinaryBody {
+            content_type: None,
+            content: BinaryContent::Stream(Box::pin(stream)),
+        }
+    }
+
+    /// Send the body with `content_type`, which should be one of the media types the
+    /// operation declares.
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// The bytes of the body, or `None` if it is streamed.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match &self.content {
+            BinaryContent::Bytes(bytes) => Some(bytes),
+            BinaryContent::Stream(_) => None,
+        }
+    }
+
+    pub fn into_body(self) -> axum::body::Body {
+        match self.content {
+            BinaryContent::Bytes(bytes) => axum::body::Body::from(bytes),
+            BinaryContent::Stream(stream) => axum::body::Body::from_stream(stream),
+        }
+    }
+}
+
+impl From<Vec<u8>> for BinaryBody {
+    fn from(bytes: Vec<u8>) -> Self {
+        BinaryBody::bytes(bytes)
+    }
+}
+
+impl From<bytes::Bytes> for BinaryBody {
+    fn from(bytes: bytes::Bytes) -> Self {
+        BinaryBody::bytes(bytes)
+    }
+}
+
+impl From<ByteArray> for BinaryBody {
+    fn from(bytes: ByteArray) -> Self {
+        BinaryBody::bytes(bytes.0)
+    }
+}
+
+impl std::fmt::Debug for BinaryBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("BinaryBody");
+        debug.field("content_type", &self.content_type);
+        match &self.content {
+            BinaryContent::Bytes(bytes) => debug.field("len", &bytes.len()),
+            BinaryContent::Stream(_) => debug.field("stream", &".."),
+        };
+        debug.finish()
+    }
+}
+
+impl PartialEq for BinaryBody {
+    /// Streams are never equal to anything.
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.content, &other.content) {
+            (BinaryContent::Bytes(a), BinaryContent::Bytes(b)) => {
+                self.content_type == other.content_type && a == b
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Base64-encoded, like [`ByteArray`].
+impl Serialize for BinaryBody {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match &self.content {
+            BinaryContent::Bytes(bytes) => {
+                serializer.serialize_str(&general_purpose::STANDARD.encode(bytes))
+            }
+            BinaryContent::Stream(_) => Err(serde::ser::Error::custom(
+                "a streaming BinaryBody can only be sent as a response",
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BinaryBody {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ByteArray::deserialize(deserializer).map(BinaryBody::from)
+    }
+}
+
+/// Request body of an operation which accepts a file, either as a part of a
+/// `multipart/form-data` request or as the whole request body.
+///
+/// The request body is not read up front, so uploads can be streamed to their destination.
+#[derive(Debug)]
+pub enum FileUpload {
+    /// A `multipart/form-data` request.
+    Multipart(axum_extra::extract::Multipart),
+    /// Any other request, whose body is the file itself.
+    Raw {
+        /// Content-Type of the request, if it has one.
+        content_type: Option<String>,
+        body: axum::body::Body,
+    },
+}
+
+/// A file received by an upload operation.
+pub struct UploadedFile {
+    /// File name given by the client, if any.
+    pub file_name: Option<String>,
+    /// Media type given by the client, if any.
+    pub content_type: Option<String>,
+    /// Content of the file, read from the request as it is polled.
+    ///
+    /// Malformed or oversized requests yield an error of kind [`std::io::ErrorKind::InvalidData`].
+    pub data: futures::stream::BoxStream<'static, std::io::Result<bytes::Bytes>>,
+}
+
+impl std::fmt::Debug for UploadedFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UploadedFile")
+            .field("file_name", &self.file_name)
+            .field("content_type", &self.content_type)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FileUpload {
+    /// Media type of the request, without parameters.
+    ///
+    /// Raw uploads without a Content-Type are taken to be `application/octet-stream`.
+    pub fn media_type(&self) -> String {
+        match self {
+            FileUpload::Multipart(_) => "multipart/form-data".to_string(),
+            FileUpload::Raw { content_type, .. } => content_type
+                .as_deref()
+                .and_then(|v| v.split(';').next())
+                .map(|v| v.trim().to_ascii_lowercase())
+                .unwrap_or_else(|| "application/octet-stream".to_string()),
+        }
+    }
+
+    /// The uploaded file: the first part named `field` of a multipart request, or the body
+    /// of any other request.
+    pub async fn into_file(self, field: &str) -> std::io::Result<UploadedFile> {
+        use futures::{StreamExt, TryStreamExt};
+
+        fn invalid<E>(e: E) -> std::io::Error
+        where
+            E: Into<Box<dyn std::error::Error + Send + Sync>>,
+        {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+        }
+
+        match self {
+            FileUpload::Multipart(mut multipart) => loop {
+                let Some(part) = multipart.next_field().await.map_err(invalid)? else {
+                    return Err(invalid(format!("Missing part {}", field)));
+                };
+                if part.name() == Some(field) {
+                    return Ok(UploadedFile {
+                        file_name: part.file_name().map(str::to_string),
+                        content_type: part.content_type().map(str::to_string),
+                        data: part.map_err(invalid).boxed(),
+                    });
+                }
+            },
+            FileUpload::Raw { content_type, body } => Ok(UploadedFile {
+                file_name: None,
+                content_type,
+                data: body.into_data_stream().map_err(invalid).boxed(),
+            }),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> axum::extract::FromRequest<S> for FileUpload
+where
+    S: Send + Sync,
+{
+    type Rejection = axum::response::Response;
+
+    async fn from_request(req: axum::extract::Request, state: &S) -> Result<Self, Self::Rejection> {
+        use axum::{response::IntoResponse, RequestExt};
+
+        let content_type = req
+            .headers()
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let multipart = content_type.as_deref().is_some_and(|v| {
+            v.trim_start()
+                .to_ascii_lowercase()
+                .starts_with("multipart/form-data")
+        });
+
+        if multipart {
+            axum_extra::extract::Multipart::from_request(req, state)
+                .await
+                .map(FileUpload::Multipart)
+                .map_err(IntoResponse::into_response)
+        } else {
+            // honour the body limit of the operation like the other extractors do
+            Ok(FileUpload::Raw {
+                content_type,
+                body: req.into_limited_body(),
+            })
+        }
+    }
+}
+
+/// A file part of a `multipart/form-data` request body, read within the size limit of the part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilePart {
+    /// File name given by the client, if any.
+    pub file_name: Option<String>,
+    /// Media type of the part, if given.
+    pub content_type: Option<String>,
+    pub data: bytes::Bytes,
+}
+
+/// Serializes the metadata and size of the part rather than its content, e.g. for the values of
+/// validation errors.
+impl serde::Serialize for FilePart {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut part = serializer.serialize_struct("FilePart", 3)?;
+        part.serialize_field("fileName", &self.file_name)?;
+        part.serialize_field("contentType", &self.content_type)?;
+        part.serialize_field("size", &self.data.len())?;
+        part.end()
+    }
+}
+
+/// Whether `media_type` is XML: `application/xml`, `text/xml`, or a `+xml` structured syntax.
+pub(crate) fn is_xml(media_type: &str) -> bool {
+    let media_type = media_type.to_ascii_lowercase();
+    media_type == "application/xml" || media_type == "text/xml" || media_type.ends_with("+xml")
+}
+
+/// Whether `media_type` is CBOR: `application/cbor`, or a `+cbor` structured syntax.
+pub(crate) fn is_cbor(media_type: &str) -> bool {
+    let media_type = media_type.to_ascii_lowercase();
+    media_type == "application/cbor" || media_type.ends_with("+cbor")
+}
+
+/// Request body of an operation which consumes JSON, `application/x-www-form-urlencoded`, XML
+/// or CBOR, decoded according to the Content-Type of the request. Other media types are decoded
+/// as JSON.
+///
+/// Form bodies are the `style=form, explode=true` representation of the properties of the
+/// schema, see [`crate::style::from_form_body`], and XML bodies follow the `xml` objects of the
+/// schema, see [`crate::xml`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RequestBody<T>(pub T);
+
+#[async_trait::async_trait]
+impl<T, S> axum::extract::FromRequest<S> for RequestBody<T>
+where
+    T: serde::de::DeserializeOwned + crate::xml::XmlSchema,
+    S: Send + Sync,
+{
+    type Rejection = axum::response::Response;
+
+    async fn from_request(req: axum::extract::Request, state: &S) -> Result<Self, Self::Rejection> {
+        use axum::response::IntoResponse;
+
+        let media_type = req
+            .headers()
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(';').next())
+            .map(|v| v.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        let form = media_type == "application/x-www-form-urlencoded";
+        if !form && !is_xml(&media_type) && !is_cbor(&media_type) {
+            return axum::Json::<T>::from_request(req, state)
+                .await
+                .map(|axum::Json(value)| RequestBody(value))
+                .map_err(IntoResponse::into_response);
+        }
+
+        let body = bytes::Bytes::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        let value = if form {
+            std::str::from_utf8(&body)
+                .map_err(|e| e.to_string())
+                .and_then(|body| crate::style::from_form_body(body).map_err(|e| e.to_string()))
+        } else if is_xml(&media_type) {
+            crate::xml::from_slice(&body).map_err(|e| e.to_string())
+        } else {
+            ciborium::from_reader(body.as_ref()).map_err(|e| e.to_string())
+        };
+        value.map(RequestBody).map_err(|e| {
+            (
+                http::StatusCode::UNPROCESSABLE_ENTITY,
+                format!("Failed to deserialize {} body: {}", media_type, e),
+            )
+                .into_response()
+        })
+    }
+}
+
+/// Headers and cookies of a response beyond those of the OpenAPI document, e.g. `Cache-Control`,
+/// `Location` or `ETag`, carried by the `extra` field of every generated `*ResponseHeaders`
+/// struct.
+///
+/// Headers replace any generated header of the same name. Cookies added to or removed from
+/// `cookies`, usually the jar of the request passed to the operation, are sent with `Set-Cookie`.
+#[derive(Debug, Clone, Default)]
+pub struct ExtraHeaders {
+    pub headers: http::HeaderMap,
+    pub cookies: axum_extra::extract::CookieJar,
+}
+
+impl ExtraHeaders {
+    /// Add a header, keeping other values of the same header.
+    pub fn header(mut self, name: http::HeaderName, value: http::HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Replace the cookie jar whose changes are sent with `Set-Cookie`.
+    pub fn cookies(mut self, cookies: axum_extra::extract::CookieJar) -> Self {
+        self.cookies = cookies;
+        self
+    }
+}
+
+impl PartialEq for ExtraHeaders {
+    /// Cookie jars are equal if they hold the same cookies, whether changed or not.
+    fn eq(&self, other: &Self) -> bool {
+        let cookies = |jar: &axum_extra::extract::CookieJar| {
+            let mut cookies = jar.iter().map(ToString::to_string).collect::<Vec<_>>();
+            cookies.sort();
+            cookies
+        };
+        self.headers == other.headers && cookies(&self.cookies) == cookies(&other.cookies)
+    }
+}
+
+/// Identity of a client authenticated with a TLS client certificate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientIdentity {
+    /// Subject distinguished name, e.g. `CN=client, O=Example`.
+    pub subject: String,
+    /// DNS names, email addresses and URIs of the subject alternative names.
+    pub alt_names: Vec<String>,
+    /// DER encoded certificate.
+    pub certificate: Vec<u8>,
+}
+
+#[cfg(feature = "arbitrary")]
+impl proptest::arbitrary::Arbitrary for Object {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        let leaf = prop_oneof![
+            Just(serde_json::Value::Null),
+            any::<bool>().prop_map(serde_json::Value::from),
+            any::<i64>().prop_map(serde_json::Value::from),
+            any::<String>().prop_map(serde_json::Value::from),
+        ];
+        let value = leaf.prop_recursive(3, 16, 4, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..4).prop_map(serde_json::Value::from),
+                prop::collection::btree_map(any::<String>(), inner, 0..4)
+                    .prop_map(|m| serde_json::Value::Object(m.into_iter().collect())),
+            ]
+        });
+        // free-form objects are JSON objects, with any values
+        prop::collection::btree_map(any::<String>(), value, 0..4)
+            .prop_map(|m| Object(serde_json::Value::Object(m.into_iter().collect())))
+            .boxed()
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<T> proptest::arbitrary::Arbitrary for Nullable<T>
+where
+    T: proptest::arbitrary::Arbitrary + 'static,
+{
+    type Parameters = T::Parameters;
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        prop::option::of(any_with::<T>(args))
+            .prop_map(|value| match value {
+                Some(value) => Nullable::Present(value),
+                None => Nullable::Null,
+            })
+            .boxed()
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl proptest::arbitrary::Arbitrary for ByteArray {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        any::<Vec<u8>>().prop_map(ByteArray).boxed()
+    }
+}
+
+/// Validate the `uniqueItems` constraint of an array.
+pub fn unique_items<T: PartialEq>(items: &[T]) -> Result<(), validator::ValidationError> {
+    for (i, item) in items.iter().enumerate() {
+        if items[..i].contains(item) {
+            return Err(validator::ValidationError::new("unique_items"));
+        }
+    }
+    Ok(())
+}
+
+/// Validate the `uniqueItems` constraint of a nullable array, `null` being valid.
+pub fn nullable_unique_items<T: PartialEq>(
+    items: &Nullable<Vec<T>>,
+) -> Result<(), validator::ValidationError> {
+    match items {
+        Nullable::Present(items) => unique_items(items),
+        Nullable::Null => Ok(()),
+    }
+}
+
+/// Validate `format: email` of a nullable string, `null` being valid.
+pub fn nullable_email(value: &Nullable<String>) -> Result<(), validator::ValidationError> {
+    match value {
+        Nullable::Present(value) if !validator::validate_email(value) => {
+            Err(validator::ValidationError::new("email"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Validate `format: uri` of a nullable string, `null` being valid.
+pub fn nullable_url(value: &Nullable<String>) -> Result<(), validator::ValidationError> {
+    match value {
+        Nullable::Present(value) if !validator::validate_url(value) => {
+            Err(validator::ValidationError::new("url"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Validate the values of a map, e.g. of typed `additionalProperties`.
+pub fn validate_map_values<T: validator::Validate>(
+    values: &std::collections::HashMap<String, T>,
+) -> Result<(), validator::ValidationError> {
+    for (key, value) in values {
+        if value.validate().is_err() {
+            let mut error = validator::ValidationError::new("map_values");
+            error.add_param("key".into(), key);
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+/// Strategy for `uuid::Uuid` fields, as `Uuid` does not implement `Arbitrary`.
+#[cfg(feature = "arbitrary")]
+pub fn arbitrary_uuid() -> impl proptest::strategy::Strategy<Value = uuid::Uuid> {
+    use proptest::prelude::*;
+
+    any::<u128>().prop_map(uuid::Uuid::from_u128)
+}
@@ -0,0 +1,51 @@
+//! Version 2 replaces the single nullable `tag` of a pet with a list of `tags`. Version 1 pets
+//! get their tag, if any, as the only tag; version 2 pets keep their first tag.
+//!
+//! Models of the same shape in both versions, like `Error`, convert with
+//! [`frunk::labelled_convert_from`] when the `conversion` feature is enabled.
+
+use crate::{models as v1, types::Nullable, v2::models as v2};
+
+impl From<v1::Pet> for v2::Pet {
+    fn from(pet: v1::Pet) -> Self {
+        let tags = match pet.tag {
+            Some(Nullable::Present(tag)) => Some(vec![tag]),
+            Some(Nullable::Null) | None => None,
+        };
+        v2::Pet {
+            id: pet.id,
+            name: pet.name,
+            tags,
+            attributes: pet.attributes,
+        }
+    }
+}
+
+impl From<v2::Pet> for v1::Pet {
+    fn from(pet: v2::Pet) -> Self {
+        let tag = pet
+            .tags
+            .and_then(|tags| tags.into_iter().next())
+            .map(Nullable::Present);
+        v1::Pet {
+            id: pet.id,
+            name: pet.name,
+            tag,
+            attributes: pet.attributes,
+        }
+    }
+}
+
+#[cfg(feature = "conversion")]
+impl From<v1::Error> for v2::Error {
+    fn from(error: v1::Error) -> Self {
+        frunk::labelled_convert_from(error)
+    }
+}
+
+#[cfg(feature = "conversion")]
+impl From<v2::Error> for v1::Error {
+    fn from(error: v2::Error) -> Self {
+        frunk::labelled_convert_from(error)
+    }
+}
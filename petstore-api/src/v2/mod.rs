@@ -0,0 +1,258 @@
+//! API version 2.0.0, generated from `api/openapi-v2.yaml`.
+
+use async_trait::async_trait;
+use axum::extract::*;
+use axum_extra::extract::{CookieJar, Multipart};
+use bytes::Bytes;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::types::*;
+
+pub const BASE_PATH: &str = "/v2";
+pub const API_VERSION: &str = "2.0.0";
+
+/// SHA-256 of the OpenAPI document, templates, options and generator this module was generated
+/// from, for `openapi-gen --check` to tell stale files from edited ones.
+pub const GENERATOR_INPUT_HASH: &str =
+    "91628d21cef6937c3cf1c2dec7484cd240a878331e3d2f3bd0ae8f54c81b2ea2";
+
+/// Version of the generator, covered by [`GENERATOR_INPUT_HASH`] with the `rustfmt`
+/// configuration formatting its output.
+pub const GENERATOR_VERSION: &str = "openapi-gen 1.0.0";
+
+/// The OpenAPI document this module was generated from.
+pub const SPEC: &str = include_str!("../../api/openapi-v2.yaml");
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum CreatePetsResponse {
+    /// Null response
+    Status201_NullResponse {
+        headers: models::CreatePets201ResponseHeaders,
+    },
+    /// Invalid pet
+    Status400_InvalidPet {
+        body: models::Error,
+        headers: models::CreatePets400ResponseHeaders,
+    },
+    /// Too many requests
+    Status429_TooManyRequests {
+        headers: models::CreatePets429ResponseHeaders,
+    },
+    /// unexpected error
+    Status0_UnexpectedError {
+        body: models::Error,
+        headers: models::CreatePetsDefaultResponseHeaders,
+    },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum ListPetsResponse {
+    /// A paged array of pets
+    Status200_APagedArrayOfPets {
+        body: ArrayBody<models::Pet>,
+        headers: models::ListPets200ResponseHeaders,
+    },
+    /// unexpected error
+    Status0_UnexpectedError {
+        body: models::Error,
+        headers: models::ListPetsDefaultResponseHeaders,
+    },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum ShowPetByIdResponse {
+    /// Expected response to a valid request
+    Status200_ExpectedResponseToAValidRequest {
+        body: models::Pet,
+        headers: models::ShowPetById200ResponseHeaders,
+    },
+    /// Pet not found
+    Status404_PetNotFound {
+        body: models::Error,
+        headers: models::ShowPetById404ResponseHeaders,
+    },
+    /// unexpected error
+    Status0_UnexpectedError {
+        body: models::Error,
+        headers: models::ShowPetByIdDefaultResponseHeaders,
+    },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum ShowPetPhotoResponse {
+    /// The photo
+    Status200_ThePhoto {
+        body: BinaryBody,
+        headers: models::ShowPetPhoto200ResponseHeaders,
+    },
+    /// A range of the photo
+    Status206_ARangeOfThePhoto {
+        body: BinaryBody,
+        headers: models::ShowPetPhoto206ResponseHeaders,
+    },
+    /// Pet or photo not found
+    Status404_PetOrPhotoNotFound {
+        body: models::Error,
+        headers: models::ShowPetPhoto404ResponseHeaders,
+    },
+    /// Range not satisfiable
+    Status416_RangeNotSatisfiable {
+        headers: models::ShowPetPhoto416ResponseHeaders,
+    },
+    /// unexpected error
+    Status0_UnexpectedError {
+        body: models::Error,
+        headers: models::ShowPetPhotoDefaultResponseHeaders,
+    },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum UploadPetDocumentsResponse {
+    /// Documents stored
+    Status201_DocumentsStored {
+        body: ArrayBody<models::Document>,
+        headers: models::UploadPetDocuments201ResponseHeaders,
+    },
+    /// Invalid upload
+    Status400_InvalidUpload {
+        body: models::Error,
+        headers: models::UploadPetDocuments400ResponseHeaders,
+    },
+    /// Pet not found
+    Status404_PetNotFound {
+        body: models::Error,
+        headers: models::UploadPetDocuments404ResponseHeaders,
+    },
+    /// unexpected error
+    Status0_UnexpectedError {
+        body: models::Error,
+        headers: models::UploadPetDocumentsDefaultResponseHeaders,
+    },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum UploadPetPhotoResponse {
+    /// Photo stored
+    Status204_PhotoStored {
+        headers: models::UploadPetPhoto204ResponseHeaders,
+    },
+    /// Invalid upload
+    Status400_InvalidUpload {
+        body: models::Error,
+        headers: models::UploadPetPhoto400ResponseHeaders,
+    },
+    /// Pet not found
+    Status404_PetNotFound {
+        body: models::Error,
+        headers: models::UploadPetPhoto404ResponseHeaders,
+    },
+    /// unexpected error
+    Status0_UnexpectedError {
+        body: models::Error,
+        headers: models::UploadPetPhotoDefaultResponseHeaders,
+    },
+}
+
+/// API
+#[async_trait]
+#[allow(clippy::ptr_arg)]
+pub trait Api {
+    /// Create a pet.
+    ///
+    /// CreatePets - POST /v2/pets
+    async fn create_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<ClientIdentity>,
+        header_params: models::CreatePetsHeaderParams,
+        body: models::Pet,
+    ) -> Result<CreatePetsResponse, String>;
+
+    /// List all pets.
+    ///
+    /// ListPets - GET /v2/pets
+    async fn list_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<ClientIdentity>,
+        cookie_params: models::ListPetsCookieParams,
+        query_params: models::ListPetsQueryParams,
+    ) -> Result<ListPetsResponse, String>;
+
+    /// Info for a specific pet.
+    ///
+    /// ShowPetById - GET /v2/pets/{petId}
+    async fn show_pet_by_id(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<ClientIdentity>,
+        path_params: models::ShowPetByIdPathParams,
+    ) -> Result<ShowPetByIdResponse, String>;
+
+    /// Download the photo of a pet.
+    ///
+    /// ShowPetPhoto - GET /v2/pets/{petId}/photo
+    async fn show_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<ClientIdentity>,
+        header_params: models::ShowPetPhotoHeaderParams,
+        path_params: models::ShowPetPhotoPathParams,
+    ) -> Result<ShowPetPhotoResponse, String>;
+
+    /// Upload documents of a pet.
+    ///
+    /// UploadPetDocuments - POST /v2/pets/{petId}/documents
+    async fn upload_pet_documents(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<ClientIdentity>,
+        path_params: models::UploadPetDocumentsPathParams,
+        body: models::UploadPetDocumentsForm,
+    ) -> Result<UploadPetDocumentsResponse, String>;
+
+    /// Upload the photo of a pet.
+    ///
+    /// UploadPetPhoto - POST /v2/pets/{petId}/photo
+    async fn upload_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<ClientIdentity>,
+        path_params: models::UploadPetPhotoPathParams,
+        body: FileUpload,
+    ) -> Result<UploadPetPhotoResponse, String>;
+}
+
+#[cfg(feature = "server")]
+pub mod server;
+
+pub mod models;
+
+/// Conversions between the models of this version and those of the crate root, written by
+/// hand as the generator can't tell how changed properties map.
+mod conversions;
@@ -0,0 +1,911 @@
+#![allow(unused_qualifications)]
+
+use http::HeaderValue;
+use validator::Validate;
+
+#[cfg(feature = "server")]
+use crate::header;
+use crate::v2::models;
+use crate::{style, types::*, xml};
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct CreatePetsHeaderParams {
+    /// Key identifying retries of the same request
+    #[validate(length(min = 1, max = 64))]
+    pub idempotency_key: Option<String>,
+}
+
+/// Headers of the 201 response of CreatePets.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CreatePets201ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl CreatePets201ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 400 response of CreatePets.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CreatePets400ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl CreatePets400ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 429 response of CreatePets.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CreatePets429ResponseHeaders {
+    /// Seconds until the next request is accepted
+    #[serde(rename = "Retry-After")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<i32>,
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl CreatePets429ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            retry_after: None,
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the default response of CreatePets.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CreatePetsDefaultResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl CreatePetsDefaultResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct ListPetsCookieParams {
+    /// Session of the client
+    #[validate(length(max = 128))]
+    pub session: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct ListPetsQueryParams {
+    /// How many items to return at one time (max 100)
+    #[serde(rename = "limit")]
+    #[validate(range(max = 100))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+}
+
+/// Headers of the 200 response of ListPets.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ListPets200ResponseHeaders {
+    /// A link to the next page of responses
+    #[serde(rename = "x-next")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x_next: Option<String>,
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ListPets200ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            x_next: None,
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the default response of ListPets.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ListPetsDefaultResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ListPetsDefaultResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct ShowPetByIdPathParams {
+    /// The id of the pet to retrieve
+    #[validate(range(min = 1))]
+    pub pet_id: i64,
+}
+
+/// Headers of the 200 response of ShowPetById.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetById200ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetById200ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 404 response of ShowPetById.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetById404ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetById404ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the default response of ShowPetById.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetByIdDefaultResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetByIdDefaultResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct ShowPetPhotoHeaderParams {
+    /// Byte range of the photo to return, e.g. `bytes=0-1023`
+    #[validate(length(max = 128))]
+    pub range: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct ShowPetPhotoPathParams {
+    /// The id of the pet
+    #[validate(range(min = 1))]
+    pub pet_id: i64,
+}
+
+/// Headers of the 200 response of ShowPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetPhoto200ResponseHeaders {
+    /// Size of the photo in bytes
+    #[serde(rename = "Content-Length")]
+    pub content_length: i64,
+    /// Unit of the ranges which can be requested
+    #[serde(rename = "Accept-Ranges")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_ranges: Option<String>,
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetPhoto200ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(content_length: i64) -> Self {
+        Self {
+            content_length,
+            accept_ranges: None,
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 206 response of ShowPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetPhoto206ResponseHeaders {
+    /// Size of the range in bytes
+    #[serde(rename = "Content-Length")]
+    pub content_length: i64,
+    /// Position of the range within the photo
+    #[serde(rename = "Content-Range")]
+    pub content_range: String,
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetPhoto206ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(content_length: i64, content_range: String) -> Self {
+        Self {
+            content_length,
+            content_range,
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 404 response of ShowPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetPhoto404ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetPhoto404ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 416 response of ShowPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetPhoto416ResponseHeaders {
+    /// Size of the photo, as `bytes */size`
+    #[serde(rename = "Content-Range")]
+    pub content_range: String,
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetPhoto416ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(content_range: String) -> Self {
+        Self {
+            content_range,
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the default response of ShowPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShowPetPhotoDefaultResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl ShowPetPhotoDefaultResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct UploadPetDocumentsPathParams {
+    /// The id of the pet
+    #[validate(range(min = 1))]
+    pub pet_id: i64,
+}
+
+/// The `multipart/form-data` request body of UploadPetDocuments.
+#[derive(Debug, Clone, PartialEq, validator::Validate)]
+pub struct UploadPetDocumentsForm {
+    /// What the documents are about
+    #[validate(length(max = 200))]
+    pub description: Option<String>,
+    /// Photos of the pet, at most 2 MiB each
+    #[validate(length(min = 1, max = 5))]
+    pub photos: Vec<FilePart>,
+    /// Vaccination certificate, at most 1 MiB
+    pub certificate: Option<FilePart>,
+}
+
+/// Headers of the 201 response of UploadPetDocuments.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetDocuments201ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetDocuments201ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 400 response of UploadPetDocuments.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetDocuments400ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetDocuments400ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 404 response of UploadPetDocuments.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetDocuments404ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetDocuments404ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the default response of UploadPetDocuments.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetDocumentsDefaultResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetDocumentsDefaultResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct UploadPetPhotoPathParams {
+    /// The id of the pet
+    #[validate(range(min = 1))]
+    pub pet_id: i64,
+}
+
+/// Headers of the 204 response of UploadPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetPhoto204ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetPhoto204ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 400 response of UploadPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetPhoto400ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetPhoto400ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the 404 response of UploadPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetPhoto404ResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetPhoto404ResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// Headers of the default response of UploadPetPhoto.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UploadPetPhotoDefaultResponseHeaders {
+    /// Headers and cookies beyond those of the OpenAPI document.
+    #[serde(skip)]
+    pub extra: ExtraHeaders,
+}
+
+impl UploadPetPhotoDefaultResponseHeaders {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> Self {
+        Self {
+            extra: ExtraHeaders::default(),
+        }
+    }
+}
+
+/// A file stored with a pet
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct Document {
+    /// Name of the form part the file was sent in
+    #[serde(rename = "part")]
+    pub part: String,
+
+    #[serde(rename = "fileName")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+
+    #[serde(rename = "contentType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+
+    #[serde(rename = "size")]
+    #[validate(range(min = 0))]
+    pub size: i64,
+}
+
+impl Document {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(part: String, size: i64) -> Document {
+        Document {
+            part,
+            file_name: None,
+            content_type: None,
+            size,
+        }
+    }
+}
+
+/// Converts the Document value to the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/, which is also the value part of the
+/// `style=form, explode=false` query parameter representation
+impl std::fmt::Display for Document {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &style::to_string(self, style::Style::Simple, false)
+                .expect("Document is always serializable"),
+        )
+    }
+}
+
+/// Converts the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/ to a Document value
+impl std::str::FromStr for Document {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        style::from_str(s, style::Style::Simple, false).map_err(|e| e.to_string())
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<Document> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<Document>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<Document>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for Document - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Document> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <Document as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        "Unable to convert header value '{}' into Document - {}",
+                        value, err
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+/// Representation of Document values in XML, following the `xml` objects of the schema
+impl xml::XmlSchema for Document {
+    fn xml() -> xml::Xml {
+        xml::Xml {
+            name: "Document",
+            namespace: None,
+            prefix: None,
+            kind: xml::Kind::Object(&[
+                xml::Property {
+                    name: "part",
+                    xml_name: "part",
+                    attribute: false,
+                    array: None,
+                    schema: <String as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "fileName",
+                    xml_name: "fileName",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<String> as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "contentType",
+                    xml_name: "contentType",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<String> as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "size",
+                    xml_name: "size",
+                    attribute: false,
+                    array: None,
+                    schema: <i64 as xml::XmlSchema>::xml,
+                },
+            ]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct Error {
+    #[serde(rename = "code")]
+    pub code: i32,
+
+    #[serde(rename = "message")]
+    pub message: String,
+
+    /// Id of the request, as in its `X-Request-Id` header
+    #[serde(rename = "requestId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl Error {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(code: i32, message: String) -> Error {
+        Error {
+            code,
+            message,
+            request_id: None,
+        }
+    }
+}
+
+/// Converts the Error value to the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/, which is also the value part of the
+/// `style=form, explode=false` query parameter representation
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &style::to_string(self, style::Style::Simple, false)
+                .expect("Error is always serializable"),
+        )
+    }
+}
+
+/// Converts the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/ to a Error value
+impl std::str::FromStr for Error {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        style::from_str(s, style::Style::Simple, false).map_err(|e| e.to_string())
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<Error> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<Error>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<Error>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for Error - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Error> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => match <Error as std::str::FromStr>::from_str(value) {
+                std::result::Result::Ok(value) => {
+                    std::result::Result::Ok(header::IntoHeaderValue(value))
+                }
+                std::result::Result::Err(err) => std::result::Result::Err(format!(
+                    "Unable to convert header value '{}' into Error - {}",
+                    value, err
+                )),
+            },
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+/// Representation of Error values in XML, following the `xml` objects of the schema
+impl xml::XmlSchema for Error {
+    fn xml() -> xml::Xml {
+        xml::Xml {
+            name: "error",
+            namespace: None,
+            prefix: None,
+            kind: xml::Kind::Object(&[
+                xml::Property {
+                    name: "code",
+                    xml_name: "code",
+                    attribute: false,
+                    array: None,
+                    schema: <i32 as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "message",
+                    xml_name: "message",
+                    attribute: false,
+                    array: None,
+                    schema: <String as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "requestId",
+                    xml_name: "requestId",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<String> as xml::XmlSchema>::xml,
+                },
+            ]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub struct Pet {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "name")]
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+
+    /// Tags of the pet, replacing the single nullable `tag` of version 1
+    #[serde(rename = "tags")]
+    #[validate(length(max = 10))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+
+    /// Free-form attributes of the pet
+    #[serde(rename = "attributes")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<crate::types::Object>,
+}
+
+impl Pet {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(id: i64, name: String) -> Pet {
+        Pet {
+            id,
+            name,
+            tags: None,
+            attributes: None,
+        }
+    }
+}
+
+/// Converts the Pet value to the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/, which is also the value part of the
+/// `style=form, explode=false` query parameter representation
+impl std::fmt::Display for Pet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &style::to_string(self, style::Style::Simple, false)
+                .expect("Pet is always serializable"),
+        )
+    }
+}
+
+/// Converts the `style=simple, explode=false` representation specified in
+/// https://swagger.io/docs/specification/serialization/ to a Pet value
+impl std::str::FromStr for Pet {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        style::from_str(s, style::Style::Simple, false).map_err(|e| e.to_string())
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<Pet> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<Pet>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<Pet>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for Pet - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Pet> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => match <Pet as std::str::FromStr>::from_str(value) {
+                std::result::Result::Ok(value) => {
+                    std::result::Result::Ok(header::IntoHeaderValue(value))
+                }
+                std::result::Result::Err(err) => std::result::Result::Err(format!(
+                    "Unable to convert header value '{}' into Pet - {}",
+                    value, err
+                )),
+            },
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+/// Representation of Pet values in XML, following the `xml` objects of the schema
+impl xml::XmlSchema for Pet {
+    fn xml() -> xml::Xml {
+        xml::Xml {
+            name: "pet",
+            namespace: None,
+            prefix: None,
+            kind: xml::Kind::Object(&[
+                xml::Property {
+                    name: "id",
+                    xml_name: "id",
+                    attribute: true,
+                    array: None,
+                    schema: <i64 as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "name",
+                    xml_name: "name",
+                    attribute: false,
+                    array: None,
+                    schema: <String as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "tags",
+                    xml_name: "tags",
+                    attribute: false,
+                    array: Some(xml::Array {
+                        wrapped: true,
+                        item_name: "tag",
+                    }),
+                    schema: <Option<Vec<String>> as xml::XmlSchema>::xml,
+                },
+                xml::Property {
+                    name: "attributes",
+                    xml_name: "attributes",
+                    attribute: false,
+                    array: None,
+                    schema: <Option<crate::types::Object> as xml::XmlSchema>::xml,
+                },
+            ]),
+        }
+    }
+}
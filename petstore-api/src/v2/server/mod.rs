@@ -0,0 +1,1728 @@
+use std::{collections::HashMap, time::Duration};
+
+use axum::{
+    body::Body,
+    extract::*,
+    handler::Handler,
+    response::{IntoResponse, Response},
+    routing::*,
+};
+use axum_extra::extract::{
+    multipart::{Field, MultipartError},
+    CookieJar, Multipart,
+};
+use bytes::Bytes;
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use http::{
+    header::{ACCEPT, CONTENT_TYPE, SET_COOKIE},
+    HeaderMap, HeaderName, HeaderValue, Method, StatusCode,
+};
+use tower_http::{
+    compression::predicate::{DefaultPredicate, NotForContentType, Predicate},
+    cors::CorsLayer,
+};
+use tracing::error;
+use validator::{Validate, ValidationErrors};
+
+use crate::{
+    cache::{CachePolicy, ResponseCache},
+    contract::{Contract, ContractLayer, Mode},
+    deprecation::Deprecation,
+    header,
+    security::{RateLimit, RateLimiter, SecurityHeadersLayer},
+    telemetry::OperationLayer,
+    types::*,
+};
+
+#[allow(unused_imports)]
+use crate::v2::models;
+
+use crate::v2::{
+    Api, CreatePetsResponse, ListPetsResponse, ShowPetByIdResponse, ShowPetPhotoResponse,
+    UploadPetDocumentsResponse, UploadPetPhotoResponse, SPEC,
+};
+
+// negotiation and body helpers shared with the server of the crate root
+#[allow(unused_imports)]
+use crate::server::*;
+
+/// Setup API Server.
+pub fn new<I, A>(api_impl: I) -> Router
+where
+    I: AsRef<A> + Clone + Send + Sync + 'static,
+    A: Api + 'static,
+{
+    builder(api_impl).build()
+}
+
+/// Builder of the API router, to configure its layers.
+pub fn builder<I>(api_impl: I) -> Builder<I> {
+    Builder {
+        api_impl,
+        cors: None,
+        security_headers: true,
+        rate_limiter: RateLimiter::default(),
+        cache: ResponseCache::default(),
+        deprecation: None,
+        versions: Vec::new(),
+    }
+}
+
+/// Builder of the API router, created by [`builder`].
+#[derive(Debug)]
+pub struct Builder<I> {
+    api_impl: I,
+    cors: Option<CorsLayer>,
+    security_headers: bool,
+    rate_limiter: RateLimiter,
+    cache: ResponseCache,
+    deprecation: Option<Deprecation>,
+    versions: Vec<Router>,
+}
+
+impl<I> Builder<I> {
+    /// Handle CORS requests as configured by `cors`, e.g. [`crate::security::cors`].
+    pub fn cors(mut self, cors: CorsLayer) -> Self {
+        self.cors = Some(cors);
+        self
+    }
+
+    /// Set the standard security headers on responses, enabled by default.
+    pub fn security_headers(mut self, enabled: bool) -> Self {
+        self.security_headers = enabled;
+        self
+    }
+
+    /// Limit requests with `rate_limiter`, by default only those to operations with an
+    /// `x-rate-limit` extension.
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Cache responses of operations with an `x-cache` extension in `cache`.
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Announce the deprecation of this API version with `Deprecation` and `Sunset` headers on
+    /// the responses of its operations.
+    pub fn deprecation(mut self, deprecation: Deprecation) -> Self {
+        self.deprecation = Some(deprecation);
+        self
+    }
+
+    /// Serve the operations of another API version, built by the [`Builder::routes`] of its
+    /// server, next to those of this one, behind the same router-wide layers.
+    ///
+    /// Build it with the [`ResponseCache`] and [`RateLimiter`] of this builder, so that the
+    /// requests to either version invalidate the responses cached for both, and a client's
+    /// requests to both count against the same rate limits.
+    pub fn version(mut self, routes: Router) -> Self {
+        self.versions.push(routes);
+        self
+    }
+
+    /// Routes of the operations of this API version, without the router-wide layers of
+    /// [`Builder::build`], to be served by the builder of another version.
+    pub fn routes<A>(self) -> Router
+    where
+        I: AsRef<A> + Clone + Send + Sync + 'static,
+        A: Api + 'static,
+    {
+        let limiter = self.rate_limiter;
+        let cache = self.cache;
+
+        // build our application with a route
+        let router = Router::new()
+            .route(
+                "/v2/pets",
+                get(list_pets::<I, A>
+                    .layer(DefaultBodyLimit::max(LIST_PETS_BODY_LIMIT))
+                    .layer(cache.layer(LIST_PETS_CACHE).base_path("/v2"))
+                    .layer(limiter.layer("listPets", LIST_PETS_RATE_LIMIT))
+                    .layer(OperationLayer::new("listPets", "/v2/pets")))
+                .post(
+                    create_pets::<I, A>
+                        .layer(DefaultBodyLimit::max(CREATE_PETS_BODY_LIMIT))
+                        .layer(cache.layer(CREATE_PETS_CACHE).base_path("/v2"))
+                        .layer(limiter.layer("createPets", CREATE_PETS_RATE_LIMIT))
+                        .layer(OperationLayer::new("createPets", "/v2/pets")),
+                ),
+            )
+            .route(
+                "/v2/pets/:pet_id",
+                get(show_pet_by_id::<I, A>
+                    .layer(DefaultBodyLimit::max(SHOW_PET_BY_ID_BODY_LIMIT))
+                    .layer(cache.layer(SHOW_PET_BY_ID_CACHE).base_path("/v2"))
+                    .layer(limiter.layer("showPetById", SHOW_PET_BY_ID_RATE_LIMIT))
+                    .layer(OperationLayer::new("showPetById", "/v2/pets/:pet_id"))),
+            )
+            .route(
+                "/v2/pets/:pet_id/documents",
+                post(
+                    upload_pet_documents::<I, A>
+                        .layer(DefaultBodyLimit::max(UPLOAD_PET_DOCUMENTS_BODY_LIMIT))
+                        .layer(cache.layer(UPLOAD_PET_DOCUMENTS_CACHE).base_path("/v2"))
+                        .layer(limiter.layer("uploadPetDocuments", UPLOAD_PET_DOCUMENTS_RATE_LIMIT))
+                        .layer(OperationLayer::new(
+                            "uploadPetDocuments",
+                            "/v2/pets/:pet_id/documents",
+                        )),
+                ),
+            )
+            .route(
+                "/v2/pets/:pet_id/photo",
+                get(show_pet_photo::<I, A>
+                    .layer(DefaultBodyLimit::max(SHOW_PET_PHOTO_BODY_LIMIT))
+                    .layer(cache.layer(SHOW_PET_PHOTO_CACHE).base_path("/v2"))
+                    .layer(limiter.layer("showPetPhoto", SHOW_PET_PHOTO_RATE_LIMIT))
+                    .layer(OperationLayer::new(
+                        "showPetPhoto",
+                        "/v2/pets/:pet_id/photo",
+                    )))
+                .post(
+                    upload_pet_photo::<I, A>
+                        .layer(DefaultBodyLimit::max(UPLOAD_PET_PHOTO_BODY_LIMIT))
+                        .layer(cache.layer(UPLOAD_PET_PHOTO_CACHE).base_path("/v2"))
+                        .layer(limiter.layer("uploadPetPhoto", UPLOAD_PET_PHOTO_RATE_LIMIT))
+                        .layer(OperationLayer::new(
+                            "uploadPetPhoto",
+                            "/v2/pets/:pet_id/photo",
+                        )),
+                ),
+            )
+            .with_state(self.api_impl);
+
+        let router = match self.deprecation {
+            Some(deprecation) => router.route_layer(deprecation.layer()),
+            None => router,
+        };
+
+        // check responses against the OpenAPI document in debug builds
+        if cfg!(debug_assertions) {
+            let contract = Contract::from_yaml(SPEC).expect("Embedded OpenAPI document is invalid");
+            router.layer(ContractLayer::new(contract, Mode::Log))
+        } else {
+            router
+        }
+    }
+
+    /// Build the router.
+    pub fn build<A>(mut self) -> Router
+    where
+        I: AsRef<A> + Clone + Send + Sync + 'static,
+        A: Api + 'static,
+    {
+        let cors = self.cors.take();
+        let security_headers = self.security_headers;
+        let versions = std::mem::take(&mut self.versions);
+        let router = versions.into_iter().fold(self.routes(), Router::merge);
+
+        // expose request counters and latencies
+        let router = router.merge(crate::telemetry::metrics_router());
+
+        // answer preflight requests before they are routed
+        let router = match cors {
+            Some(cors) => router.layer(cors),
+            None => router,
+        };
+
+        let router = if security_headers {
+            router.layer(SecurityHeadersLayer)
+        } else {
+            router
+        };
+
+        // propagate or generate request ids
+        let router = router.layer(crate::telemetry::RequestIdLayer);
+
+        // compress responses as negotiated by Accept-Encoding, except binary payloads, which are
+        // mostly compressed already and whose ranges refer to the uncompressed bytes
+        router.layer(
+            tower_http::compression::CompressionLayer::new().compress_when(
+                DefaultPredicate::new()
+                    .and(NotForContentType::const_new("application/octet-stream")),
+            ),
+        )
+    }
+}
+
+#[derive(validator::Validate)]
+#[allow(dead_code)]
+struct CreatePetsBodyValidator<'a> {
+    #[validate]
+    body: &'a models::Pet,
+}
+
+#[tracing::instrument(skip_all)]
+fn create_pets_validation(
+    header_params: models::CreatePetsHeaderParams,
+    body: models::Pet,
+) -> std::result::Result<(models::CreatePetsHeaderParams, models::Pet), ValidationErrors> {
+    header_params.validate()?;
+    let b = CreatePetsBodyValidator { body: &body };
+    b.validate()?;
+
+    Ok((header_params, body))
+}
+
+/// Request body limit for CreatePets.
+const CREATE_PETS_BODY_LIMIT: usize = 16384;
+
+/// Rate limit of CreatePets, or `None` for the default limit of the rate limiter.
+const CREATE_PETS_RATE_LIMIT: Option<RateLimit> = Some(RateLimit::new(10, Duration::from_secs(60)));
+
+/// Response caching policy of CreatePets, or `None` to leave its responses uncached.
+const CREATE_PETS_CACHE: Option<CachePolicy> = None;
+
+/// CreatePets - POST /v2/pets
+#[tracing::instrument(skip_all)]
+#[allow(clippy::too_many_arguments)]
+async fn create_pets<I, A>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    identity: Option<Extension<ClientIdentity>>,
+    headers: HeaderMap,
+    State(api_impl): State<I>,
+    RequestBody(body): RequestBody<models::Pet>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync,
+    A: Api,
+{
+    #[allow(unused_variables)]
+    let Some(media_type) = negotiate(&headers, &["application/json"]) else {
+        return Response::builder()
+            .status(StatusCode::NOT_ACCEPTABLE)
+            .body(Body::empty())
+            .map_err(|e| {
+                error!(error = ?e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            });
+    };
+
+    if !consumes(
+        &headers,
+        &[
+            "application/json",
+            "application/x-www-form-urlencoded",
+            "application/xml",
+            "application/cbor",
+        ],
+    ) {
+        return Response::builder()
+            .status(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+            .body(Body::empty())
+            .map_err(|e| {
+                error!(error = ?e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            });
+    }
+
+    // Header parameters
+    let header_params = {
+        let header_idempotency_key = headers.get(HeaderName::from_static("idempotency-key"));
+
+        let header_idempotency_key = match header_idempotency_key {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!(
+                            "Invalid header Idempotency-Key - {}",
+                            err
+                        )))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        models::CreatePetsHeaderParams {
+            idempotency_key: header_idempotency_key,
+        }
+    };
+
+    #[allow(clippy::redundant_closure)]
+    let validation =
+        tokio::task::spawn_blocking(move || create_pets_validation(header_params, body))
+            .await
+            .unwrap();
+
+    let Ok((header_params, body)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .create_pets(
+            method,
+            host,
+            cookies,
+            identity.map(|Extension(identity)| identity),
+            header_params,
+            body,
+        )
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            CreatePetsResponse::Status201_NullResponse {
+                headers: models::CreatePets201ResponseHeaders { extra },
+            } => {
+                let mut response = response.status(201);
+                add_extra_headers(&mut response, extra);
+                response.body(Body::empty())
+            }
+            CreatePetsResponse::Status400_InvalidPet {
+                body,
+                headers: models::CreatePets400ResponseHeaders { extra },
+            } => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static(body_media_type(media_type, "application/json")),
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                #[cfg(debug_assertions)]
+                if let Err(e) = body.validate() {
+                    return invalid_response("CreatePets", e);
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    encode_body(media_type, &body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            CreatePetsResponse::Status429_TooManyRequests {
+                headers: models::CreatePets429ResponseHeaders { retry_after, extra },
+            } => {
+                if let Some(retry_after) = retry_after {
+                    let retry_after = match header::IntoHeaderValue(retry_after).try_into() {
+                        Ok(val) => val,
+                        Err(e) => {
+                            return Response::builder()
+                                                                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                                                                    .body(Body::from(format!("An internal server error occurred handling retry_after header - {}", e))).map_err(|e| { error!(error = ?e); StatusCode::INTERNAL_SERVER_ERROR });
+                        }
+                    };
+
+                    {
+                        let mut response_headers = response.headers_mut().unwrap();
+                        response_headers
+                            .insert(HeaderName::from_static("retry-after"), retry_after);
+                    }
+                }
+
+                let mut response = response.status(429);
+                add_extra_headers(&mut response, extra);
+                response.body(Body::empty())
+            }
+            CreatePetsResponse::Status0_UnexpectedError {
+                body,
+                headers: models::CreatePetsDefaultResponseHeaders { extra },
+            } => {
+                let mut response = response.status(500);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static(body_media_type(media_type, "application/json")),
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                #[cfg(debug_assertions)]
+                if let Err(e) = body.validate() {
+                    return invalid_response("CreatePets", e);
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    encode_body(media_type, &body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(e) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            error!(error = %e, "CreatePets failed");
+            response.status(500).body(Body::empty())
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn list_pets_validation(
+    cookie_params: models::ListPetsCookieParams,
+    query_params: models::ListPetsQueryParams,
+) -> std::result::Result<
+    (models::ListPetsCookieParams, models::ListPetsQueryParams),
+    ValidationErrors,
+> {
+    cookie_params.validate()?;
+    query_params.validate()?;
+
+    Ok((cookie_params, query_params))
+}
+
+/// Request body limit for ListPets.
+const LIST_PETS_BODY_LIMIT: usize = DEFAULT_BODY_LIMIT;
+
+/// Rate limit of ListPets, or `None` for the default limit of the rate limiter.
+const LIST_PETS_RATE_LIMIT: Option<RateLimit> = None;
+
+/// Response caching policy of ListPets, or `None` to leave its responses uncached.
+const LIST_PETS_CACHE: Option<CachePolicy> = Some(CachePolicy::new(
+    Duration::from_secs(30),
+    &["accept", "cookie"],
+));
+
+/// ListPets - GET /v2/pets
+#[tracing::instrument(skip_all)]
+#[allow(clippy::too_many_arguments)]
+async fn list_pets<I, A>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    identity: Option<Extension<ClientIdentity>>,
+    headers: HeaderMap,
+    Query(query_params): Query<models::ListPetsQueryParams>,
+    State(api_impl): State<I>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync,
+    A: Api,
+{
+    #[allow(unused_variables)]
+    let Some(media_type) = negotiate(
+        &headers,
+        &[
+            "application/json",
+            "application/x-ndjson",
+            "application/xml",
+            "application/cbor",
+        ],
+    ) else {
+        return Response::builder()
+            .status(StatusCode::NOT_ACCEPTABLE)
+            .body(Body::empty())
+            .map_err(|e| {
+                error!(error = ?e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            });
+    };
+
+    // Cookie parameters
+    let cookie_params = {
+        let cookie_session = match cookies.get("session") {
+            Some(v) => match HeaderValue::from_str(v.value())
+                .map_err(|e| e.to_string())
+                .and_then(header::IntoHeaderValue::<String>::try_from)
+            {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid cookie session - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        models::ListPetsCookieParams {
+            session: cookie_session,
+        }
+    };
+
+    #[allow(clippy::redundant_closure)]
+    let validation =
+        tokio::task::spawn_blocking(move || list_pets_validation(cookie_params, query_params))
+            .await
+            .unwrap();
+
+    let Ok((cookie_params, query_params)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .list_pets(
+            method,
+            host,
+            cookies,
+            identity.map(|Extension(identity)| identity),
+            cookie_params,
+            query_params,
+        )
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => {
+            match rsp {
+                ListPetsResponse::Status200_APagedArrayOfPets {
+                    body,
+                    headers: models::ListPets200ResponseHeaders { x_next, extra },
+                } => {
+                    if let Some(x_next) = x_next {
+                        let x_next = match header::IntoHeaderValue(x_next).try_into() {
+                            Ok(val) => val,
+                            Err(e) => {
+                                return Response::builder()
+                                                                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                                                                    .body(Body::from(format!("An internal server error occurred handling x_next header - {}", e))).map_err(|e| { error!(error = ?e); StatusCode::INTERNAL_SERVER_ERROR });
+                            }
+                        };
+
+                        {
+                            let mut response_headers = response.headers_mut().unwrap();
+                            response_headers.insert(HeaderName::from_static("x-next"), x_next);
+                        }
+                    }
+
+                    let mut response = response.status(200);
+                    {
+                        let mut response_headers = response.headers_mut().unwrap();
+                        response_headers.insert(
+                            CONTENT_TYPE,
+                            HeaderValue::from_static(array_media_type(media_type)),
+                        );
+                    }
+                    add_extra_headers(&mut response, extra);
+
+                    let body_content = match body {
+                        ArrayBody::Vec(items) => {
+                            #[cfg(debug_assertions)]
+                            if let Err(e) = validate_items(
+                                &items,
+                                ArrayConstraints {
+                                    min_items: None,
+                                    max_items: Some(100),
+                                    unique_items: false,
+                                },
+                                Validate::validate,
+                            ) {
+                                return invalid_response("ListPets", e);
+                            }
+                            let body_content = tokio::task::spawn_blocking(move || {
+                                encode_array(media_type, "pets", &items).map_err(|e| {
+                                    error!(error = ?e);
+                                    StatusCode::INTERNAL_SERVER_ERROR
+                                })
+                            })
+                            .await
+                            .unwrap()?;
+                            Body::from(body_content)
+                        }
+                        ArrayBody::Stream(items) => {
+                            #[cfg(debug_assertions)]
+                            let items = validate_stream(
+                                items,
+                                ArrayConstraints {
+                                    min_items: None,
+                                    max_items: Some(100),
+                                    unique_items: false,
+                                },
+                                Validate::validate,
+                            );
+                            stream_array(media_type, "pets", items)
+                        }
+                    };
+                    response.body(body_content)
+                }
+                ListPetsResponse::Status0_UnexpectedError {
+                    body,
+                    headers: models::ListPetsDefaultResponseHeaders { extra },
+                } => {
+                    let mut response = response.status(500);
+                    {
+                        let mut response_headers = response.headers_mut().unwrap();
+                        response_headers.insert(
+                            CONTENT_TYPE,
+                            HeaderValue::from_static(body_media_type(
+                                media_type,
+                                "application/json",
+                            )),
+                        );
+                    }
+                    add_extra_headers(&mut response, extra);
+
+                    #[cfg(debug_assertions)]
+                    if let Err(e) = body.validate() {
+                        return invalid_response("ListPets", e);
+                    }
+
+                    let body_content = tokio::task::spawn_blocking(move || {
+                        encode_body(media_type, &body).map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })
+                    })
+                    .await
+                    .unwrap()?;
+                    response.body(Body::from(body_content))
+                }
+            }
+        }
+        Err(e) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            error!(error = %e, "ListPets failed");
+            response.status(500).body(Body::empty())
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn show_pet_by_id_validation(
+    path_params: models::ShowPetByIdPathParams,
+) -> std::result::Result<(models::ShowPetByIdPathParams,), ValidationErrors> {
+    path_params.validate()?;
+
+    Ok((path_params,))
+}
+
+/// Request body limit for ShowPetById.
+const SHOW_PET_BY_ID_BODY_LIMIT: usize = DEFAULT_BODY_LIMIT;
+
+/// Rate limit of ShowPetById, or `None` for the default limit of the rate limiter.
+const SHOW_PET_BY_ID_RATE_LIMIT: Option<RateLimit> = None;
+
+/// Response caching policy of ShowPetById, or `None` to leave its responses uncached.
+const SHOW_PET_BY_ID_CACHE: Option<CachePolicy> =
+    Some(CachePolicy::new(Duration::from_secs(60), &["accept"]));
+
+/// ShowPetById - GET /v2/pets/{petId}
+#[tracing::instrument(skip_all)]
+#[allow(clippy::too_many_arguments)]
+async fn show_pet_by_id<I, A>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    identity: Option<Extension<ClientIdentity>>,
+    headers: HeaderMap,
+    Path(path_params): Path<models::ShowPetByIdPathParams>,
+    State(api_impl): State<I>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync,
+    A: Api,
+{
+    #[allow(unused_variables)]
+    let Some(media_type) = negotiate(
+        &headers,
+        &[
+            "application/json",
+            "application/xml",
+            "application/cbor",
+            "text/plain",
+        ],
+    ) else {
+        return Response::builder()
+            .status(StatusCode::NOT_ACCEPTABLE)
+            .body(Body::empty())
+            .map_err(|e| {
+                error!(error = ?e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            });
+    };
+
+    #[allow(clippy::redundant_closure)]
+    let validation = tokio::task::spawn_blocking(move || show_pet_by_id_validation(path_params))
+        .await
+        .unwrap();
+
+    let Ok((path_params,)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .show_pet_by_id(
+            method,
+            host,
+            cookies,
+            identity.map(|Extension(identity)| identity),
+            path_params,
+        )
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            ShowPetByIdResponse::Status200_ExpectedResponseToAValidRequest {
+                body,
+                headers: models::ShowPetById200ResponseHeaders { extra },
+            } => {
+                let mut response = response.status(200);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static(body_media_type(media_type, "application/json")),
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                #[cfg(debug_assertions)]
+                if let Err(e) = body.validate() {
+                    return invalid_response("ShowPetById", e);
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    encode_body(media_type, &body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            ShowPetByIdResponse::Status404_PetNotFound {
+                body,
+                headers: models::ShowPetById404ResponseHeaders { extra },
+            } => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static(body_media_type(media_type, "application/json")),
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                #[cfg(debug_assertions)]
+                if let Err(e) = body.validate() {
+                    return invalid_response("ShowPetById", e);
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    encode_body(media_type, &body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            ShowPetByIdResponse::Status0_UnexpectedError {
+                body,
+                headers: models::ShowPetByIdDefaultResponseHeaders { extra },
+            } => {
+                let mut response = response.status(500);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static(body_media_type(media_type, "application/json")),
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                #[cfg(debug_assertions)]
+                if let Err(e) = body.validate() {
+                    return invalid_response("ShowPetById", e);
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    encode_body(media_type, &body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(e) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            error!(error = %e, "ShowPetById failed");
+            response.status(500).body(Body::empty())
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn show_pet_photo_validation(
+    header_params: models::ShowPetPhotoHeaderParams,
+    path_params: models::ShowPetPhotoPathParams,
+) -> std::result::Result<
+    (
+        models::ShowPetPhotoHeaderParams,
+        models::ShowPetPhotoPathParams,
+    ),
+    ValidationErrors,
+> {
+    header_params.validate()?;
+    path_params.validate()?;
+
+    Ok((header_params, path_params))
+}
+
+/// Request body limit for ShowPetPhoto.
+const SHOW_PET_PHOTO_BODY_LIMIT: usize = DEFAULT_BODY_LIMIT;
+
+/// Rate limit of ShowPetPhoto, or `None` for the default limit of the rate limiter.
+const SHOW_PET_PHOTO_RATE_LIMIT: Option<RateLimit> = None;
+
+/// Response caching policy of ShowPetPhoto, or `None` to leave its responses uncached.
+const SHOW_PET_PHOTO_CACHE: Option<CachePolicy> = None;
+
+/// ShowPetPhoto - GET /v2/pets/{petId}/photo
+#[tracing::instrument(skip_all)]
+#[allow(clippy::too_many_arguments)]
+async fn show_pet_photo<I, A>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    identity: Option<Extension<ClientIdentity>>,
+    headers: HeaderMap,
+    Path(path_params): Path<models::ShowPetPhotoPathParams>,
+    State(api_impl): State<I>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync,
+    A: Api,
+{
+    #[allow(unused_variables)]
+    let Some(media_type) = negotiate(
+        &headers,
+        &[
+            "application/octet-stream",
+            "image/jpeg",
+            "image/png",
+            "application/json",
+        ],
+    ) else {
+        return Response::builder()
+            .status(StatusCode::NOT_ACCEPTABLE)
+            .body(Body::empty())
+            .map_err(|e| {
+                error!(error = ?e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            });
+    };
+
+    // Header parameters
+    let header_params = {
+        let header_range = headers.get(HeaderName::from_static("range"));
+
+        let header_range = match header_range {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header Range - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        models::ShowPetPhotoHeaderParams {
+            range: header_range,
+        }
+    };
+
+    #[allow(clippy::redundant_closure)]
+    let validation =
+        tokio::task::spawn_blocking(move || show_pet_photo_validation(header_params, path_params))
+            .await
+            .unwrap();
+
+    let Ok((header_params, path_params)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .show_pet_photo(
+            method,
+            host,
+            cookies,
+            identity.map(|Extension(identity)| identity),
+            header_params,
+            path_params,
+        )
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            ShowPetPhotoResponse::Status200_ThePhoto {
+                body,
+                headers:
+                    models::ShowPetPhoto200ResponseHeaders {
+                        content_length,
+                        accept_ranges,
+                        extra,
+                    },
+            } => {
+                let content_length = match header::IntoHeaderValue(content_length).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                                                                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                                                                    .body(Body::from(format!("An internal server error occurred handling content_length header - {}", e))).map_err(|e| { error!(error = ?e); StatusCode::INTERNAL_SERVER_ERROR });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers
+                        .insert(HeaderName::from_static("content-length"), content_length);
+                }
+                if let Some(accept_ranges) = accept_ranges {
+                    let accept_ranges = match header::IntoHeaderValue(accept_ranges).try_into() {
+                        Ok(val) => val,
+                        Err(e) => {
+                            return Response::builder()
+                                                                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                                                                    .body(Body::from(format!("An internal server error occurred handling accept_ranges header - {}", e))).map_err(|e| { error!(error = ?e); StatusCode::INTERNAL_SERVER_ERROR });
+                        }
+                    };
+
+                    {
+                        let mut response_headers = response.headers_mut().unwrap();
+                        response_headers
+                            .insert(HeaderName::from_static("accept-ranges"), accept_ranges);
+                    }
+                }
+
+                let mut response = response.status(200);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str(
+                            body.content_type().unwrap_or("application/octet-stream"),
+                        )
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                let body_content = body.into_body();
+                response.body(body_content)
+            }
+            ShowPetPhotoResponse::Status206_ARangeOfThePhoto {
+                body,
+                headers:
+                    models::ShowPetPhoto206ResponseHeaders {
+                        content_length,
+                        content_range,
+                        extra,
+                    },
+            } => {
+                let content_length = match header::IntoHeaderValue(content_length).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                                                                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                                                                    .body(Body::from(format!("An internal server error occurred handling content_length header - {}", e))).map_err(|e| { error!(error = ?e); StatusCode::INTERNAL_SERVER_ERROR });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers
+                        .insert(HeaderName::from_static("content-length"), content_length);
+                }
+                let content_range = match header::IntoHeaderValue(content_range).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                                                                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                                                                    .body(Body::from(format!("An internal server error occurred handling content_range header - {}", e))).map_err(|e| { error!(error = ?e); StatusCode::INTERNAL_SERVER_ERROR });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers
+                        .insert(HeaderName::from_static("content-range"), content_range);
+                }
+
+                let mut response = response.status(206);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str(
+                            body.content_type().unwrap_or("application/octet-stream"),
+                        )
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                let body_content = body.into_body();
+                response.body(body_content)
+            }
+            ShowPetPhotoResponse::Status404_PetOrPhotoNotFound {
+                body,
+                headers: models::ShowPetPhoto404ResponseHeaders { extra },
+            } => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static(body_media_type(media_type, "application/json")),
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                #[cfg(debug_assertions)]
+                if let Err(e) = body.validate() {
+                    return invalid_response("ShowPetPhoto", e);
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    encode_body(media_type, &body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            ShowPetPhotoResponse::Status416_RangeNotSatisfiable {
+                headers:
+                    models::ShowPetPhoto416ResponseHeaders {
+                        content_range,
+                        extra,
+                    },
+            } => {
+                let content_range = match header::IntoHeaderValue(content_range).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                                                                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                                                                    .body(Body::from(format!("An internal server error occurred handling content_range header - {}", e))).map_err(|e| { error!(error = ?e); StatusCode::INTERNAL_SERVER_ERROR });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers
+                        .insert(HeaderName::from_static("content-range"), content_range);
+                }
+
+                let mut response = response.status(416);
+                add_extra_headers(&mut response, extra);
+                response.body(Body::empty())
+            }
+            ShowPetPhotoResponse::Status0_UnexpectedError {
+                body,
+                headers: models::ShowPetPhotoDefaultResponseHeaders { extra },
+            } => {
+                let mut response = response.status(500);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static(body_media_type(media_type, "application/json")),
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                #[cfg(debug_assertions)]
+                if let Err(e) = body.validate() {
+                    return invalid_response("ShowPetPhoto", e);
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    encode_body(media_type, &body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(e) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            error!(error = %e, "ShowPetPhoto failed");
+            response.status(500).body(Body::empty())
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn upload_pet_documents_validation(
+    path_params: models::UploadPetDocumentsPathParams,
+    body: models::UploadPetDocumentsForm,
+) -> std::result::Result<
+    (
+        models::UploadPetDocumentsPathParams,
+        models::UploadPetDocumentsForm,
+    ),
+    ValidationErrors,
+> {
+    path_params.validate()?;
+    body.validate()?;
+
+    Ok((path_params, body))
+}
+
+/// Request body limit for UploadPetDocuments.
+const UPLOAD_PET_DOCUMENTS_BODY_LIMIT: usize = 12582912;
+
+/// Rate limit of UploadPetDocuments, or `None` for the default limit of the rate limiter.
+const UPLOAD_PET_DOCUMENTS_RATE_LIMIT: Option<RateLimit> = None;
+
+/// Response caching policy of UploadPetDocuments, or `None` to leave its responses uncached.
+const UPLOAD_PET_DOCUMENTS_CACHE: Option<CachePolicy> = None;
+
+/// Read the `multipart/form-data` request body of UploadPetDocuments. Parts not in the schema are
+/// ignored.
+async fn upload_pet_documents_form(
+    mut multipart: Multipart,
+) -> Result<models::UploadPetDocumentsForm, Response> {
+    let mut description = None;
+    let mut photos = Vec::new();
+    let mut certificate = None;
+
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        match field.name() {
+            Some("description") => {
+                description = Some(text_part(field).await?);
+            }
+            Some("photos") => {
+                // stop before reading a part beyond maxItems
+                if photos.len() == 5 {
+                    return Err(part_error(
+                        StatusCode::BAD_REQUEST,
+                        "More than 5 parts photos".to_string(),
+                    ));
+                }
+                photos.push(file_part(field, Some("image/jpeg, image/png"), Some(2097152)).await?);
+            }
+            Some("certificate") => {
+                certificate = Some(file_part(field, Some("application/pdf"), Some(1048576)).await?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(models::UploadPetDocumentsForm {
+        description,
+        photos,
+        certificate,
+    })
+}
+
+/// UploadPetDocuments - POST /v2/pets/{petId}/documents
+#[tracing::instrument(skip_all)]
+#[allow(clippy::too_many_arguments)]
+async fn upload_pet_documents<I, A>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    identity: Option<Extension<ClientIdentity>>,
+    headers: HeaderMap,
+    Path(path_params): Path<models::UploadPetDocumentsPathParams>,
+    State(api_impl): State<I>,
+    body: Multipart,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync,
+    A: Api,
+{
+    #[allow(unused_variables)]
+    let Some(media_type) = negotiate(&headers, &["application/json"]) else {
+        return Response::builder()
+            .status(StatusCode::NOT_ACCEPTABLE)
+            .body(Body::empty())
+            .map_err(|e| {
+                error!(error = ?e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            });
+    };
+
+    if !consumes(&headers, &["multipart/form-data"]) {
+        return Response::builder()
+            .status(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+            .body(Body::empty())
+            .map_err(|e| {
+                error!(error = ?e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            });
+    }
+
+    let body = match upload_pet_documents_form(body).await {
+        Ok(body) => body,
+        Err(response) => return Ok(response),
+    };
+
+    #[allow(clippy::redundant_closure)]
+    let validation =
+        tokio::task::spawn_blocking(move || upload_pet_documents_validation(path_params, body))
+            .await
+            .unwrap();
+
+    let Ok((path_params, body)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .upload_pet_documents(
+            method,
+            host,
+            cookies,
+            identity.map(|Extension(identity)| identity),
+            path_params,
+            body,
+        )
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            UploadPetDocumentsResponse::Status201_DocumentsStored {
+                body,
+                headers: models::UploadPetDocuments201ResponseHeaders { extra },
+            } => {
+                let mut response = response.status(201);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static(array_media_type(media_type)),
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                let body_content = match body {
+                    ArrayBody::Vec(items) => {
+                        #[cfg(debug_assertions)]
+                        if let Err(e) = validate_items(
+                            &items,
+                            ArrayConstraints {
+                                min_items: None,
+                                max_items: None,
+                                unique_items: false,
+                            },
+                            Validate::validate,
+                        ) {
+                            return invalid_response("UploadPetDocuments", e);
+                        }
+                        let body_content = tokio::task::spawn_blocking(move || {
+                            encode_array(media_type, "items", &items).map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            })
+                        })
+                        .await
+                        .unwrap()?;
+                        Body::from(body_content)
+                    }
+                    ArrayBody::Stream(items) => {
+                        #[cfg(debug_assertions)]
+                        let items = validate_stream(
+                            items,
+                            ArrayConstraints {
+                                min_items: None,
+                                max_items: None,
+                                unique_items: false,
+                            },
+                            Validate::validate,
+                        );
+                        stream_array(media_type, "items", items)
+                    }
+                };
+                response.body(body_content)
+            }
+            UploadPetDocumentsResponse::Status400_InvalidUpload {
+                body,
+                headers: models::UploadPetDocuments400ResponseHeaders { extra },
+            } => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static(body_media_type(media_type, "application/json")),
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                #[cfg(debug_assertions)]
+                if let Err(e) = body.validate() {
+                    return invalid_response("UploadPetDocuments", e);
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    encode_body(media_type, &body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            UploadPetDocumentsResponse::Status404_PetNotFound {
+                body,
+                headers: models::UploadPetDocuments404ResponseHeaders { extra },
+            } => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static(body_media_type(media_type, "application/json")),
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                #[cfg(debug_assertions)]
+                if let Err(e) = body.validate() {
+                    return invalid_response("UploadPetDocuments", e);
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    encode_body(media_type, &body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            UploadPetDocumentsResponse::Status0_UnexpectedError {
+                body,
+                headers: models::UploadPetDocumentsDefaultResponseHeaders { extra },
+            } => {
+                let mut response = response.status(500);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static(body_media_type(media_type, "application/json")),
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                #[cfg(debug_assertions)]
+                if let Err(e) = body.validate() {
+                    return invalid_response("UploadPetDocuments", e);
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    encode_body(media_type, &body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(e) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            error!(error = %e, "UploadPetDocuments failed");
+            response.status(500).body(Body::empty())
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn upload_pet_photo_validation(
+    path_params: models::UploadPetPhotoPathParams,
+) -> std::result::Result<(models::UploadPetPhotoPathParams,), ValidationErrors> {
+    path_params.validate()?;
+
+    Ok((path_params,))
+}
+
+/// Request body limit for UploadPetPhoto.
+const UPLOAD_PET_PHOTO_BODY_LIMIT: usize = 10485760;
+
+/// Rate limit of UploadPetPhoto, or `None` for the default limit of the rate limiter.
+const UPLOAD_PET_PHOTO_RATE_LIMIT: Option<RateLimit> = None;
+
+/// Response caching policy of UploadPetPhoto, or `None` to leave its responses uncached.
+const UPLOAD_PET_PHOTO_CACHE: Option<CachePolicy> = None;
+
+/// UploadPetPhoto - POST /v2/pets/{petId}/photo
+#[tracing::instrument(skip_all)]
+#[allow(clippy::too_many_arguments)]
+async fn upload_pet_photo<I, A>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    identity: Option<Extension<ClientIdentity>>,
+    headers: HeaderMap,
+    Path(path_params): Path<models::UploadPetPhotoPathParams>,
+    State(api_impl): State<I>,
+    body: FileUpload,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync,
+    A: Api,
+{
+    #[allow(unused_variables)]
+    let Some(media_type) = negotiate(&headers, &["application/json"]) else {
+        return Response::builder()
+            .status(StatusCode::NOT_ACCEPTABLE)
+            .body(Body::empty())
+            .map_err(|e| {
+                error!(error = ?e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            });
+    };
+
+    if !consumes(
+        &headers,
+        &[
+            "multipart/form-data",
+            "application/octet-stream",
+            "image/jpeg",
+            "image/png",
+        ],
+    ) {
+        return Response::builder()
+            .status(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+            .body(Body::empty())
+            .map_err(|e| {
+                error!(error = ?e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            });
+    }
+
+    #[allow(clippy::redundant_closure)]
+    let validation = tokio::task::spawn_blocking(move || upload_pet_photo_validation(path_params))
+        .await
+        .unwrap();
+
+    let Ok((path_params,)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .upload_pet_photo(
+            method,
+            host,
+            cookies,
+            identity.map(|Extension(identity)| identity),
+            path_params,
+            body,
+        )
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            UploadPetPhotoResponse::Status204_PhotoStored {
+                headers: models::UploadPetPhoto204ResponseHeaders { extra },
+            } => {
+                let mut response = response.status(204);
+                add_extra_headers(&mut response, extra);
+                response.body(Body::empty())
+            }
+            UploadPetPhotoResponse::Status400_InvalidUpload {
+                body,
+                headers: models::UploadPetPhoto400ResponseHeaders { extra },
+            } => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static(body_media_type(media_type, "application/json")),
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                #[cfg(debug_assertions)]
+                if let Err(e) = body.validate() {
+                    return invalid_response("UploadPetPhoto", e);
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    encode_body(media_type, &body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            UploadPetPhotoResponse::Status404_PetNotFound {
+                body,
+                headers: models::UploadPetPhoto404ResponseHeaders { extra },
+            } => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static(body_media_type(media_type, "application/json")),
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                #[cfg(debug_assertions)]
+                if let Err(e) = body.validate() {
+                    return invalid_response("UploadPetPhoto", e);
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    encode_body(media_type, &body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            UploadPetPhotoResponse::Status0_UnexpectedError {
+                body,
+                headers: models::UploadPetPhotoDefaultResponseHeaders { extra },
+            } => {
+                let mut response = response.status(500);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static(body_media_type(media_type, "application/json")),
+                    );
+                }
+                add_extra_headers(&mut response, extra);
+
+                #[cfg(debug_assertions)]
+                if let Err(e) = body.validate() {
+                    return invalid_response("UploadPetPhoto", e);
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    encode_body(media_type, &body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(e) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            error!(error = %e, "UploadPetPhoto failed");
+            response.status(500).body(Body::empty())
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
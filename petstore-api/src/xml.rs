@@ -0,0 +1,505 @@
+//! (De)serialization of models as `application/xml`, following the `xml` objects of the OpenAPI
+//! document: element names, attributes, namespaces and wrapped arrays.
+//!
+//! Values are converted through [`serde_json::Value`], guided by the [`Xml`] description of
+//! their schema, which is generated for every model as an implementation of [`XmlSchema`].
+//! `null` values and absent properties are written as absent elements. Properties which are
+//! not in the schema, e.g. additional properties, are written as elements named after them and
+//! read back as text.
+//!
+//! ```
+//! # use petstore_api::{models::Pet, xml};
+//! let pet = Pet::new(1, "Rex".to_string());
+//!
+//! let encoded = xml::to_vec(&pet).unwrap();
+//! assert_eq!(encoded, br#"<pet id="1"><name>Rex</name></pet>"#);
+//! assert_eq!(xml::from_slice::<Pet>(&encoded).unwrap(), pet);
+//! ```
+
+use std::{collections::HashMap, fmt, io};
+
+use quick_xml::{
+    events::{BytesEnd, BytesStart, BytesText, Event},
+    Reader, Writer,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+/// Representation of the values of a schema in XML.
+#[derive(Debug, Clone, Copy)]
+pub struct Xml {
+    /// Name of the element of a value, unless it is named by a property.
+    pub name: &'static str,
+    /// Namespace of the element, declared on it.
+    pub namespace: Option<&'static str>,
+    /// Prefix of the element name, bound to the namespace.
+    pub prefix: Option<&'static str>,
+    pub kind: Kind,
+}
+
+/// Kind of the values of a schema.
+#[derive(Debug, Clone, Copy)]
+pub enum Kind {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    /// An object with the given properties.
+    Object(&'static [Property]),
+    /// Any value, written as nested elements and read as text.
+    Any,
+}
+
+/// Representation of a property of an object schema.
+#[derive(Debug, Clone, Copy)]
+pub struct Property {
+    /// Name of the property in JSON.
+    pub name: &'static str,
+    /// Name of the element or attribute of the property.
+    pub xml_name: &'static str,
+    /// Whether the property is an attribute rather than an element.
+    pub attribute: bool,
+    /// How the items are written, if the property is an array.
+    pub array: Option<Array>,
+    /// Representation of the value of the property, or of its items if it is an array.
+    pub schema: fn() -> Xml,
+}
+
+/// Representation of an array property.
+#[derive(Debug, Clone, Copy)]
+pub struct Array {
+    /// Whether the items are wrapped in an element named after the property.
+    pub wrapped: bool,
+    /// Name of the element of every item.
+    pub item_name: &'static str,
+}
+
+/// Types with a representation in XML.
+pub trait XmlSchema {
+    /// Representation of the values of the type.
+    fn xml() -> Xml;
+}
+
+/// Error raised while (de)serializing XML.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error(e.to_string())
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error(e.to_string())
+    }
+}
+
+impl From<quick_xml::Error> for Error {
+    fn from(e: quick_xml::Error) -> Self {
+        Error(e.to_string())
+    }
+}
+
+impl From<quick_xml::events::attributes::AttrError> for Error {
+    fn from(e: quick_xml::events::attributes::AttrError) -> Self {
+        Error(e.to_string())
+    }
+}
+
+/// Serialize `value` as an XML document without declaration.
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>, Error>
+where
+    T: Serialize + XmlSchema + ?Sized,
+{
+    let xml = T::xml();
+    let mut writer = Writer::new(Vec::new());
+    write_value(&mut writer, xml.name, &serde_json::to_value(value)?, &xml)?;
+    Ok(writer.into_inner())
+}
+
+/// Serialize `items` as the elements of a `wrapper` element, the root of an array body, named
+/// by the `xml` object of the array schema.
+pub fn items_to_vec<T>(wrapper: &str, items: &[T]) -> Result<Vec<u8>, Error>
+where
+    T: Serialize + XmlSchema,
+{
+    let mut buf = format!("<{}>", wrapper).into_bytes();
+    for item in items {
+        buf.extend(to_vec(item)?);
+    }
+    buf.extend(format!("</{}>", wrapper).into_bytes());
+    Ok(buf)
+}
+
+/// Deserialize a value from an XML document. Namespace prefixes are ignored.
+pub fn from_slice<T>(xml: &[u8]) -> Result<T, Error>
+where
+    T: DeserializeOwned + XmlSchema,
+{
+    let root = parse(xml)?;
+    let value = read_value(&root, &T::xml())?;
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Representation of string values.
+pub fn string() -> Xml {
+    scalar(Kind::String)
+}
+
+/// Representation of integer values.
+pub fn integer() -> Xml {
+    scalar(Kind::Integer)
+}
+
+/// Representation of number values.
+pub fn number() -> Xml {
+    scalar(Kind::Number)
+}
+
+/// Representation of boolean values.
+pub fn boolean() -> Xml {
+    scalar(Kind::Boolean)
+}
+
+/// Representation of values of any schema.
+pub fn any() -> Xml {
+    scalar(Kind::Any)
+}
+
+fn scalar(kind: Kind) -> Xml {
+    Xml {
+        name: "value",
+        namespace: None,
+        prefix: None,
+        kind,
+    }
+}
+
+macro_rules! xml_schema {
+    ($kind:ident: $($ty:ty),*) => {
+        $(
+            impl XmlSchema for $ty {
+                fn xml() -> Xml {
+                    scalar(Kind::$kind)
+                }
+            }
+        )*
+    };
+}
+
+xml_schema!(String: String, uuid::Uuid, chrono::NaiveDate, crate::types::ByteArray);
+xml_schema!(Integer: i8, i16, i32, i64, u8, u16, u32, u64);
+xml_schema!(Number: f32, f64);
+xml_schema!(Boolean: bool);
+xml_schema!(Any: Value, crate::types::Object);
+
+impl<Tz: chrono::TimeZone> XmlSchema for chrono::DateTime<Tz> {
+    fn xml() -> Xml {
+        string()
+    }
+}
+
+impl<T: XmlSchema> XmlSchema for Option<T> {
+    fn xml() -> Xml {
+        T::xml()
+    }
+}
+
+impl<T: XmlSchema> XmlSchema for crate::types::Nullable<T> {
+    fn xml() -> Xml {
+        T::xml()
+    }
+}
+
+/// The items of an array property are described by [`Property::array`].
+impl<T: XmlSchema> XmlSchema for Vec<T> {
+    fn xml() -> Xml {
+        T::xml()
+    }
+}
+
+impl<T> XmlSchema for HashMap<String, T> {
+    fn xml() -> Xml {
+        any()
+    }
+}
+
+fn qualified(name: &str, xml: &Xml) -> String {
+    match xml.prefix {
+        Some(prefix) => format!("{}:{}", prefix, name),
+        None => name.to_string(),
+    }
+}
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn write_value(
+    writer: &mut Writer<Vec<u8>>,
+    name: &str,
+    value: &Value,
+    xml: &Xml,
+) -> Result<(), Error> {
+    let (Value::Object(object), Kind::Object(properties)) = (value, xml.kind) else {
+        return write_any(writer, &qualified(name, xml), value);
+    };
+
+    let name = qualified(name, xml);
+    let mut start = BytesStart::new(name.as_str());
+    match (xml.namespace, xml.prefix) {
+        (Some(namespace), Some(prefix)) => {
+            start.push_attribute((format!("xmlns:{}", prefix).as_str(), namespace))
+        }
+        (Some(namespace), None) => start.push_attribute(("xmlns", namespace)),
+        _ => {}
+    }
+    for property in properties.iter().filter(|p| p.attribute) {
+        match object.get(property.name) {
+            None | Some(Value::Null) => {}
+            Some(value) => start.push_attribute((property.xml_name, scalar_text(value).as_str())),
+        }
+    }
+    writer.write_event(Event::Start(start))?;
+
+    for property in properties.iter().filter(|p| !p.attribute) {
+        let Some(value) = object.get(property.name) else {
+            continue;
+        };
+        let schema = (property.schema)();
+        match (property.array, value) {
+            (Some(array), Value::Array(items)) => {
+                if array.wrapped {
+                    writer.write_event(Event::Start(BytesStart::new(property.xml_name)))?;
+                }
+                for item in items {
+                    write_value(writer, array.item_name, item, &schema)?;
+                }
+                if array.wrapped {
+                    writer.write_event(Event::End(BytesEnd::new(property.xml_name)))?;
+                }
+            }
+            (_, value) => write_value(writer, property.xml_name, value, &schema)?,
+        }
+    }
+    // entries which are not in the schema
+    for (key, value) in object {
+        if !properties.iter().any(|p| p.name == key) {
+            write_any(writer, key, value)?;
+        }
+    }
+
+    writer.write_event(Event::End(BytesEnd::new(name.as_str())))?;
+    Ok(())
+}
+
+fn write_any(writer: &mut Writer<Vec<u8>>, name: &str, value: &Value) -> Result<(), Error> {
+    match value {
+        Value::Null => {}
+        Value::Array(items) => {
+            for item in items {
+                write_any(writer, name, item)?;
+            }
+        }
+        Value::Object(object) => {
+            writer.write_event(Event::Start(BytesStart::new(name)))?;
+            for (key, value) in object {
+                write_any(writer, key, value)?;
+            }
+            writer.write_event(Event::End(BytesEnd::new(name)))?;
+        }
+        value => {
+            writer.write_event(Event::Start(BytesStart::new(name)))?;
+            writer.write_event(Event::Text(BytesText::new(&scalar_text(value))))?;
+            writer.write_event(Event::End(BytesEnd::new(name)))?;
+        }
+    }
+    Ok(())
+}
+
+/// An element of a parsed document, by local names.
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+}
+
+impl Element {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |e| e.name == name)
+    }
+}
+
+fn parse(xml: &[u8]) -> Result<Element, Error> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut stack: Vec<Element> = Vec::new();
+    let mut buf = Vec::new();
+    loop {
+        let event = reader.read_event_into(&mut buf)?;
+        let empty = matches!(event, Event::Empty(_));
+        let completed = match event {
+            Event::Start(start) | Event::Empty(start) => {
+                let mut element = Element {
+                    name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+                    ..Element::default()
+                };
+                for attribute in start.attributes() {
+                    let attribute = attribute?;
+                    let key = attribute.key;
+                    if key.as_namespace_binding().is_some() {
+                        continue;
+                    }
+                    element.attributes.push((
+                        String::from_utf8_lossy(key.local_name().as_ref()).into_owned(),
+                        attribute.unescape_value()?.into_owned(),
+                    ));
+                }
+                if empty {
+                    Some(element)
+                } else {
+                    stack.push(element);
+                    None
+                }
+            }
+            Event::End(_) => stack.pop(),
+            Event::Text(text) => {
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&text.unescape()?);
+                }
+                None
+            }
+            Event::CData(data) => {
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&String::from_utf8_lossy(&data));
+                }
+                None
+            }
+            Event::Eof => return Err(Error("Unexpected end of XML document".to_string())),
+            _ => None,
+        };
+        if let Some(element) = completed {
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(element),
+                None => return Ok(element),
+            }
+        }
+        buf.clear();
+    }
+}
+
+fn read_value(element: &Element, xml: &Xml) -> Result<Value, Error> {
+    match xml.kind {
+        Kind::Object(properties) => read_object(element, properties),
+        Kind::Any => Ok(read_any(element)),
+        kind => read_scalar(&element.text, kind),
+    }
+}
+
+fn read_object(element: &Element, properties: &[Property]) -> Result<Value, Error> {
+    let mut object = Map::new();
+    for property in properties {
+        let schema = (property.schema)();
+        if property.attribute {
+            if let Some(value) = element.attribute(property.xml_name) {
+                object.insert(property.name.to_string(), read_scalar(value, schema.kind)?);
+            }
+            continue;
+        }
+
+        match property.array {
+            Some(array) => {
+                let wrapper = if array.wrapped {
+                    element.children(property.xml_name).next()
+                } else {
+                    Some(element)
+                };
+                let Some(wrapper) = wrapper else {
+                    continue;
+                };
+                let items = wrapper
+                    .children(array.item_name)
+                    .map(|item| read_value(item, &schema))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if array.wrapped || !items.is_empty() {
+                    object.insert(property.name.to_string(), Value::Array(items));
+                }
+            }
+            None => {
+                if let Some(child) = element.children(property.xml_name).next() {
+                    object.insert(property.name.to_string(), read_value(child, &schema)?);
+                }
+            }
+        }
+    }
+
+    // elements which are not in the schema
+    for child in &element.children {
+        let known = properties.iter().any(|p| match p.array {
+            Some(array) if !array.wrapped => array.item_name == child.name,
+            _ => !p.attribute && p.xml_name == child.name,
+        });
+        if !known && !object.contains_key(&child.name) {
+            object.insert(child.name.clone(), read_any(child));
+        }
+    }
+    Ok(Value::Object(object))
+}
+
+fn read_scalar(text: &str, kind: Kind) -> Result<Value, Error> {
+    let invalid = || Error(format!("Invalid {:?} value {:?}", kind, text));
+    Ok(match kind {
+        Kind::Integer => match text.trim().parse::<i64>() {
+            Ok(i) => Value::from(i),
+            Err(_) => Value::from(text.trim().parse::<u64>().map_err(|_| invalid())?),
+        },
+        Kind::Number => Value::from(text.trim().parse::<f64>().map_err(|_| invalid())?),
+        Kind::Boolean => Value::from(text.trim().parse::<bool>().map_err(|_| invalid())?),
+        _ => Value::String(text.to_string()),
+    })
+}
+
+fn read_any(element: &Element) -> Value {
+    if element.children.is_empty() && element.attributes.is_empty() {
+        return Value::String(element.text.clone());
+    }
+
+    let mut object = Map::new();
+    for (name, value) in &element.attributes {
+        object.insert(name.clone(), Value::String(value.clone()));
+    }
+    for child in &element.children {
+        let value = read_any(child);
+        match object.get_mut(&child.name) {
+            Some(Value::Array(items)) => items.push(value),
+            Some(previous) => *previous = Value::Array(vec![previous.take(), value]),
+            None => {
+                object.insert(child.name.clone(), value);
+            }
+        }
+    }
+    Value::Object(object)
+}
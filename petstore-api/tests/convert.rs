@@ -0,0 +1,138 @@
+//! Tests for the conversions of the `convert` module.
+//!
+//! Run with `cargo test --features conversion`.
+#![cfg(feature = "conversion")]
+
+use frunk::LabelledGeneric;
+use petstore_api::{convert, types::Nullable};
+
+#[derive(Debug, Clone, PartialEq, LabelledGeneric)]
+struct Model {
+    id: i64,
+    name: String,
+    tag: Option<Nullable<String>>,
+    weight: f64,
+    rank: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, LabelledGeneric)]
+struct Entity {
+    weight: f32,
+    id: i32,
+    tag: Option<String>,
+    name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, LabelledGeneric)]
+struct Unstored {
+    rank: Option<i32>,
+}
+
+#[test]
+fn fields_are_coerced_by_name() {
+    let entity = Entity {
+        weight: 1.5,
+        id: 7,
+        tag: Some("dog".to_string()),
+        name: "Rex".to_string(),
+    };
+
+    let model: Model = convert::convert_with(entity.clone(), Unstored { rank: Some(1) }).unwrap();
+    assert_eq!(
+        model,
+        Model {
+            id: 7,
+            name: "Rex".to_string(),
+            tag: Some(Nullable::Present("dog".to_string())),
+            weight: 1.5,
+            rank: Some(1),
+        }
+    );
+
+    // fields the target doesn't have are dropped
+    let unstored: Unstored = convert::convert(model).unwrap();
+    assert_eq!(unstored, Unstored { rank: Some(1) });
+}
+
+#[test]
+fn fields_the_target_does_not_have_are_split_off() {
+    #[derive(Debug, PartialEq, LabelledGeneric)]
+    struct Row {
+        name: String,
+        id: i32,
+        weight: f64,
+    }
+    #[derive(Debug, PartialEq, LabelledGeneric)]
+    struct Rest {
+        rank: Option<i32>,
+        tag: Option<Nullable<String>>,
+    }
+
+    let model = Model {
+        id: 7,
+        name: "Rex".to_string(),
+        tag: Some(Nullable::Null),
+        weight: 1.5,
+        rank: Some(1),
+    };
+    let (row, rest): (Row, Rest) = convert::split(model).unwrap();
+    assert_eq!(
+        row,
+        Row {
+            name: "Rex".to_string(),
+            id: 7,
+            weight: 1.5,
+        }
+    );
+    assert_eq!(
+        rest,
+        Rest {
+            rank: Some(1),
+            tag: Some(Nullable::Null),
+        }
+    );
+}
+
+#[test]
+fn nullable_properties_are_nullable_columns() {
+    #[derive(Debug, PartialEq, LabelledGeneric)]
+    struct Row {
+        id: i32,
+        tag: Option<String>,
+    }
+    #[derive(Debug, PartialEq, LabelledGeneric)]
+    struct Property {
+        id: i64,
+        tag: Option<Nullable<String>>,
+    }
+
+    for tag in [None, Some(Nullable::Null)] {
+        let row: Row = convert::convert(Property { id: 1, tag }).unwrap();
+        assert_eq!(row, Row { id: 1, tag: None });
+    }
+
+    let property: Property = convert::convert(Row { id: 1, tag: None }).unwrap();
+    assert_eq!(property.tag, Some(Nullable::Null));
+}
+
+#[test]
+fn out_of_range_integers_fail() {
+    #[derive(LabelledGeneric)]
+    struct Wide {
+        id: i64,
+    }
+    #[derive(Debug, LabelledGeneric)]
+    struct Narrow {
+        id: i32,
+    }
+
+    let narrow: Narrow = convert::convert(Wide {
+        id: i64::from(i32::MAX),
+    })
+    .unwrap();
+    assert_eq!(narrow.id, i32::MAX);
+
+    let error = convert::convert::<_, Narrow, _>(Wide { id: i64::MAX }).unwrap_err();
+    assert_eq!(error.field, "id");
+    assert!(error.to_string().starts_with("id: "), "{}", error);
+}
@@ -1,4 +1,7 @@
 use diesel::prelude::*;
+use frunk::LabelledGeneric;
+
+use petstore_api::{convert, models, types};
 
 diesel::table! {
     pet (id) {
@@ -8,10 +11,42 @@ diesel::table! {
     }
 }
 
-#[derive(serde::Serialize, Selectable, Queryable, Insertable, Clone)]
+/// Row of the `pet` table, converted from and into [`models::Pet`] field by field, so a
+/// property added to the model which isn't stored nor listed in [`UnstoredPet`] doesn't build.
+#[derive(serde::Serialize, Selectable, Queryable, Insertable, Clone, LabelledGeneric)]
 #[diesel(table_name = pet)]
 pub struct PetEntity {
     pub id: i32,
     pub name: String,
     pub tag: Option<String>,
 }
+
+/// Properties of [`models::Pet`] which aren't stored, and are absent from stored pets.
+#[derive(Default, LabelledGeneric)]
+pub struct UnstoredPet {
+    pub attributes: Option<types::Object>,
+}
+
+impl TryFrom<models::Pet> for PetEntity {
+    type Error = convert::Error;
+
+    /// Fails if the id of the pet is out of the range of the `id` column, or if the pet has
+    /// properties which aren't stored.
+    fn try_from(pet: models::Pet) -> Result<Self, Self::Error> {
+        let (entity, unstored): (Self, UnstoredPet) = convert::split(pet)?;
+        if unstored.attributes.is_some() {
+            return Err(convert::Error {
+                field: "attributes",
+                message: "not stored".to_string(),
+            });
+        }
+        Ok(entity)
+    }
+}
+
+impl From<PetEntity> for models::Pet {
+    fn from(entity: PetEntity) -> Self {
+        // columns only ever widen into properties
+        convert::convert_with(entity, UnstoredPet::default()).expect("Stored pets are valid")
+    }
+}
@@ -0,0 +1,548 @@
+//! Implementation of the petstore API storing pets in PostgreSQL, for both API versions.
+
+pub mod entities;
+
+use http::{header::LOCATION, Method};
+use std::convert::TryFrom;
+use std::io;
+use std::sync::Arc;
+
+use axum::extract::*;
+use axum::{async_trait, http::StatusCode};
+use axum_extra::extract::CookieJar;
+
+use chrono::{TimeZone, Utc};
+use diesel::prelude::*;
+use diesel_async::{
+    pooled_connection::AsyncDieselConnectionManager, AsyncPgConnection, RunQueryDsl,
+};
+use futures::{Stream, StreamExt};
+
+use petstore_api::*;
+use petstore_storage as storage;
+
+/// Why a pet wasn't stored, with the body of the error response.
+enum InsertError {
+    /// The pet can't be stored, e.g. as its id is beyond the range of the id column, so the
+    /// request is at fault.
+    Invalid(models::Error),
+    /// The database failed.
+    Failed(models::Error),
+}
+
+/// Pool of connections to the database.
+pub type Pool = bb8::Pool<AsyncDieselConnectionManager<AsyncPgConnection>>;
+
+pub struct ServerState {
+    pool: Pool,
+    photos: blob::BlobStore,
+}
+
+impl ServerState {
+    /// Store pets in the database of `pool`, and their documents and photos in `photos`.
+    pub fn new(pool: Pool, photos: blob::BlobStore) -> Self {
+        ServerState { pool, photos }
+    }
+
+    /// Whether the pet `pet_id` is stored, or the error response if that can't be told.
+    async fn pet_exists(&self, pet_id: i64) -> Result<bool, models::Error> {
+        // ids beyond the range of the id column can't be stored
+        let Ok(id) = i32::try_from(pet_id) else {
+            return Ok(false);
+        };
+        let mut conn = self.pool.get().await.map_err(internal_error)?;
+
+        entities::pet::table
+            .find(id)
+            .select(entities::pet::id)
+            .first::<i32>(&mut conn)
+            .await
+            .optional()
+            .map(|id| id.is_some())
+            .map_err(internal_error)
+    }
+
+    /// Store `pet`.
+    async fn insert_pet(&self, pet: models::Pet) -> Result<(), InsertError> {
+        let new_pet = entities::PetEntity::try_from(pet)
+            .map_err(|e| InsertError::Invalid(models::Error::new(400, e.to_string())))?;
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| InsertError::Failed(internal_error(e)))?;
+
+        diesel::insert_into(entities::pet::table)
+            .values(new_pet)
+            .returning(entities::PetEntity::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map(|_| ())
+            .map_err(|e| InsertError::Failed(internal_error(e)))
+    }
+
+    /// Stored pets, streamed from the database as they are read instead of collecting the
+    /// whole table.
+    fn pets(&self) -> impl Stream<Item = Result<models::Pet, String>> + Send + 'static {
+        let pool = self.pool.clone();
+        async_stream::try_stream! {
+            let mut conn = pool.get_owned().await.map_err(|e| e.to_string())?;
+
+            let mut rows = entities::pet::table
+                .select(entities::PetEntity::as_select())
+                .load_stream::<entities::PetEntity>(&mut *conn)
+                .await
+                .map_err(|e| e.to_string())?;
+
+            while let Some(row) = rows.next().await {
+                yield models::Pet::from(row.map_err(|e| e.to_string())?);
+            }
+        }
+    }
+
+    /// The pet `pet_id`, if it is stored.
+    async fn pet(&self, pet_id: i64) -> Result<Option<models::Pet>, models::Error> {
+        // ids beyond the range of the id column can't be stored
+        let Ok(id) = i32::try_from(pet_id) else {
+            return Ok(None);
+        };
+        let mut conn = self.pool.get().await.map_err(internal_error)?;
+
+        entities::pet::table
+            .find(id)
+            .select(entities::PetEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()
+            .map(|pet| pet.map(models::Pet::from))
+            .map_err(internal_error)
+    }
+}
+
+#[allow(unused_variables)]
+#[async_trait]
+impl petstore_api::Api for ServerState {
+    async fn create_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: models::CreatePetsHeaderParams,
+        body: models::Pet,
+    ) -> Result<CreatePetsResponse, String> {
+        let location = storage::pet_location(BASE_PATH, body.id)?;
+        match self.insert_pet(body).await {
+            Ok(()) => {
+                let mut headers = models::CreatePets201ResponseHeaders::new();
+                headers.extra.headers.insert(LOCATION, location);
+                Ok(CreatePetsResponse::Status201_NullResponse { headers })
+            }
+            Err(InsertError::Invalid(e)) => Ok(CreatePetsResponse::Status400_InvalidPet {
+                body: e,
+                headers: models::CreatePets400ResponseHeaders::new(),
+            }),
+            Err(InsertError::Failed(e)) => Ok(CreatePetsResponse::Status0_UnexpectedError {
+                body: e,
+                headers: models::CreatePetsDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+
+    async fn list_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        cookie_params: models::ListPetsCookieParams,
+        query_params: models::ListPetsQueryParams,
+    ) -> Result<ListPetsResponse, String> {
+        Ok(ListPetsResponse::Status200_APagedArrayOfPets {
+            body: types::ArrayBody::stream(self.pets()),
+            headers: models::ListPets200ResponseHeaders::new(),
+        })
+    }
+
+    async fn show_pet_by_id(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::ShowPetByIdPathParams,
+    ) -> Result<ShowPetByIdResponse, String> {
+        match self.pet(path_params.pet_id).await {
+            Ok(Some(pet)) => Ok(
+                ShowPetByIdResponse::Status200_ExpectedResponseToAValidRequest {
+                    body: pet,
+                    headers: models::ShowPetById200ResponseHeaders::new(),
+                },
+            ),
+            Ok(None) => Ok(ShowPetByIdResponse::Status404_PetNotFound {
+                body: models::Error::new(404, format!("Pet {} not found", path_params.pet_id)),
+                headers: models::ShowPetById404ResponseHeaders::new(),
+            }),
+            Err(e) => Ok(ShowPetByIdResponse::Status0_UnexpectedError {
+                body: e,
+                headers: models::ShowPetByIdDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+
+    async fn show_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: models::ShowPetPhotoHeaderParams,
+        path_params: models::ShowPetPhotoPathParams,
+    ) -> Result<ShowPetPhotoResponse, String> {
+        let not_found = || ShowPetPhotoResponse::Status404_PetOrPhotoNotFound {
+            body: models::Error::new(404, format!("Pet {} has no photo", path_params.pet_id)),
+            headers: models::ShowPetPhoto404ResponseHeaders::new(),
+        };
+        match self.pet_exists(path_params.pet_id).await {
+            Ok(true) => {}
+            Ok(false) => return Ok(not_found()),
+            Err(e) => {
+                return Ok(ShowPetPhotoResponse::Status0_UnexpectedError {
+                    body: e,
+                    headers: models::ShowPetPhotoDefaultResponseHeaders::new(),
+                })
+            }
+        }
+
+        let key = path_params.pet_id.to_string();
+        match self.photos.get(&key, header_params.range.as_deref()).await {
+            Ok(Some(download)) => Ok(storage::v1::photo_response(download)),
+            Ok(None) => Ok(not_found()),
+            Err(e) => Ok(ShowPetPhotoResponse::Status0_UnexpectedError {
+                body: photo_error(e),
+                headers: models::ShowPetPhotoDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+
+    async fn upload_pet_documents(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::UploadPetDocumentsPathParams,
+        body: models::UploadPetDocumentsForm,
+    ) -> Result<UploadPetDocumentsResponse, String> {
+        match self.pet_exists(path_params.pet_id).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return Ok(UploadPetDocumentsResponse::Status404_PetNotFound {
+                    body: models::Error::new(404, format!("Pet {} not found", path_params.pet_id)),
+                    headers: models::UploadPetDocuments404ResponseHeaders::new(),
+                })
+            }
+            Err(e) => {
+                return Ok(UploadPetDocumentsResponse::Status0_UnexpectedError {
+                    body: e,
+                    headers: models::UploadPetDocumentsDefaultResponseHeaders::new(),
+                })
+            }
+        }
+
+        match storage::v1::store_documents(&self.photos, path_params.pet_id, body).await {
+            Ok(documents) => Ok(UploadPetDocumentsResponse::Status201_DocumentsStored {
+                body: documents.into(),
+                headers: models::UploadPetDocuments201ResponseHeaders::new(),
+            }),
+            Err(e) => Ok(UploadPetDocumentsResponse::Status0_UnexpectedError {
+                body: photo_error(e),
+                headers: models::UploadPetDocumentsDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+
+    async fn upload_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::UploadPetPhotoPathParams,
+        body: types::FileUpload,
+    ) -> Result<UploadPetPhotoResponse, String> {
+        match self.pet_exists(path_params.pet_id).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return Ok(UploadPetPhotoResponse::Status404_PetNotFound {
+                    body: models::Error::new(404, format!("Pet {} not found", path_params.pet_id)),
+                    headers: models::UploadPetPhoto404ResponseHeaders::new(),
+                })
+            }
+            Err(e) => {
+                return Ok(UploadPetPhotoResponse::Status0_UnexpectedError {
+                    body: e,
+                    headers: models::UploadPetPhotoDefaultResponseHeaders::new(),
+                })
+            }
+        }
+
+        // the photo is streamed to disk as it arrives
+        match storage::store_photo(&self.photos, path_params.pet_id, body).await {
+            Ok(_) => Ok(UploadPetPhotoResponse::Status204_PhotoStored {
+                headers: models::UploadPetPhoto204ResponseHeaders::new(),
+            }),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                Ok(UploadPetPhotoResponse::Status400_InvalidUpload {
+                    body: models::Error::new(400, e.to_string()),
+                    headers: models::UploadPetPhoto400ResponseHeaders::new(),
+                })
+            }
+            Err(e) => Ok(UploadPetPhotoResponse::Status0_UnexpectedError {
+                body: photo_error(e),
+                headers: models::UploadPetPhotoDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+}
+
+/// Version 2 of the API, storing pets as version 1 does: the `tag` column keeps the first of
+/// their `tags`.
+#[allow(unused_variables)]
+#[async_trait]
+impl v2::Api for ServerState {
+    async fn create_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: v2::models::CreatePetsHeaderParams,
+        body: v2::models::Pet,
+    ) -> Result<v2::CreatePetsResponse, String> {
+        let location = storage::pet_location(v2::BASE_PATH, body.id)?;
+        match self.insert_pet(body.into()).await {
+            Ok(()) => {
+                let mut headers = v2::models::CreatePets201ResponseHeaders::new();
+                headers.extra.headers.insert(LOCATION, location);
+                Ok(v2::CreatePetsResponse::Status201_NullResponse { headers })
+            }
+            Err(InsertError::Invalid(e)) => Ok(v2::CreatePetsResponse::Status400_InvalidPet {
+                body: e.into(),
+                headers: v2::models::CreatePets400ResponseHeaders::new(),
+            }),
+            Err(InsertError::Failed(e)) => Ok(v2::CreatePetsResponse::Status0_UnexpectedError {
+                body: e.into(),
+                headers: v2::models::CreatePetsDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+
+    async fn list_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        cookie_params: v2::models::ListPetsCookieParams,
+        query_params: v2::models::ListPetsQueryParams,
+    ) -> Result<v2::ListPetsResponse, String> {
+        let pets = self.pets().map(|pet| pet.map(v2::models::Pet::from));
+        Ok(v2::ListPetsResponse::Status200_APagedArrayOfPets {
+            body: types::ArrayBody::stream(pets),
+            headers: v2::models::ListPets200ResponseHeaders::new(),
+        })
+    }
+
+    async fn show_pet_by_id(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: v2::models::ShowPetByIdPathParams,
+    ) -> Result<v2::ShowPetByIdResponse, String> {
+        match self.pet(path_params.pet_id).await {
+            Ok(Some(pet)) => Ok(
+                v2::ShowPetByIdResponse::Status200_ExpectedResponseToAValidRequest {
+                    body: pet.into(),
+                    headers: v2::models::ShowPetById200ResponseHeaders::new(),
+                },
+            ),
+            Ok(None) => Ok(v2::ShowPetByIdResponse::Status404_PetNotFound {
+                body: v2::models::Error::new(404, format!("Pet {} not found", path_params.pet_id)),
+                headers: v2::models::ShowPetById404ResponseHeaders::new(),
+            }),
+            Err(e) => Ok(v2::ShowPetByIdResponse::Status0_UnexpectedError {
+                body: e.into(),
+                headers: v2::models::ShowPetByIdDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+
+    async fn show_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: v2::models::ShowPetPhotoHeaderParams,
+        path_params: v2::models::ShowPetPhotoPathParams,
+    ) -> Result<v2::ShowPetPhotoResponse, String> {
+        let not_found = || v2::ShowPetPhotoResponse::Status404_PetOrPhotoNotFound {
+            body: v2::models::Error::new(404, format!("Pet {} has no photo", path_params.pet_id)),
+            headers: v2::models::ShowPetPhoto404ResponseHeaders::new(),
+        };
+        match self.pet_exists(path_params.pet_id).await {
+            Ok(true) => {}
+            Ok(false) => return Ok(not_found()),
+            Err(e) => {
+                return Ok(v2::ShowPetPhotoResponse::Status0_UnexpectedError {
+                    body: e.into(),
+                    headers: v2::models::ShowPetPhotoDefaultResponseHeaders::new(),
+                })
+            }
+        }
+
+        let key = path_params.pet_id.to_string();
+        match self.photos.get(&key, header_params.range.as_deref()).await {
+            Ok(Some(download)) => Ok(storage::v2::photo_response(download)),
+            Ok(None) => Ok(not_found()),
+            Err(e) => Ok(v2::ShowPetPhotoResponse::Status0_UnexpectedError {
+                body: photo_error(e).into(),
+                headers: v2::models::ShowPetPhotoDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+
+    async fn upload_pet_documents(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: v2::models::UploadPetDocumentsPathParams,
+        body: v2::models::UploadPetDocumentsForm,
+    ) -> Result<v2::UploadPetDocumentsResponse, String> {
+        match self.pet_exists(path_params.pet_id).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return Ok(v2::UploadPetDocumentsResponse::Status404_PetNotFound {
+                    body: v2::models::Error::new(
+                        404,
+                        format!("Pet {} not found", path_params.pet_id),
+                    ),
+                    headers: v2::models::UploadPetDocuments404ResponseHeaders::new(),
+                })
+            }
+            Err(e) => {
+                return Ok(v2::UploadPetDocumentsResponse::Status0_UnexpectedError {
+                    body: e.into(),
+                    headers: v2::models::UploadPetDocumentsDefaultResponseHeaders::new(),
+                })
+            }
+        }
+
+        match storage::v2::store_documents(&self.photos, path_params.pet_id, body).await {
+            Ok(documents) => Ok(v2::UploadPetDocumentsResponse::Status201_DocumentsStored {
+                body: documents.into(),
+                headers: v2::models::UploadPetDocuments201ResponseHeaders::new(),
+            }),
+            Err(e) => Ok(v2::UploadPetDocumentsResponse::Status0_UnexpectedError {
+                body: photo_error(e).into(),
+                headers: v2::models::UploadPetDocumentsDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+
+    async fn upload_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: v2::models::UploadPetPhotoPathParams,
+        body: types::FileUpload,
+    ) -> Result<v2::UploadPetPhotoResponse, String> {
+        match self.pet_exists(path_params.pet_id).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return Ok(v2::UploadPetPhotoResponse::Status404_PetNotFound {
+                    body: v2::models::Error::new(
+                        404,
+                        format!("Pet {} not found", path_params.pet_id),
+                    ),
+                    headers: v2::models::UploadPetPhoto404ResponseHeaders::new(),
+                })
+            }
+            Err(e) => {
+                return Ok(v2::UploadPetPhotoResponse::Status0_UnexpectedError {
+                    body: e.into(),
+                    headers: v2::models::UploadPetPhotoDefaultResponseHeaders::new(),
+                })
+            }
+        }
+
+        // the photo is streamed to disk as it arrives
+        match storage::store_photo(&self.photos, path_params.pet_id, body).await {
+            Ok(_) => Ok(v2::UploadPetPhotoResponse::Status204_PhotoStored {
+                headers: v2::models::UploadPetPhoto204ResponseHeaders::new(),
+            }),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                Ok(v2::UploadPetPhotoResponse::Status400_InvalidUpload {
+                    body: v2::models::Error::new(400, e.to_string()),
+                    headers: v2::models::UploadPetPhoto400ResponseHeaders::new(),
+                })
+            }
+            Err(e) => Ok(v2::UploadPetPhotoResponse::Status0_UnexpectedError {
+                body: photo_error(e).into(),
+                headers: v2::models::UploadPetPhotoDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+}
+
+/// Builder of the router serving both API versions, which share a response cache and a rate
+/// limiter, with version 1 deprecated.
+pub fn builder(state: Arc<ServerState>) -> server::Builder<Arc<ServerState>> {
+    // shared by the versions, which invalidate each other's responses and count requests
+    // together
+    let cache = cache::ResponseCache::default();
+    let rate_limiter = security::RateLimiter::default();
+    server::builder(state.clone())
+        .cache(cache.clone())
+        .rate_limiter(rate_limiter.clone())
+        .deprecation(
+            deprecation::Deprecation::new(Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap())
+                .sunset(Utc.with_ymd_and_hms(2027, 6, 1, 0, 0, 0).unwrap())
+                .link("https://petstore.swagger.io/docs/migrating-to-v2"),
+        )
+        .version(
+            v2::server::builder(state)
+                .cache(cache)
+                .rate_limiter(rate_limiter)
+                .routes(),
+        )
+}
+
+/// Utility function for logging any error and mapping it into the body of an
+/// `unexpected error` response.
+fn internal_error<E>(err: E) -> models::Error
+where
+    E: std::fmt::Display,
+{
+    tracing::error!(error = %err, "Database request failed");
+    models::Error::new(
+        StatusCode::INTERNAL_SERVER_ERROR.as_u16().into(),
+        err.to_string(),
+    )
+}
+
+/// Like [`internal_error`], for failures of the photo storage.
+fn photo_error(err: io::Error) -> models::Error {
+    tracing::error!(error = %err, "Photo storage failed");
+    models::Error::new(
+        StatusCode::INTERNAL_SERVER_ERROR.as_u16().into(),
+        err.to_string(),
+    )
+}
@@ -0,0 +1,58 @@
+//! Pets rejected before they reach the database, which these tests don't have.
+
+use std::sync::Arc;
+
+use axum::body::{self, Body};
+use diesel_async::pooled_connection::AsyncDieselConnectionManager;
+use http::{header::CONTENT_TYPE, Request, StatusCode};
+use tower::ServiceExt;
+
+use petstore_api::{blob, models};
+use petstore_db_svc::ServerState;
+
+/// Both API versions, with a pool which never connects.
+fn app(photos: &tempfile::TempDir) -> axum::Router {
+    let manager = AsyncDieselConnectionManager::new("postgres://localhost/petstore");
+    let pool = bb8::Pool::builder().build_unchecked(manager);
+    let state = ServerState::new(pool, blob::BlobStore::new(photos.path()));
+    petstore_db_svc::builder(Arc::new(state)).build()
+}
+
+#[tokio::test]
+async fn pets_which_cant_be_stored_are_rejected_with_400() {
+    let photos = tempfile::tempdir().unwrap();
+    for (uri, pet) in [
+        // attributes aren't stored
+        (
+            "http://localhost/v1/pets",
+            r#"{"id":1,"name":"Rex","attributes":{"color":"brown"}}"#,
+        ),
+        (
+            "http://localhost/v2/pets",
+            r#"{"id":1,"name":"Rex","attributes":{"color":"brown"}}"#,
+        ),
+        // ids beyond the range of the id column
+        (
+            "http://localhost/v1/pets",
+            r#"{"id":4294967296,"name":"Rex"}"#,
+        ),
+    ] {
+        let response = app(&photos)
+            .oneshot(
+                Request::post(uri)
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(pet))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", pet);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        let body = body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let error: models::Error = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error.code, 400);
+    }
+}
@@ -0,0 +1,132 @@
+//! Documents and photos of pets in a [`BlobStore`], shared by the services. Written by hand
+//! next to the generated `petstore-api`, as the generator can't tell how stored files map onto
+//! the `Document` model.
+//!
+//! The documents and photo operations are the same in every API version, so the responses and
+//! models of each are built by the functions of its module, [`v1`] and [`v2`].
+
+use std::io;
+
+use http::HeaderValue;
+
+use petstore_api::{blob::BlobStore, types};
+
+/// Media types of photos which are served as such; others are served as
+/// `application/octet-stream`.
+pub const PHOTO_MEDIA_TYPES: [&str; 2] = ["image/jpeg", "image/png"];
+
+/// URL of a created pet below `base_path`, sent as its `Location`.
+pub fn pet_location(base_path: &str, id: i64) -> Result<HeaderValue, String> {
+    HeaderValue::from_str(&format!("{}/pets/{}", base_path, id)).map_err(|e| e.to_string())
+}
+
+/// Store the photo of the pet `pet_id` of an `uploadPetPhoto` request, streamed to the store
+/// as it arrives, and return its size.
+///
+/// Malformed and oversized uploads fail with an error of kind [`io::ErrorKind::InvalidData`].
+pub async fn store_photo(
+    store: &BlobStore,
+    pet_id: i64,
+    upload: types::FileUpload,
+) -> io::Result<u64> {
+    let photo = upload.into_file("photo").await?;
+    let content_type = photo
+        .content_type
+        .as_deref()
+        .filter(|content_type| PHOTO_MEDIA_TYPES.contains(content_type));
+    store
+        .put(&pet_id.to_string(), content_type, photo.data)
+        .await
+}
+
+/// `store_documents` and `photo_response` for the models and responses of an API version.
+macro_rules! version_storage {
+    ($version:ident) => {
+        use std::io;
+
+        use futures::stream;
+
+        use petstore_api::{
+            blob::{self, BlobStore},
+            $version::{models, ShowPetPhotoResponse},
+        };
+
+        /// Store the files of an `uploadPetDocuments` form, each under its own key, and
+        /// describe them.
+        pub async fn store_documents(
+            store: &BlobStore,
+            pet_id: i64,
+            form: models::UploadPetDocumentsForm,
+        ) -> io::Result<Vec<models::Document>> {
+            let files = form
+                .photos
+                .into_iter()
+                .enumerate()
+                .map(|(i, file)| ("photos", format!("{}-photos-{}", pet_id, i), file))
+                .chain(
+                    form.certificate
+                        .map(|file| ("certificate", format!("{}-certificate", pet_id), file)),
+                );
+
+            let mut documents = Vec::new();
+            for (part, key, file) in files {
+                let data = file.data;
+                let size = store
+                    .put(
+                        &key,
+                        file.content_type.as_deref(),
+                        stream::once(async move { Ok(data) }),
+                    )
+                    .await?;
+                documents.push(models::Document {
+                    part: part.to_string(),
+                    file_name: file.file_name,
+                    content_type: file.content_type,
+                    size: i64::try_from(size).unwrap_or(i64::MAX),
+                });
+            }
+            Ok(documents)
+        }
+
+        /// Map a photo read from the blob store to the response of `showPetPhoto`.
+        pub fn photo_response(download: blob::Download) -> ShowPetPhotoResponse {
+            match download {
+                blob::Download::Full { body, len } => ShowPetPhotoResponse::Status200_ThePhoto {
+                    body,
+                    headers: models::ShowPetPhoto200ResponseHeaders {
+                        accept_ranges: Some("bytes".to_string()),
+                        ..models::ShowPetPhoto200ResponseHeaders::new(
+                            i64::try_from(len).unwrap_or(i64::MAX),
+                        )
+                    },
+                },
+                blob::Download::Partial {
+                    body,
+                    len,
+                    content_range,
+                } => ShowPetPhotoResponse::Status206_ARangeOfThePhoto {
+                    body,
+                    headers: models::ShowPetPhoto206ResponseHeaders::new(
+                        i64::try_from(len).unwrap_or(i64::MAX),
+                        content_range,
+                    ),
+                },
+                blob::Download::Unsatisfiable { content_range } => {
+                    ShowPetPhotoResponse::Status416_RangeNotSatisfiable {
+                        headers: models::ShowPetPhoto416ResponseHeaders::new(content_range),
+                    }
+                }
+            }
+        }
+    };
+}
+
+/// Documents and photos in the models and responses of API version 1.
+pub mod v1 {
+    version_storage!(v1);
+}
+
+/// Documents and photos in the models and responses of API version 2.
+pub mod v2 {
+    version_storage!(v2);
+}
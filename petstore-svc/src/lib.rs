@@ -0,0 +1,305 @@
+//! Implementation of the petstore API for both API versions, without a database: pets are
+//! made up from their ids, and their documents and photos are kept in a blob store.
+
+use async_trait::async_trait;
+use axum::extract::*;
+use axum_extra::extract::CookieJar;
+use chrono::{TimeZone, Utc};
+use http::{header::LOCATION, Method};
+use std::{io, sync::Arc};
+
+use petstore_storage as storage;
+
+use petstore_api::*;
+
+pub struct ServerState {
+    // database: sea_orm::DbConn,
+    photos: blob::BlobStore,
+}
+
+impl ServerState {
+    /// Keep the documents and photos of pets in `photos`.
+    pub fn new(photos: blob::BlobStore) -> Self {
+        ServerState { photos }
+    }
+}
+
+#[allow(unused_variables)]
+#[async_trait]
+impl petstore_api::Api for ServerState {
+    async fn create_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: models::CreatePetsHeaderParams,
+        body: models::Pet,
+    ) -> Result<CreatePetsResponse, String> {
+        let mut headers = models::CreatePets201ResponseHeaders::new();
+        headers
+            .extra
+            .headers
+            .insert(LOCATION, storage::pet_location(BASE_PATH, body.id)?);
+        Ok(CreatePetsResponse::Status201_NullResponse { headers })
+    }
+
+    async fn list_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        cookie_params: models::ListPetsCookieParams,
+        query_params: models::ListPetsQueryParams,
+    ) -> Result<ListPetsResponse, String> {
+        Ok(ListPetsResponse::Status200_APagedArrayOfPets {
+            body: Vec::new().into(),
+            headers: models::ListPets200ResponseHeaders::new(),
+        })
+    }
+
+    async fn show_pet_by_id(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::ShowPetByIdPathParams,
+    ) -> Result<ShowPetByIdResponse, String> {
+        Ok(
+            ShowPetByIdResponse::Status200_ExpectedResponseToAValidRequest {
+                body: pet(path_params.pet_id),
+                headers: models::ShowPetById200ResponseHeaders::new(),
+            },
+        )
+    }
+
+    async fn show_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: models::ShowPetPhotoHeaderParams,
+        path_params: models::ShowPetPhotoPathParams,
+    ) -> Result<ShowPetPhotoResponse, String> {
+        let key = path_params.pet_id.to_string();
+        match self.photos.get(&key, header_params.range.as_deref()).await {
+            Ok(Some(download)) => Ok(storage::v1::photo_response(download)),
+            Ok(None) => Ok(ShowPetPhotoResponse::Status404_PetOrPhotoNotFound {
+                body: models::Error::new(404, format!("Pet {} has no photo", path_params.pet_id)),
+                headers: models::ShowPetPhoto404ResponseHeaders::new(),
+            }),
+            Err(e) => Ok(ShowPetPhotoResponse::Status0_UnexpectedError {
+                body: models::Error::new(500, e.to_string()),
+                headers: models::ShowPetPhotoDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+
+    async fn upload_pet_documents(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::UploadPetDocumentsPathParams,
+        body: models::UploadPetDocumentsForm,
+    ) -> Result<UploadPetDocumentsResponse, String> {
+        match storage::v1::store_documents(&self.photos, path_params.pet_id, body).await {
+            Ok(documents) => Ok(UploadPetDocumentsResponse::Status201_DocumentsStored {
+                body: documents.into(),
+                headers: models::UploadPetDocuments201ResponseHeaders::new(),
+            }),
+            Err(e) => Ok(UploadPetDocumentsResponse::Status0_UnexpectedError {
+                body: models::Error::new(500, e.to_string()),
+                headers: models::UploadPetDocumentsDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+
+    async fn upload_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::UploadPetPhotoPathParams,
+        body: types::FileUpload,
+    ) -> Result<UploadPetPhotoResponse, String> {
+        match storage::store_photo(&self.photos, path_params.pet_id, body).await {
+            Ok(_) => Ok(UploadPetPhotoResponse::Status204_PhotoStored {
+                headers: models::UploadPetPhoto204ResponseHeaders::new(),
+            }),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                Ok(UploadPetPhotoResponse::Status400_InvalidUpload {
+                    body: models::Error::new(400, e.to_string()),
+                    headers: models::UploadPetPhoto400ResponseHeaders::new(),
+                })
+            }
+            Err(e) => Ok(UploadPetPhotoResponse::Status0_UnexpectedError {
+                body: models::Error::new(500, e.to_string()),
+                headers: models::UploadPetPhotoDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+}
+
+#[allow(unused_variables)]
+#[async_trait]
+impl v2::Api for ServerState {
+    async fn create_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: v2::models::CreatePetsHeaderParams,
+        body: v2::models::Pet,
+    ) -> Result<v2::CreatePetsResponse, String> {
+        let mut headers = v2::models::CreatePets201ResponseHeaders::new();
+        headers
+            .extra
+            .headers
+            .insert(LOCATION, storage::pet_location(v2::BASE_PATH, body.id)?);
+        Ok(v2::CreatePetsResponse::Status201_NullResponse { headers })
+    }
+
+    async fn list_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        cookie_params: v2::models::ListPetsCookieParams,
+        query_params: v2::models::ListPetsQueryParams,
+    ) -> Result<v2::ListPetsResponse, String> {
+        Ok(v2::ListPetsResponse::Status200_APagedArrayOfPets {
+            body: Vec::new().into(),
+            headers: v2::models::ListPets200ResponseHeaders::new(),
+        })
+    }
+
+    async fn show_pet_by_id(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: v2::models::ShowPetByIdPathParams,
+    ) -> Result<v2::ShowPetByIdResponse, String> {
+        Ok(
+            v2::ShowPetByIdResponse::Status200_ExpectedResponseToAValidRequest {
+                body: pet(path_params.pet_id).into(),
+                headers: v2::models::ShowPetById200ResponseHeaders::new(),
+            },
+        )
+    }
+
+    async fn show_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: v2::models::ShowPetPhotoHeaderParams,
+        path_params: v2::models::ShowPetPhotoPathParams,
+    ) -> Result<v2::ShowPetPhotoResponse, String> {
+        let key = path_params.pet_id.to_string();
+        match self.photos.get(&key, header_params.range.as_deref()).await {
+            Ok(Some(download)) => Ok(storage::v2::photo_response(download)),
+            Ok(None) => Ok(v2::ShowPetPhotoResponse::Status404_PetOrPhotoNotFound {
+                body: v2::models::Error::new(
+                    404,
+                    format!("Pet {} has no photo", path_params.pet_id),
+                ),
+                headers: v2::models::ShowPetPhoto404ResponseHeaders::new(),
+            }),
+            Err(e) => Ok(v2::ShowPetPhotoResponse::Status0_UnexpectedError {
+                body: v2::models::Error::new(500, e.to_string()),
+                headers: v2::models::ShowPetPhotoDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+
+    async fn upload_pet_documents(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: v2::models::UploadPetDocumentsPathParams,
+        body: v2::models::UploadPetDocumentsForm,
+    ) -> Result<v2::UploadPetDocumentsResponse, String> {
+        match storage::v2::store_documents(&self.photos, path_params.pet_id, body).await {
+            Ok(documents) => Ok(v2::UploadPetDocumentsResponse::Status201_DocumentsStored {
+                body: documents.into(),
+                headers: v2::models::UploadPetDocuments201ResponseHeaders::new(),
+            }),
+            Err(e) => Ok(v2::UploadPetDocumentsResponse::Status0_UnexpectedError {
+                body: v2::models::Error::new(500, e.to_string()),
+                headers: v2::models::UploadPetDocumentsDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+
+    async fn upload_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: v2::models::UploadPetPhotoPathParams,
+        body: types::FileUpload,
+    ) -> Result<v2::UploadPetPhotoResponse, String> {
+        match storage::store_photo(&self.photos, path_params.pet_id, body).await {
+            Ok(_) => Ok(v2::UploadPetPhotoResponse::Status204_PhotoStored {
+                headers: v2::models::UploadPetPhoto204ResponseHeaders::new(),
+            }),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                Ok(v2::UploadPetPhotoResponse::Status400_InvalidUpload {
+                    body: v2::models::Error::new(400, e.to_string()),
+                    headers: v2::models::UploadPetPhoto400ResponseHeaders::new(),
+                })
+            }
+            Err(e) => Ok(v2::UploadPetPhotoResponse::Status0_UnexpectedError {
+                body: v2::models::Error::new(500, e.to_string()),
+                headers: v2::models::UploadPetPhotoDefaultResponseHeaders::new(),
+            }),
+        }
+    }
+}
+
+/// The pet `id`, in the models of version 1.
+fn pet(id: i64) -> models::Pet {
+    models::Pet {
+        id,
+        name: "pet".to_string(),
+        tag: None,
+        attributes: Some(serde_json::json!({ "color": "brown" }).into()),
+    }
+}
+
+/// Builder of the router serving both API versions, which share a response cache and a rate
+/// limiter, with version 1 deprecated.
+pub fn builder(state: Arc<ServerState>) -> server::Builder<Arc<ServerState>> {
+    // shared by the versions, which invalidate each other's responses and count requests
+    // together
+    let cache = cache::ResponseCache::default();
+    let rate_limiter = security::RateLimiter::default();
+    server::builder(state.clone())
+        .cache(cache.clone())
+        .rate_limiter(rate_limiter.clone())
+        .deprecation(
+            deprecation::Deprecation::new(Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap())
+                .sunset(Utc.with_ymd_and_hms(2027, 6, 1, 0, 0, 0).unwrap())
+                .link("https://petstore.swagger.io/docs/migrating-to-v2"),
+        )
+        .version(
+            v2::server::builder(state)
+                .cache(cache)
+                .rate_limiter(rate_limiter)
+                .routes(),
+        )
+}
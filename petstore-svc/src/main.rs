@@ -1,65 +1,35 @@
-use async_trait::async_trait;
-use axum::extract::*;
-use axum_extra::extract::CookieJar;
-use http::Method;
 use std::sync::Arc;
 
-use petstore_api::*;
+use petstore_api::{blob, security, serve, telemetry};
+use petstore_svc::ServerState;
 
-struct ServerState {
-    // database: sea_orm::DbConn,
-}
+#[tokio::main]
+async fn main() {
+    let tracer_provider = telemetry::init(
+        "petstore-svc",
+        &telemetry::LogConfig::from_env().expect("Logging is configured"),
+    )
+    .expect("Tracing is configured");
 
-#[allow(unused_variables)]
-#[async_trait]
-impl petstore_api::Api for ServerState {
-    async fn create_pets(
-        &self,
-        method: Method,
-        host: Host,
-        cookies: CookieJar,
-        body: models::Pet,
-    ) -> Result<CreatePetsResponse, String> {
-        Ok(CreatePetsResponse::Status201_NullResponse)
+    let state = ServerState::new(blob::BlobStore::from_env());
+    let mut app = petstore_svc::builder(Arc::new(state));
+    if let Some(cors) = security::cors_from_env().expect("CORS is configured") {
+        app = app.cors(cors);
     }
+    let app = app.build();
 
-    async fn list_pets(
-        &self,
-        method: Method,
-        host: Host,
-        cookies: CookieJar,
-        query_params: models::ListPetsQueryParams,
-    ) -> Result<ListPetsResponse, String> {
-        Ok(ListPetsResponse::Status200_APagedArrayOfPets {
-            body: Vec::new(),
-            x_next: None,
-        })
-    }
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+    tracing::info!(address = %listener.local_addr().unwrap(), "Listening");
+    serve::serve_with_graceful_shutdown(
+        listener,
+        app,
+        serve::TlsConfig::from_env(),
+        serve::shutdown_signal(),
+    )
+    .await
+    .unwrap();
 
-    async fn show_pet_by_id(
-        &self,
-        method: Method,
-        host: Host,
-        cookies: CookieJar,
-        path_params: models::ShowPetByIdPathParams,
-    ) -> Result<ShowPetByIdResponse, String> {
-        Ok(
-            ShowPetByIdResponse::Status200_ExpectedResponseToAValidRequest(
-                petstore_api::models::Pet {
-                    id: 1,
-                    name: "pet".to_string(),
-                    tag: None,
-                },
-            ),
-        )
+    if let Some(tracer_provider) = tracer_provider {
+        tracer_provider.shutdown().unwrap();
     }
 }
-
-#[tokio::main]
-async fn main() {
-    let app = petstore_api::server::new(Arc::new(ServerState {}));
-
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
-    println!("listening on {}", listener.local_addr().unwrap());
-    axum::serve(listener, app).await.unwrap();
-}
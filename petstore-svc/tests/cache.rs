@@ -0,0 +1,207 @@
+//! Response caching of operations with an `x-cache` extension.
+
+mod common;
+
+use std::{
+    convert::Infallible,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use axum::{
+    body::{self, Body},
+    response::Response,
+    routing::get as get_route,
+    Router,
+};
+use http::{
+    header::{AGE, CACHE_CONTROL, CONTENT_TYPE, VARY},
+    Request, StatusCode,
+};
+use tower::{service_fn, Layer, ServiceExt};
+
+use petstore_api::{
+    cache::{CachePolicy, ResponseCache},
+    contract::{Contract, ContractLayer, Mode},
+    server,
+};
+
+use common::ServerState;
+
+fn get(uri: &str, accept: &str) -> Request<Body> {
+    Request::get(uri)
+        .header("accept", accept)
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// Send `request` and read the whole body, which completes caching the response.
+async fn send<S>(service: S, request: Request<Body>) -> (Response<()>, String)
+where
+    S: tower::Service<Request<Body>, Response = Response, Error = Infallible>,
+{
+    let response = service.oneshot(request).await.unwrap();
+    let (parts, body) = response.into_parts();
+    let body = body::to_bytes(body, usize::MAX).await.unwrap();
+    (
+        Response::from_parts(parts, ()),
+        String::from_utf8(body.to_vec()).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn responses_are_served_from_the_cache() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let cache = ResponseCache::default();
+    let policy = CachePolicy::new(Duration::from_secs(60), &["accept"]);
+    let service = cache.layer(Some(policy)).layer(service_fn({
+        let calls = calls.clone();
+        move |_: Request<Body>| {
+            let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            async move { Ok::<_, Infallible>(Response::new(Body::from(format!("call {}", call)))) }
+        }
+    }));
+
+    let (response, body) = send(service.clone(), get("/v1/pets/1", "application/json")).await;
+    assert_eq!(body, "call 1");
+    assert_eq!(response.headers()[CACHE_CONTROL], "max-age=60");
+    assert_eq!(response.headers()[VARY], "accept");
+    assert_eq!(response.headers()[AGE], "0");
+
+    let (response, body) = send(service.clone(), get("/v1/pets/1", "application/json")).await;
+    assert_eq!(body, "call 1");
+    assert_eq!(response.headers()[CACHE_CONTROL], "max-age=60");
+    assert!(response.headers().contains_key(AGE));
+
+    // other representations and other queries are cached separately
+    let (_, body) = send(service.clone(), get("/v1/pets/1", "application/xml")).await;
+    assert_eq!(body, "call 2");
+    let (_, body) = send(service.clone(), get("/v1/pets/1?a=b", "application/json")).await;
+    assert_eq!(body, "call 3");
+    assert_eq!(cache.len(), 3);
+
+    // no-cache requests go to the operation and refresh the cache
+    let mut request = get("/v1/pets/1", "application/json");
+    request
+        .headers_mut()
+        .insert(CACHE_CONTROL, "no-cache".parse().unwrap());
+    let (_, body) = send(service.clone(), request).await;
+    assert_eq!(body, "call 4");
+    let (_, body) = send(service, get("/v1/pets/1", "application/json")).await;
+    assert_eq!(body, "call 4");
+}
+
+#[tokio::test]
+async fn least_recently_used_responses_are_evicted() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let cache = ResponseCache::new(2);
+    let policy = CachePolicy::new(Duration::from_secs(60), &[]);
+    let service = cache.layer(Some(policy)).layer(service_fn({
+        let calls = calls.clone();
+        move |_: Request<Body>| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Ok::<_, Infallible>(Response::new(Body::empty())) }
+        }
+    }));
+
+    for uri in ["/a", "/b", "/a", "/c"] {
+        send(service.clone(), get(uri, "*/*")).await;
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert_eq!(cache.len(), 2);
+
+    // /b was the least recently used when /c was cached
+    send(service.clone(), get("/a", "*/*")).await;
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    send(service, get("/b", "*/*")).await;
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn successful_mutations_invalidate_cached_responses() {
+    let cache = ResponseCache::default();
+    let app = server::builder(Arc::new(ServerState))
+        .cache(cache.clone())
+        .build();
+
+    let (response, _) = send(
+        app.clone(),
+        get("http://localhost/v1/pets", "application/json"),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CACHE_CONTROL], "max-age=30");
+    assert_eq!(response.headers()[VARY], "accept, cookie");
+    assert_eq!(cache.len(), 1);
+
+    // rejected requests change nothing, e.g. one with an empty name
+    let (response, _) = send(
+        app.clone(),
+        Request::post("http://localhost/v1/pets")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"id":7,"name":""}"#))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(cache.len(), 1);
+
+    let (response, _) = send(
+        app,
+        Request::post("http://localhost/v1/pets")
+            .header("content-type", "application/json")
+            .header("idempotency-key", "1")
+            .body(Body::from(r#"{"id":7,"name":"Rex"}"#))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert!(cache.is_empty());
+}
+
+#[tokio::test]
+async fn errors_are_not_cached() {
+    let cache = ResponseCache::default();
+    let app = server::builder(Arc::new(ServerState))
+        .cache(cache.clone())
+        .build();
+
+    let (response, _) = send(app, get("http://localhost/v1/pets/1", "application/json")).await;
+    assert!(!response.status().is_success());
+    assert!(!response.headers().contains_key(CACHE_CONTROL));
+    assert!(cache.is_empty());
+}
+
+#[tokio::test]
+async fn cached_responses_are_contract_checked() {
+    let cache = ResponseCache::default();
+    let policy = CachePolicy::new(Duration::from_secs(60), &[]);
+    // a pet whose id is a string, cached as showPetById is
+    let app = Router::new()
+        .route(
+            "/v1/pets/:pet_id",
+            get_route(|| async {
+                (
+                    [(CONTENT_TYPE, "application/json")],
+                    r#"{"id":"1","name":"Rex"}"#,
+                )
+            }),
+        )
+        .layer(cache.layer(Some(policy)))
+        .layer(ContractLayer::new(Contract::new().unwrap(), Mode::Fail));
+
+    // when the response is cached and when it is served from the cache
+    for _ in 0..2 {
+        let (response, body) = send(
+            app.clone(),
+            get("http://localhost/v1/pets/1", "application/json"),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(body.starts_with("GET /v1/pets/1 -> 200 OK: "), "{}", body);
+    }
+    assert_eq!(cache.len(), 1);
+}
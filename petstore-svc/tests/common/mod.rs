@@ -0,0 +1,216 @@
+//! API implementation shared by the tests of the generated server, a stub answering with what
+//! the tests check. The service of the crate is tested by the photos, forms and media types
+//! tests.
+
+use async_trait::async_trait;
+use axum::extract::*;
+use axum_extra::extract::{cookie::Cookie, CookieJar};
+use http::{header::LOCATION, HeaderValue, Method};
+
+use petstore_api::*;
+
+pub struct ServerState;
+
+#[allow(unused_variables)]
+#[async_trait]
+impl Api for ServerState {
+    async fn create_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: models::CreatePetsHeaderParams,
+        body: models::Pet,
+    ) -> Result<CreatePetsResponse, String> {
+        // the pet is remembered in a cookie, to check extra headers and cookies reach the client
+        let mut headers = models::CreatePets201ResponseHeaders::new();
+        headers.extra = types::ExtraHeaders::default()
+            .header(
+                LOCATION,
+                HeaderValue::from_str(&format!("/v1/pets/{}", body.id)).unwrap(),
+            )
+            .cookies(cookies.add(Cookie::new("last_pet", body.id.to_string())));
+        Ok(CreatePetsResponse::Status201_NullResponse { headers })
+    }
+
+    async fn list_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        cookie_params: models::ListPetsCookieParams,
+        query_params: models::ListPetsQueryParams,
+    ) -> Result<ListPetsResponse, String> {
+        // the client identity, if any, is listed as pet to check it reaches the API
+        let pets = identity
+            .into_iter()
+            .map(|identity| models::Pet::new(1, identity.subject))
+            .collect::<Vec<_>>();
+        Ok(ListPetsResponse::Status200_APagedArrayOfPets {
+            body: pets.into(),
+            headers: models::ListPets200ResponseHeaders::new(),
+        })
+    }
+
+    async fn show_pet_by_id(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::ShowPetByIdPathParams,
+    ) -> Result<ShowPetByIdResponse, String> {
+        Ok(ShowPetByIdResponse::Status0_UnexpectedError {
+            body: models::Error::new(404, "not found".to_string()),
+            headers: models::ShowPetByIdDefaultResponseHeaders::new(),
+        })
+    }
+
+    async fn show_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: models::ShowPetPhotoHeaderParams,
+        path_params: models::ShowPetPhotoPathParams,
+    ) -> Result<ShowPetPhotoResponse, String> {
+        // photos are stored by the service of the crate, see the photos tests
+        Ok(ShowPetPhotoResponse::Status404_PetOrPhotoNotFound {
+            body: models::Error::new(404, "not found".to_string()),
+            headers: models::ShowPetPhoto404ResponseHeaders::new(),
+        })
+    }
+
+    async fn upload_pet_documents(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::UploadPetDocumentsPathParams,
+        body: models::UploadPetDocumentsForm,
+    ) -> Result<UploadPetDocumentsResponse, String> {
+        Ok(UploadPetDocumentsResponse::Status201_DocumentsStored {
+            body: Vec::new().into(),
+            headers: models::UploadPetDocuments201ResponseHeaders::new(),
+        })
+    }
+
+    async fn upload_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::UploadPetPhotoPathParams,
+        body: types::FileUpload,
+    ) -> Result<UploadPetPhotoResponse, String> {
+        Ok(UploadPetPhotoResponse::Status204_PhotoStored {
+            headers: models::UploadPetPhoto204ResponseHeaders::new(),
+        })
+    }
+}
+
+#[allow(unused_variables)]
+#[async_trait]
+impl v2::Api for ServerState {
+    async fn create_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: v2::models::CreatePetsHeaderParams,
+        body: v2::models::Pet,
+    ) -> Result<v2::CreatePetsResponse, String> {
+        let mut headers = v2::models::CreatePets201ResponseHeaders::new();
+        headers.extra = types::ExtraHeaders::default().header(
+            LOCATION,
+            HeaderValue::from_str(&format!("/v2/pets/{}", body.id)).unwrap(),
+        );
+        Ok(v2::CreatePetsResponse::Status201_NullResponse { headers })
+    }
+
+    async fn list_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        cookie_params: v2::models::ListPetsCookieParams,
+        query_params: v2::models::ListPetsQueryParams,
+    ) -> Result<v2::ListPetsResponse, String> {
+        Ok(v2::ListPetsResponse::Status200_APagedArrayOfPets {
+            body: Vec::new().into(),
+            headers: v2::models::ListPets200ResponseHeaders::new(),
+        })
+    }
+
+    async fn show_pet_by_id(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: v2::models::ShowPetByIdPathParams,
+    ) -> Result<v2::ShowPetByIdResponse, String> {
+        // version 2 pets have several tags
+        let pet = v2::models::Pet {
+            tags: Some(vec!["dog".to_string(), "brown".to_string()]),
+            ..v2::models::Pet::new(path_params.pet_id, "Rex".to_string())
+        };
+        Ok(
+            v2::ShowPetByIdResponse::Status200_ExpectedResponseToAValidRequest {
+                body: pet,
+                headers: v2::models::ShowPetById200ResponseHeaders::new(),
+            },
+        )
+    }
+
+    async fn show_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: v2::models::ShowPetPhotoHeaderParams,
+        path_params: v2::models::ShowPetPhotoPathParams,
+    ) -> Result<v2::ShowPetPhotoResponse, String> {
+        Ok(v2::ShowPetPhotoResponse::Status404_PetOrPhotoNotFound {
+            body: v2::models::Error::new(404, "not found".to_string()),
+            headers: v2::models::ShowPetPhoto404ResponseHeaders::new(),
+        })
+    }
+
+    async fn upload_pet_documents(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: v2::models::UploadPetDocumentsPathParams,
+        body: v2::models::UploadPetDocumentsForm,
+    ) -> Result<v2::UploadPetDocumentsResponse, String> {
+        Ok(v2::UploadPetDocumentsResponse::Status201_DocumentsStored {
+            body: Vec::new().into(),
+            headers: v2::models::UploadPetDocuments201ResponseHeaders::new(),
+        })
+    }
+
+    async fn upload_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: v2::models::UploadPetPhotoPathParams,
+        body: types::FileUpload,
+    ) -> Result<v2::UploadPetPhotoResponse, String> {
+        Ok(v2::UploadPetPhotoResponse::Status204_PhotoStored {
+            headers: v2::models::UploadPetPhoto204ResponseHeaders::new(),
+        })
+    }
+}
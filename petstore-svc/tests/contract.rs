@@ -0,0 +1,159 @@
+//! Responses checked against the OpenAPI document, for recorded requests and by the
+//! `ContractLayer`.
+
+mod common;
+
+use std::{
+    io,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::{
+    body::{Body, HttpBody},
+    response::Response,
+    routing::get,
+    Router,
+};
+use bytes::Bytes;
+use http::{
+    header::{CONTENT_LENGTH, CONTENT_TYPE},
+    Request, StatusCode,
+};
+use http_body::{Frame, SizeHint};
+use tower::ServiceExt;
+
+use petstore_api::{
+    contract::{self, Contract, ContractLayer, Mode},
+    server,
+};
+
+use common::ServerState;
+
+/// Requests as a client sends them, covering each JSON operation.
+fn recorded_requests() -> Vec<Request<Body>> {
+    let create_pet = |body: &'static str| {
+        Request::post("http://localhost/v1/pets")
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    };
+    vec![
+        Request::get("http://localhost/v1/pets?limit=10")
+            .body(Body::empty())
+            .unwrap(),
+        create_pet(r#"{"id":1,"name":"Rex","tag":"dog"}"#),
+        create_pet(r#"{"id":2,"name":"Tom","tag":null}"#),
+        Request::get("http://localhost/v1/pets/1")
+            .body(Body::empty())
+            .unwrap(),
+    ]
+}
+
+/// Router answering `GET /v1/pets/:pet_id` with a pet whose id is a string.
+fn nonconforming_router() -> Router {
+    Router::new().route(
+        "/v1/pets/:pet_id",
+        get(|| async {
+            (
+                [(CONTENT_TYPE, "application/json")],
+                r#"{"id":"1","name":"Rex"}"#,
+            )
+        }),
+    )
+}
+
+/// JSON body of a known length whose connection fails while it is read.
+struct FailingBody;
+
+impl HttpBody for FailingBody {
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, io::Error>>> {
+        Poll::Ready(Some(Err(io::ErrorKind::ConnectionReset.into())))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(23)
+    }
+}
+
+#[tokio::test]
+async fn recorded_requests_conform() {
+    contract::assert_conformance(server::new(Arc::new(ServerState)), recorded_requests()).await;
+}
+
+#[tokio::test]
+#[should_panic(expected = "GET /v1/pets/1 -> 200 OK")]
+async fn nonconforming_responses_are_reported() {
+    contract::assert_conformance(
+        nonconforming_router(),
+        [Request::get("http://localhost/v1/pets/1")
+            .body(Body::empty())
+            .unwrap()],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn nonconforming_responses_fail_in_fail_mode() {
+    let app =
+        nonconforming_router().layer(ContractLayer::new(Contract::new().unwrap(), Mode::Fail));
+
+    let response = app
+        .oneshot(
+            Request::get("http://localhost/v1/pets/1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        response.headers()[CONTENT_TYPE],
+        "text/plain; charset=utf-8"
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.starts_with("GET /v1/pets/1 -> 200 OK: "), "{}", body);
+}
+
+#[tokio::test]
+async fn unreadable_responses_are_replaced_by_errors() {
+    let app = Router::new()
+        .route(
+            "/v1/pets/:pet_id",
+            get(|| async {
+                Response::builder()
+                    .header(CONTENT_TYPE, "application/json")
+                    .header(CONTENT_LENGTH, "23")
+                    .body(Body::new(FailingBody))
+                    .unwrap()
+            }),
+        )
+        .layer(ContractLayer::new(Contract::new().unwrap(), Mode::Log));
+
+    let response = app
+        .oneshot(
+            Request::get("http://localhost/v1/pets/1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    // the headers describe the empty body, not the lost one
+    assert!(response.headers().get(CONTENT_TYPE).is_none());
+    assert_eq!(response.headers()[CONTENT_LENGTH], "0");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(body.is_empty());
+}
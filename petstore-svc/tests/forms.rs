@@ -0,0 +1,245 @@
+//! `application/x-www-form-urlencoded` and `multipart/form-data` request bodies, as read by the
+//! service.
+
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    Router,
+};
+use http::{Request, StatusCode};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+use petstore_api::{
+    blob::{BlobStore, Download},
+    models, server, style,
+};
+use petstore_svc::ServerState;
+
+const BOUNDARY: &str = "form-boundary";
+
+/// The service, storing documents in `dir`.
+fn app(dir: &TempDir) -> Router {
+    server::new(Arc::new(ServerState::new(BlobStore::new(dir.path()))))
+}
+
+fn create_pet(content_type: &str, body: &str) -> Request<Body> {
+    Request::post("http://localhost/v1/pets")
+        .header("content-type", content_type)
+        .header("idempotency-key", "1")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+/// A part of a `multipart/form-data` body: name, file name and content type, and content.
+type Part<'a> = (&'a str, Option<(&'a str, &'a str)>, Vec<u8>);
+
+fn upload_documents(pet_id: i64, parts: &[Part]) -> Request<Body> {
+    let mut body = Vec::new();
+    for (name, file, data) in parts {
+        body.extend_from_slice(format!("--{BOUNDARY}\r\n").as_bytes());
+        match file {
+            Some((file_name, content_type)) => body.extend_from_slice(
+                format!(
+                    "Content-Disposition: form-data; name=\"{name}\"; filename=\"{file_name}\"\r\n\
+                     Content-Type: {content_type}\r\n\r\n"
+                )
+                .as_bytes(),
+            ),
+            None => body.extend_from_slice(
+                format!("Content-Disposition: form-data; name=\"{name}\"\r\n\r\n").as_bytes(),
+            ),
+        }
+        body.extend_from_slice(data);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{BOUNDARY}--\r\n").as_bytes());
+
+    Request::post(format!("http://localhost/v1/pets/{}/documents", pet_id))
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        )
+        .body(Body::from(body))
+        .unwrap()
+}
+
+fn photo<'a>(file_name: &'a str, content_type: &'a str, len: usize) -> Part<'a> {
+    ("photos", Some((file_name, content_type)), vec![7; len])
+}
+
+#[test]
+fn form_bodies_decode_plus_as_space() {
+    let pet: models::Pet = style::from_form_body("id=1&name=Rex+the%20dog").unwrap();
+    assert_eq!(pet, models::Pet::new(1, "Rex the dog".to_string()));
+}
+
+#[tokio::test]
+async fn pets_are_created_from_forms() {
+    let dir = TempDir::new().unwrap();
+    let app = app(&dir);
+    let form = "application/x-www-form-urlencoded";
+
+    let response = app
+        .clone()
+        .oneshot(create_pet(form, "id=1&name=Rex+the+dog"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = app
+        .clone()
+        .oneshot(create_pet(
+            "application/x-www-form-urlencoded; charset=utf-8",
+            "id=2&name=Rex",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // not an int64
+    let response = app
+        .clone()
+        .oneshot(create_pet(form, "id=one&name=Rex"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // minLength: 1
+    let response = app
+        .clone()
+        .oneshot(create_pet(form, "id=1&name="))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .oneshot(create_pet("text/plain", "id=1&name=Rex"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[tokio::test]
+async fn documents_are_read_part_by_part() {
+    let dir = TempDir::new().unwrap();
+    let app = app(&dir);
+
+    let response = app
+        .oneshot(upload_documents(
+            1,
+            &[
+                ("description", None, b"Rex at the vet".to_vec()),
+                photo("front.png", "image/png", 16),
+                photo("side.jpg", "image/jpeg", 32),
+                (
+                    "certificate",
+                    Some(("rabies.pdf", "application/pdf")),
+                    vec![1; 8],
+                ),
+                // not in the schema
+                ("comment", None, b"ignored".to_vec()),
+            ],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let documents: Vec<models::Document> = serde_json::from_slice(&body).unwrap();
+    let described = documents
+        .iter()
+        .map(|document| {
+            (
+                document.part.as_str(),
+                document.file_name.as_deref(),
+                document.content_type.as_deref(),
+                document.size,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        described,
+        [
+            ("photos", Some("front.png"), Some("image/png"), 16),
+            ("photos", Some("side.jpg"), Some("image/jpeg"), 32),
+            (
+                "certificate",
+                Some("rabies.pdf"),
+                Some("application/pdf"),
+                8
+            ),
+        ]
+    );
+
+    // each file is stored under its own key
+    let store = BlobStore::new(dir.path());
+    for (key, len) in [("1-photos-0", 16), ("1-photos-1", 32), ("1-certificate", 8)] {
+        match store.get(key, None).await.unwrap() {
+            Some(Download::Full { len: stored, .. }) => assert_eq!(stored, len, "{}", key),
+            _ => panic!("{} was not stored", key),
+        }
+    }
+}
+
+#[tokio::test]
+async fn invalid_documents_are_rejected() {
+    let dir = TempDir::new().unwrap();
+    let app = app(&dir);
+
+    // minItems: 1
+    let response = app
+        .clone()
+        .oneshot(upload_documents(
+            2,
+            &[("description", None, b"no photos".to_vec())],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // maxItems: 5, checked before the sixth photo is read, which would be too large
+    let mut photos = vec![photo("photo.png", "image/png", 4); 5];
+    photos.push(photo("photo.png", "image/png", 2 * 1024 * 1024 + 1));
+    let response = app
+        .clone()
+        .oneshot(upload_documents(2, &photos))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[..], b"More than 5 parts photos");
+
+    // maxLength: 200
+    let response = app
+        .clone()
+        .oneshot(upload_documents(
+            2,
+            &[
+                ("description", None, vec![b'a'; 201]),
+                photo("photo.png", "image/png", 4),
+            ],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // encoding: image/jpeg, image/png
+    let response = app
+        .clone()
+        .oneshot(upload_documents(2, &[photo("photo.txt", "text/plain", 4)]))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    // maxLength: 2097152 per photo
+    let response = app
+        .oneshot(upload_documents(
+            2,
+            &[photo("photo.png", "image/png", 2 * 1024 * 1024 + 1)],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
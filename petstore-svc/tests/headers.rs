@@ -0,0 +1,89 @@
+//! Response headers: typed per response, and extra headers and cookies, of the stub of the tests
+//! and of the service.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use http::{
+    header::{LOCATION, SET_COOKIE},
+    Request, StatusCode,
+};
+use serde_json::json;
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+use petstore_api::{blob::BlobStore, models, server};
+
+use common::ServerState;
+
+#[test]
+fn response_headers_are_named_as_in_the_spec() {
+    let headers = models::ShowPetPhoto200ResponseHeaders {
+        accept_ranges: Some("bytes".to_string()),
+        ..models::ShowPetPhoto200ResponseHeaders::new(4)
+    };
+    assert_eq!(
+        serde_json::to_value(&headers).unwrap(),
+        json!({"Content-Length": 4, "Accept-Ranges": "bytes"})
+    );
+
+    let headers = models::ShowPetPhoto200ResponseHeaders::new(4);
+    assert_eq!(
+        serde_json::to_value(&headers).unwrap(),
+        json!({"Content-Length": 4})
+    );
+}
+
+#[tokio::test]
+async fn extra_headers_and_cookies_are_sent() {
+    let app = server::new(Arc::new(ServerState));
+
+    let response = app
+        .oneshot(
+            Request::post("http://localhost/v1/pets")
+                .header("content-type", "application/json")
+                .header("idempotency-key", "1")
+                .header("cookie", "session=abc")
+                .body(Body::from(r#"{"id":7,"name":"Rex"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()[LOCATION], "/v1/pets/7");
+
+    // only cookies changed by the operation are set
+    let cookies = response
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .map(|v| v.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(cookies, ["last_pet=7"]);
+}
+
+#[tokio::test]
+async fn created_pets_are_located_in_their_version() {
+    let dir = TempDir::new().unwrap();
+    let state = petstore_svc::ServerState::new(BlobStore::new(dir.path()));
+    let app = petstore_svc::builder(Arc::new(state)).build();
+
+    for (version, location) in [("v1", "/v1/pets/8"), ("v2", "/v2/pets/8")] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::post(format!("http://localhost/{}/pets", version))
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"id":8,"name":"Rex"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED, "{}", version);
+        assert_eq!(response.headers()[LOCATION], location);
+        // the service sets no cookies
+        assert!(response.headers().get(SET_COOKIE).is_none(), "{}", version);
+    }
+}
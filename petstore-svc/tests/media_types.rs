@@ -0,0 +1,283 @@
+//! XML, CBOR and plain text bodies, next to JSON, and requests in media types or of sizes the
+//! operations do not accept. Pets are shown and created by the service, errors and lists by the
+//! stub of the tests.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    Router,
+};
+use http::{
+    header::{CONTENT_TYPE, LOCATION},
+    Request, Response, StatusCode,
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+use petstore_api::{blob::BlobStore, models, server, types::ClientIdentity, xml};
+
+use common::ServerState;
+
+/// The service, storing documents and photos in `dir`.
+fn service(dir: &TempDir) -> Router {
+    server::new(Arc::new(petstore_svc::ServerState::new(BlobStore::new(
+        dir.path(),
+    ))))
+}
+
+fn show_pet(accept: &str) -> Request<Body> {
+    Request::get("http://localhost/v1/pets/1")
+        .header("accept", accept)
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// List pets as a client whose identity the stub lists as pet.
+fn list_pets(accept: &str) -> Request<Body> {
+    let mut request = Request::get("http://localhost/v1/pets")
+        .header("accept", accept)
+        .body(Body::empty())
+        .unwrap();
+    request.extensions_mut().insert(ClientIdentity {
+        subject: "Rex".to_string(),
+        alt_names: Vec::new(),
+        certificate: Vec::new(),
+    });
+    request
+}
+
+fn create_pet(content_type: &str, body: Vec<u8>) -> Request<Body> {
+    Request::post("http://localhost/v1/pets")
+        .header("content-type", content_type)
+        .header("idempotency-key", "1")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+async fn content(response: Response<Body>) -> (String, Vec<u8>) {
+    let content_type = response.headers()[CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (content_type, body.to_vec())
+}
+
+#[test]
+fn xml_follows_the_xml_objects_of_the_schema() {
+    let mut pet = models::Pet::new(1, "Rex & Fido".to_string());
+    pet.tag = Some(petstore_api::types::Nullable::Present("dog".to_string()));
+
+    let encoded = xml::to_vec(&pet).unwrap();
+    assert_eq!(
+        String::from_utf8(encoded.clone()).unwrap(),
+        r#"<pet id="1"><name>Rex &amp; Fido</name><tag>dog</tag></pet>"#
+    );
+    assert_eq!(xml::from_slice::<models::Pet>(&encoded).unwrap(), pet);
+
+    // declarations, prefixes and whitespace are ignored
+    let decoded = xml::from_slice::<models::Pet>(
+        br#"<?xml version="1.0"?>
+            <p:pet xmlns:p="urn:pets" id="2">
+              <p:name>Fido</p:name>
+            </p:pet>"#,
+    )
+    .unwrap();
+    assert_eq!(decoded, models::Pet::new(2, "Fido".to_string()));
+
+    assert!(xml::from_slice::<models::Pet>(br#"<pet id="two"><name>Fido</name></pet>"#).is_err());
+}
+
+#[tokio::test]
+async fn responses_are_encoded_as_accepted() {
+    let app = server::new(Arc::new(ServerState));
+    // the stub answers every pet with a 404 error
+    let error = models::Error::new(404, "not found".to_string());
+
+    let response = app
+        .clone()
+        .oneshot(show_pet("application/xml"))
+        .await
+        .unwrap();
+    assert_eq!(
+        content(response).await,
+        (
+            "application/xml".to_string(),
+            b"<error><code>404</code><message>not found</message></error>".to_vec()
+        )
+    );
+
+    let response = app
+        .clone()
+        .oneshot(show_pet("application/cbor"))
+        .await
+        .unwrap();
+    let (content_type, body) = content(response).await;
+    assert_eq!(content_type, "application/cbor");
+    assert_eq!(
+        ciborium::from_reader::<models::Error, _>(body.as_slice()).unwrap(),
+        error
+    );
+
+    let response = app.clone().oneshot(show_pet("text/plain")).await.unwrap();
+    assert_eq!(
+        content(response).await,
+        (
+            "text/plain".to_string(),
+            b"code,404,message,not%20found".to_vec()
+        )
+    );
+
+    let response = app.oneshot(show_pet("application/json")).await.unwrap();
+    let (content_type, body) = content(response).await;
+    assert_eq!(content_type, "application/json");
+    let body: models::Error = serde_json::from_slice(&body).unwrap();
+    assert_eq!((body.code, body.message), (error.code, error.message));
+}
+
+#[tokio::test]
+async fn arrays_are_wrapped_in_their_xml_name() {
+    let app = server::new(Arc::new(ServerState));
+    let pets = vec![models::Pet::new(1, "Rex".to_string())];
+
+    let response = app
+        .clone()
+        .oneshot(list_pets("application/xml"))
+        .await
+        .unwrap();
+    assert_eq!(
+        content(response).await,
+        (
+            "application/xml".to_string(),
+            br#"<pets><pet id="1"><name>Rex</name></pet></pets>"#.to_vec()
+        )
+    );
+
+    let response = app.oneshot(list_pets("application/cbor")).await.unwrap();
+    let (content_type, body) = content(response).await;
+    assert_eq!(content_type, "application/cbor");
+    assert_eq!(
+        ciborium::from_reader::<Vec<models::Pet>, _>(body.as_slice()).unwrap(),
+        pets
+    );
+}
+
+#[tokio::test]
+async fn pets_are_shown_as_accepted() {
+    let dir = TempDir::new().unwrap();
+    let app = service(&dir);
+
+    let response = app
+        .clone()
+        .oneshot(show_pet("application/xml"))
+        .await
+        .unwrap();
+    assert_eq!(
+        content(response).await,
+        (
+            "application/xml".to_string(),
+            br#"<pet id="1"><name>pet</name><attributes><color>brown</color></attributes></pet>"#
+                .to_vec()
+        )
+    );
+
+    let response = app.oneshot(show_pet("application/cbor")).await.unwrap();
+    let (content_type, body) = content(response).await;
+    assert_eq!(content_type, "application/cbor");
+    let pet: models::Pet = ciborium::from_reader(body.as_slice()).unwrap();
+    assert_eq!((pet.id, pet.name.as_str()), (1, "pet"));
+    assert_eq!(
+        serde_json::to_value(pet.attributes).unwrap(),
+        serde_json::json!({ "color": "brown" })
+    );
+}
+
+#[tokio::test]
+async fn pets_are_created_from_xml_and_cbor() {
+    let dir = TempDir::new().unwrap();
+    let app = service(&dir);
+
+    let response = app
+        .clone()
+        .oneshot(create_pet(
+            "application/xml",
+            br#"<pet id="1"><name>Rex</name></pet>"#.to_vec(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()[LOCATION], "/v1/pets/1");
+
+    let mut pet = Vec::new();
+    ciborium::into_writer(&models::Pet::new(2, "Fido".to_string()), &mut pet).unwrap();
+    let response = app
+        .clone()
+        .oneshot(create_pet("application/cbor", pet))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()[LOCATION], "/v1/pets/2");
+
+    // not well-formed
+    let response = app
+        .clone()
+        .oneshot(create_pet("application/xml", b"<pet id=\"1\">".to_vec()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // minLength: 1
+    let response = app
+        .oneshot(create_pet(
+            "application/xml",
+            br#"<pet id="1"><name></name></pet>"#.to_vec(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn unsupported_media_types_are_rejected() {
+    let app = server::new(Arc::new(ServerState));
+
+    let response = app.clone().oneshot(show_pet("image/png")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+
+    // JSON, but not as the operation's media type
+    let response = app
+        .oneshot(create_pet(
+            "text/json",
+            br#"{"id":1,"name":"Rex"}"#.to_vec(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[tokio::test]
+async fn bodies_over_the_operation_limit_are_rejected() {
+    let app = server::new(Arc::new(ServerState));
+    // x-body-limit: 16384
+    let padded = |len: usize| {
+        let mut body = br#"{"id":1,"name":"Rex"}"#.to_vec();
+        body.resize(len, b' ');
+        body
+    };
+
+    let response = app
+        .clone()
+        .oneshot(create_pet("application/json", padded(16 * 1024)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = app
+        .oneshot(create_pet("application/json", padded(16 * 1024 + 1)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
@@ -0,0 +1,79 @@
+//! Header, cookie and path parameters of the generated handlers.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use http::{Request, StatusCode};
+use tower::ServiceExt;
+
+use petstore_api::server;
+
+use common::ServerState;
+
+fn create_pet(idempotency_key: Option<&str>) -> Request<Body> {
+    let mut request =
+        Request::post("http://localhost/v1/pets").header("content-type", "application/json");
+    if let Some(idempotency_key) = idempotency_key {
+        request = request.header("idempotency-key", idempotency_key);
+    }
+    request
+        .body(Body::from(r#"{"id":1,"name":"Rex"}"#))
+        .unwrap()
+}
+
+fn list_pets(cookie: &str) -> Request<Body> {
+    Request::get("http://localhost/v1/pets")
+        .header("cookie", cookie)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn optional_headers_are_validated() {
+    let app = server::new(Arc::new(ServerState));
+
+    let response = app.clone().oneshot(create_pet(Some("1"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = app.clone().oneshot(create_pet(None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // maxLength: 64
+    let response = app
+        .oneshot(create_pet(Some(&"k".repeat(65))))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn cookies_are_validated() {
+    let app = server::new(Arc::new(ServerState));
+
+    let response = app.clone().oneshot(list_pets("session=abc")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // maxLength: 128
+    let cookie = format!("session={}", "s".repeat(129));
+    let response = app.oneshot(list_pets(&cookie)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn path_parameters_are_typed() {
+    let app = server::new(Arc::new(ServerState));
+    let show_pet = |id: &str| {
+        Request::get(format!("http://localhost/v1/pets/{}", id))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(show_pet("rex")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // minimum: 1
+    let response = app.oneshot(show_pet("0")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,204 @@
+//! Photo upload and download by the service, streamed through the blob store.
+
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    Router,
+};
+use http::{Request, Response, StatusCode};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+use petstore_api::{blob::BlobStore, server};
+use petstore_svc::ServerState;
+
+const BOUNDARY: &str = "photo-boundary";
+
+/// The service, storing photos in `dir`.
+fn app(dir: &TempDir) -> Router {
+    server::new(Arc::new(ServerState::new(BlobStore::new(dir.path()))))
+}
+
+fn photo() -> Vec<u8> {
+    (0..=255).collect()
+}
+
+fn upload_multipart(pet_id: i64, part: &str, content_type: &str, data: &[u8]) -> Request<Body> {
+    let mut body = format!(
+        "--{BOUNDARY}\r\n\
+         Content-Disposition: form-data; name=\"{part}\"; filename=\"photo\"\r\n\
+         Content-Type: {content_type}\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+
+    Request::post(format!("http://localhost/v1/pets/{}/photo", pet_id))
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        )
+        .body(Body::from(body))
+        .unwrap()
+}
+
+fn upload_raw(pet_id: i64, content_type: &str, data: Vec<u8>) -> Request<Body> {
+    Request::post(format!("http://localhost/v1/pets/{}/photo", pet_id))
+        .header("content-type", content_type)
+        .body(Body::from(data))
+        .unwrap()
+}
+
+fn download(pet_id: i64, range: Option<&str>) -> Request<Body> {
+    let mut request = Request::get(format!("http://localhost/v1/pets/{}/photo", pet_id));
+    if let Some(range) = range {
+        request = request.header("range", range);
+    }
+    request.body(Body::empty()).unwrap()
+}
+
+fn header<'a>(response: &'a Response<Body>, name: &str) -> Option<&'a str> {
+    response
+        .headers()
+        .get(name)
+        .map(|value| value.to_str().unwrap())
+}
+
+#[tokio::test]
+async fn multipart_uploads_are_downloaded_whole() {
+    let dir = TempDir::new().unwrap();
+    let app = app(&dir);
+
+    let response = app
+        .clone()
+        .oneshot(upload_multipart(1, "photo", "image/png", &photo()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = app.oneshot(download(1, None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header(&response, "content-type"), Some("image/png"));
+    assert_eq!(header(&response, "content-length"), Some("256"));
+    assert_eq!(header(&response, "accept-ranges"), Some("bytes"));
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body, photo());
+}
+
+#[tokio::test]
+async fn raw_uploads_are_downloaded_by_range() {
+    let dir = TempDir::new().unwrap();
+    let app = app(&dir);
+
+    let response = app
+        .clone()
+        .oneshot(upload_raw(2, "application/octet-stream", photo()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = app
+        .clone()
+        .oneshot(download(2, Some("bytes=16-31")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(
+        header(&response, "content-type"),
+        Some("application/octet-stream")
+    );
+    assert_eq!(header(&response, "content-length"), Some("16"));
+    assert_eq!(header(&response, "content-range"), Some("bytes 16-31/256"));
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body, photo()[16..32]);
+
+    // the last bytes
+    let response = app
+        .clone()
+        .oneshot(download(2, Some("bytes=-10")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(
+        header(&response, "content-range"),
+        Some("bytes 246-255/256")
+    );
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body, photo()[246..]);
+
+    let response = app
+        .clone()
+        .oneshot(download(2, Some("bytes=256-")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(header(&response, "content-range"), Some("bytes */256"));
+
+    // multiple ranges are answered with the whole photo
+    let response = app
+        .oneshot(download(2, Some("bytes=0-1,4-5")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body, photo());
+}
+
+#[tokio::test]
+async fn invalid_uploads_are_rejected() {
+    let dir = TempDir::new().unwrap();
+    let app = app(&dir);
+
+    let response = app
+        .clone()
+        .oneshot(upload_raw(3, "text/plain", photo()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let response = app
+        .clone()
+        .oneshot(upload_multipart(3, "picture", "image/png", &photo()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // x-body-limit: 10485760
+    let response = app
+        .clone()
+        .oneshot(upload_raw(3, "image/png", vec![0; 10 * 1024 * 1024 + 1]))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // nothing was stored
+    let response = app.oneshot(download(3, None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn photos_are_shared_by_the_versions() {
+    let dir = TempDir::new().unwrap();
+    let app = petstore_svc::builder(Arc::new(ServerState::new(BlobStore::new(dir.path())))).build();
+
+    let response = app
+        .clone()
+        .oneshot(upload_multipart(4, "photo", "image/jpeg", &photo()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = app
+        .oneshot(
+            Request::get("http://localhost/v2/pets/4/photo")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header(&response, "content-type"), Some("image/jpeg"));
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body, photo());
+}
@@ -0,0 +1,168 @@
+//! CORS, security headers and rate limiting of the router.
+
+mod common;
+
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use axum::{body::Body, extract::ConnectInfo};
+use http::{HeaderValue, Method, Request, StatusCode};
+use tower::ServiceExt;
+
+use petstore_api::{
+    security::{self, RateLimit, RateLimiter},
+    server,
+};
+
+use common::ServerState;
+
+const ORIGIN: &str = "https://petstore.example";
+
+fn create_pet(ip: [u8; 4]) -> Request<Body> {
+    let mut request = Request::post("http://localhost/v1/pets")
+        .header("content-type", "application/json")
+        .header("idempotency-key", "1")
+        .body(Body::from(r#"{"id":1,"name":"Rex"}"#))
+        .unwrap();
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(SocketAddr::from((ip, 1234))));
+    request
+}
+
+#[tokio::test]
+async fn preflight_requests_are_answered() {
+    let app = server::builder(Arc::new(ServerState))
+        .cors(security::cors(vec![HeaderValue::from_static(ORIGIN)]))
+        .build();
+
+    let response = app
+        .oneshot(
+            Request::options("http://localhost/v1/pets")
+                .header("origin", ORIGIN)
+                .header("access-control-request-method", "POST")
+                .header(
+                    "access-control-request-headers",
+                    "content-type,idempotency-key",
+                )
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert_eq!(headers["access-control-allow-origin"], ORIGIN);
+    assert_eq!(headers["access-control-allow-methods"], "GET,POST");
+    assert!(headers["access-control-allow-headers"]
+        .to_str()
+        .unwrap()
+        .contains("idempotency-key"));
+    assert_eq!(headers["x-content-type-options"], "nosniff");
+}
+
+#[tokio::test]
+async fn preflight_requests_allow_ranged_photo_downloads() {
+    let app = server::builder(Arc::new(ServerState))
+        .cors(security::cors(vec![HeaderValue::from_static(ORIGIN)]))
+        .build();
+
+    let response = app
+        .oneshot(
+            Request::options("http://localhost/v1/pets/1/photo")
+                .header("origin", ORIGIN)
+                .header("access-control-request-method", "GET")
+                .header("access-control-request-headers", "range")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["access-control-allow-headers"]
+        .to_str()
+        .unwrap()
+        .contains("range"));
+}
+
+#[tokio::test]
+async fn allowed_origins_are_read_from_the_environment() {
+    // the only test of the binary reading `CORS_ALLOWED_ORIGINS`
+    std::env::remove_var("CORS_ALLOWED_ORIGINS");
+    assert!(security::cors_from_env().unwrap().is_none());
+
+    std::env::set_var("CORS_ALLOWED_ORIGINS", "bad\norigin");
+    assert_eq!(
+        security::cors_from_env().unwrap_err(),
+        "Invalid CORS origin 'bad\norigin'"
+    );
+
+    std::env::set_var(
+        "CORS_ALLOWED_ORIGINS",
+        format!("http://localhost:5173, {}", ORIGIN),
+    );
+    let cors = security::cors_from_env().unwrap().unwrap();
+    let app = server::builder(Arc::new(ServerState)).cors(cors).build();
+
+    let response = app
+        .oneshot(
+            Request::options("http://localhost/v1/pets")
+                .header("origin", ORIGIN)
+                .header("access-control-request-method", "GET")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["access-control-allow-origin"], ORIGIN);
+}
+
+#[tokio::test]
+async fn rate_limits_are_enforced_per_client() {
+    let app = server::new(Arc::new(ServerState));
+
+    // createPets allows 10 requests per minute
+    for _ in 0..10 {
+        let response = app
+            .clone()
+            .oneshot(create_pet([10, 0, 0, 1]))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+    let response = app
+        .clone()
+        .oneshot(create_pet([10, 0, 0, 1]))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()["retry-after"], "6");
+
+    let response = app.oneshot(create_pet([10, 0, 0, 2])).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()["x-ratelimit-remaining"], "9");
+}
+
+#[tokio::test]
+async fn default_rate_limit_applies_to_other_operations() {
+    let app = server::builder(Arc::new(ServerState))
+        .rate_limiter(
+            RateLimiter::new().with_default_limit(RateLimit::new(1, Duration::from_secs(1))),
+        )
+        .build();
+    let list_pets = || {
+        Request::builder()
+            .method(Method::GET)
+            .uri("http://localhost/v1/pets")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(list_pets()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = app.oneshot(list_pets()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()["retry-after"], "1");
+}
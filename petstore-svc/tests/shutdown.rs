@@ -0,0 +1,60 @@
+//! Graceful shutdown of the server: no new connections, open requests answered.
+
+use std::{sync::Arc, time::Duration};
+
+use axum::{routing::get, Router};
+use http::StatusCode;
+use reqwest::Client;
+use tokio::{
+    net::TcpListener,
+    sync::{oneshot, Notify},
+};
+
+use petstore_api::serve;
+
+#[tokio::test]
+async fn open_requests_are_answered_before_shutting_down() {
+    // the handler tells when the request arrived, and answers a while later
+    let arrived = Arc::new(Notify::new());
+    let app = Router::new().route(
+        "/slow",
+        get({
+            let arrived = arrived.clone();
+            move || async move {
+                arrived.notify_one();
+                tokio::time::sleep(Duration::from_millis(300)).await;
+                "done"
+            }
+        }),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (shutdown, signal) = oneshot::channel::<()>();
+    let server = tokio::spawn(serve::serve_with_graceful_shutdown(
+        listener,
+        app,
+        None,
+        async move {
+            signal.await.ok();
+        },
+    ));
+
+    let request = tokio::spawn(Client::new().get(format!("http://{}/slow", addr)).send());
+    arrived.notified().await;
+    shutdown.send(()).unwrap();
+
+    let response = request.await.unwrap().unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().await.unwrap(), "done");
+
+    tokio::time::timeout(Duration::from_secs(5), server)
+        .await
+        .expect("the server shuts down once the request is answered")
+        .unwrap()
+        .unwrap();
+    assert!(Client::new()
+        .get(format!("http://{}/slow", addr))
+        .send()
+        .await
+        .is_err());
+}
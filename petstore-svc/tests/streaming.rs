@@ -0,0 +1,215 @@
+//! Array responses streamed as a JSON array or as NDJSON, and compressed as negotiated.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{body::Body, extract::*};
+use axum_extra::extract::CookieJar;
+use futures::{stream, StreamExt};
+use http::{
+    header::{ACCEPT, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE},
+    Method, Request, StatusCode,
+};
+use tower::ServiceExt;
+use tower_http::decompression::Decompression;
+
+use petstore_api::*;
+
+/// API streaming its pets, and ending the stream after them unless `endless`.
+struct StreamingState {
+    endless: bool,
+}
+
+#[allow(unused_variables)]
+#[async_trait]
+impl Api for StreamingState {
+    async fn create_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: models::CreatePetsHeaderParams,
+        body: models::Pet,
+    ) -> Result<CreatePetsResponse, String> {
+        Err("not streamed".to_string())
+    }
+
+    async fn list_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        cookie_params: models::ListPetsCookieParams,
+        query_params: models::ListPetsQueryParams,
+    ) -> Result<ListPetsResponse, String> {
+        let pets = stream::iter(pets().into_iter().map(Ok));
+        let body = if self.endless {
+            types::ArrayBody::stream(pets.chain(stream::pending()))
+        } else {
+            types::ArrayBody::stream(pets)
+        };
+        Ok(ListPetsResponse::Status200_APagedArrayOfPets {
+            body,
+            headers: models::ListPets200ResponseHeaders::new(),
+        })
+    }
+
+    async fn show_pet_by_id(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::ShowPetByIdPathParams,
+    ) -> Result<ShowPetByIdResponse, String> {
+        Err("not streamed".to_string())
+    }
+
+    async fn show_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: models::ShowPetPhotoHeaderParams,
+        path_params: models::ShowPetPhotoPathParams,
+    ) -> Result<ShowPetPhotoResponse, String> {
+        Err("not streamed".to_string())
+    }
+
+    async fn upload_pet_documents(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::UploadPetDocumentsPathParams,
+        body: models::UploadPetDocumentsForm,
+    ) -> Result<UploadPetDocumentsResponse, String> {
+        Err("not streamed".to_string())
+    }
+
+    async fn upload_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::UploadPetPhotoPathParams,
+        body: types::FileUpload,
+    ) -> Result<UploadPetPhotoResponse, String> {
+        Err("not streamed".to_string())
+    }
+}
+
+fn pets() -> Vec<models::Pet> {
+    vec![
+        models::Pet::new(1, "Rex".to_string()),
+        models::Pet::new(2, "Tom".to_string()),
+    ]
+}
+
+fn list_pets(accept: &str) -> Request<Body> {
+    Request::get("http://localhost/v1/pets")
+        .header(ACCEPT, accept)
+        .body(Body::empty())
+        .unwrap()
+}
+
+async fn text(body: Body) -> String {
+    let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn streams_are_sent_as_json_arrays() {
+    let app = server::new(Arc::new(StreamingState { endless: false }));
+
+    let response = app.oneshot(list_pets("application/json")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+    let body = text(response.into_body()).await;
+    assert_eq!(
+        serde_json::from_str::<Vec<models::Pet>>(&body).unwrap(),
+        pets()
+    );
+}
+
+#[tokio::test]
+async fn streams_are_sent_as_ndjson() {
+    let app = server::new(Arc::new(StreamingState { endless: false }));
+
+    let response = app
+        .oneshot(list_pets("application/x-ndjson"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/x-ndjson");
+    assert_eq!(
+        text(response.into_body()).await,
+        "{\"id\":1,\"name\":\"Rex\"}\n{\"id\":2,\"name\":\"Tom\"}\n"
+    );
+}
+
+#[tokio::test]
+async fn streams_are_sent_before_they_end() {
+    // the contract checks of debug builds must not wait for the end of the stream either
+    let app = server::new(Arc::new(StreamingState { endless: true }));
+
+    for accept in ["application/json", "application/x-ndjson"] {
+        let response = app.clone().oneshot(list_pets(accept)).await.unwrap();
+        let mut body = response.into_body().into_data_stream();
+        let first = tokio::time::timeout(std::time::Duration::from_secs(5), body.next())
+            .await
+            .expect("the first chunk is sent without waiting for the others")
+            .unwrap()
+            .unwrap();
+        assert!(!first.is_empty(), "{}", accept);
+    }
+}
+
+#[tokio::test]
+async fn responses_are_compressed_as_negotiated() {
+    let app = server::new(Arc::new(StreamingState { endless: false }));
+
+    for encoding in ["gzip", "br", "zstd"] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::get("http://localhost/v1/pets")
+                    .header(ACCEPT_ENCODING, encoding)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[CONTENT_ENCODING], encoding);
+
+        // the client decompresses the encoding it asked for only
+        let client = Decompression::new(app.clone())
+            .gzip(encoding == "gzip")
+            .br(encoding == "br")
+            .zstd(encoding == "zstd")
+            .no_deflate();
+        let response = client
+            .oneshot(
+                Request::get("http://localhost/v1/pets")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(Body::new(response.into_body()), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Vec<models::Pet>>(&body).unwrap(),
+            pets(),
+            "{}",
+            encoding
+        );
+    }
+}
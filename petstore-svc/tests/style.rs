@@ -0,0 +1,131 @@
+//! Parameter values in the `style=simple` and `style=form` representations, for arrays, which
+//! `style=form` explodes into pairs, and empty strings.
+
+use serde::{Deserialize, Serialize};
+
+use petstore_api::{
+    models,
+    style::{self, Style},
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Tagged {
+    tags: Vec<String>,
+}
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+#[test]
+fn exploded_form_arrays_repeat_the_parameter() {
+    let tags = strings(&["dog", "good, old"]);
+    let query = style::to_query("tags", &tags, true).unwrap();
+    assert_eq!(query, "tags=dog&tags=good%2C%20old");
+
+    let query = format!("limit=10&{}&offset=2", query);
+    assert_eq!(
+        style::from_query::<Vec<String>>("tags", &query, true).unwrap(),
+        tags
+    );
+    assert_eq!(
+        style::from_query::<Option<Vec<String>>>("names", &query, true).unwrap(),
+        None
+    );
+    assert_eq!(style::from_query::<i32>("limit", &query, true).unwrap(), 10);
+
+    // no pair for an empty array, an empty value for an empty string
+    assert_eq!(style::to_query("tags", &strings(&[]), true).unwrap(), "");
+    assert_eq!(
+        style::to_query("tags", &strings(&[""]), true).unwrap(),
+        "tags="
+    );
+    assert_eq!(
+        style::from_query::<Vec<String>>("tags", "limit=10", true).unwrap(),
+        strings(&[])
+    );
+    assert_eq!(
+        style::from_query::<Vec<String>>("tags", "tags=", true).unwrap(),
+        strings(&[""])
+    );
+}
+
+#[test]
+fn exploded_form_arrays_need_the_parameter() {
+    let error = style::to_string(&strings(&["a", "b"]), Style::Form, true).unwrap_err();
+    assert!(error.to_string().contains("style::to_query"), "{}", error);
+    let error = style::from_str::<Vec<String>>("a&b", Style::Form, true).unwrap_err();
+    assert!(error.to_string().contains("style::to_query"), "{}", error);
+}
+
+#[test]
+fn form_parameters_round_trip() {
+    let tags = strings(&["dog", "cat"]);
+    let query = style::to_query("tags", &tags, false).unwrap();
+    assert_eq!(query, "tags=dog,cat");
+    assert_eq!(
+        style::from_query::<Vec<String>>("tags", &query, false).unwrap(),
+        tags
+    );
+
+    // the properties of exploded objects are the pairs of the query
+    let pet = models::Pet {
+        id: 1,
+        name: "Rex".to_string(),
+        tag: None,
+        attributes: None,
+    };
+    let query = style::to_query("pet", &pet, true).unwrap();
+    assert_eq!(query, "id=1&name=Rex");
+    assert_eq!(
+        style::from_query::<models::Pet>("pet", &query, true).unwrap(),
+        pet
+    );
+}
+
+#[test]
+fn empty_strings_in_arrays_are_kept() {
+    for (tags, encoded) in [
+        (strings(&[]), ""),
+        (strings(&[""]), r#""""#),
+        (strings(&["", ""]), r#""","""#),
+        (strings(&["a", ""]), r#"a,"""#),
+        // a string of two quotes is percent-encoded as any other
+        (strings(&[r#""""#]), "%22%22"),
+    ] {
+        let simple = style::to_string(&tags, Style::Simple, false).unwrap();
+        assert_eq!(simple, encoded);
+        assert_eq!(
+            style::from_str::<Vec<String>>(&simple, Style::Simple, false).unwrap(),
+            tags
+        );
+
+        let query = style::to_query("tags", &tags, false).unwrap();
+        assert_eq!(
+            style::from_query::<Vec<String>>("tags", &query, false).unwrap(),
+            tags
+        );
+    }
+
+    // and in nested arrays
+    for tags in [strings(&[]), strings(&[""])] {
+        let tagged = Tagged { tags };
+        let simple = style::to_string(&tagged, Style::Simple, false).unwrap();
+        assert_eq!(
+            style::from_str::<Tagged>(&simple, Style::Simple, false).unwrap(),
+            tagged
+        );
+    }
+
+    // empty strings outside of arrays are left empty
+    let pet = models::Pet {
+        id: 1,
+        name: String::new(),
+        tag: None,
+        attributes: None,
+    };
+    assert_eq!(
+        style::to_string(&pet, Style::Form, true).unwrap(),
+        "id=1&name="
+    );
+}
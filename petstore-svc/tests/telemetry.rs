@@ -0,0 +1,203 @@
+//! Spans are exported over OTLP to a collector stand-in, metrics are served at `/metrics`, and
+//! request ids are propagated.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use axum::body::Body;
+use http::{Request, StatusCode};
+use opentelemetry::{global, trace::TracerProvider as _};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_proto::tonic::{
+    collector::trace::v1::{
+        trace_service_server::{TraceService, TraceServiceServer},
+        ExportTraceServiceRequest, ExportTraceServiceResponse,
+    },
+    common::v1::any_value::Value,
+    trace::v1::{span::SpanKind, Span},
+};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use tower::ServiceExt;
+use tracing_subscriber::layer::SubscriberExt;
+
+use petstore_api::*;
+
+use common::ServerState;
+
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+/// OTLP collector stand-in keeping the exported spans.
+#[derive(Clone, Default)]
+struct Collector {
+    spans: Arc<Mutex<Vec<Span>>>,
+}
+
+#[tonic::async_trait]
+impl TraceService for Collector {
+    async fn export(
+        &self,
+        request: tonic::Request<ExportTraceServiceRequest>,
+    ) -> Result<tonic::Response<ExportTraceServiceResponse>, tonic::Status> {
+        let spans = request
+            .into_inner()
+            .resource_spans
+            .into_iter()
+            .flat_map(|r| r.scope_spans)
+            .flat_map(|s| s.spans);
+        self.spans.lock().unwrap().extend(spans);
+        Ok(tonic::Response::new(ExportTraceServiceResponse::default()))
+    }
+}
+
+fn attribute<'a>(span: &'a Span, key: &str) -> Option<&'a Value> {
+    span.attributes
+        .iter()
+        .find(|kv| kv.key == key)
+        .and_then(|kv| kv.value.as_ref())
+        .and_then(|v| v.value.as_ref())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn operations_are_traced_and_measured() {
+    let collector = Collector::default();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(TraceServiceServer::new(collector.clone()))
+            .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+    );
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()
+        .unwrap();
+    let provider = telemetry::tracer_provider("petstore-svc", exporter);
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    let _subscriber = tracing::subscriber::set_default(
+        tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("petstore-svc"))),
+    );
+
+    let app = server::new(Arc::new(ServerState));
+    // the metrics recorder is global to the test binary, so counts are compared to those before
+    let before = scrape(&app).await;
+    let response = app
+        .clone()
+        .oneshot(
+            Request::get("http://localhost/v1/pets")
+                .header("traceparent", TRACEPARENT)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    for result in provider.force_flush() {
+        result.unwrap();
+    }
+    let spans = collector.spans.lock().unwrap().clone();
+    let span = spans
+        .iter()
+        .find(|span| {
+            attribute(span, "openapi.operation_id") == Some(&Value::StringValue("listPets".into()))
+        })
+        .expect("listPets span is exported");
+    assert_eq!(span.name, "GET /v1/pets");
+    assert_eq!(span.kind, SpanKind::Server as i32);
+    assert_eq!(hex(&span.trace_id), "4bf92f3577b34da6a3ce929d0e0e4736");
+    assert_eq!(hex(&span.parent_span_id), "00f067aa0ba902b7");
+    assert_eq!(
+        attribute(span, "http.request.method"),
+        Some(&Value::StringValue("GET".into()))
+    );
+    assert_eq!(
+        attribute(span, "http.route"),
+        Some(&Value::StringValue("/v1/pets".into()))
+    );
+    assert_eq!(
+        attribute(span, "http.response.status_code"),
+        Some(&Value::IntValue(200))
+    );
+
+    let after = scrape(&app).await;
+    for series in [
+        r#"http_server_requests_total{operation="listPets",method="GET",status="200"}"#,
+        r#"http_server_request_duration_seconds_count{operation="listPets",method="GET",status="200"}"#,
+    ] {
+        assert_eq!(
+            count(&after, series),
+            count(&before, series) + 1,
+            "{}",
+            series
+        );
+    }
+}
+
+#[tokio::test]
+async fn request_ids_are_propagated() {
+    let app = server::new(Arc::new(ServerState));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::get("http://localhost/v1/pets/1")
+                .header("x-request-id", "req-1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(response.headers()["x-request-id"], "req-1");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let error: models::Error = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error.request_id.as_deref(), Some("req-1"));
+
+    // not listPets, whose requests `operations_are_traced_and_measured` counts
+    let response = app
+        .oneshot(
+            Request::post("http://localhost/v1/pets")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"id":1,"name":"Rex"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert!(!response.headers()["x-request-id"].is_empty());
+}
+
+/// The metrics served at `/metrics`.
+async fn scrape(app: &axum::Router) -> String {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::get("http://localhost/metrics")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+/// The value of the counter `series` in `metrics`, 0 if it wasn't recorded yet.
+fn count(metrics: &str, series: &str) -> u64 {
+    metrics
+        .lines()
+        .find_map(|line| line.strip_prefix(series)?.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
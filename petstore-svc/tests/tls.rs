@@ -0,0 +1,216 @@
+//! TLS, client certificates and HTTP/2 of the server.
+
+mod common;
+
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use http::{StatusCode, Version};
+use rcgen::{BasicConstraints, Certificate, CertificateParams, DnType, IsCa, KeyPair};
+use reqwest::{tls::TlsInfo, Client};
+use tempfile::TempDir;
+use tokio::{io::AsyncReadExt, net::TcpListener};
+
+use petstore_api::{
+    serve::{self, TlsConfig},
+    server,
+};
+
+use common::ServerState;
+
+struct Ca {
+    cert: Certificate,
+    key: KeyPair,
+}
+
+impl Ca {
+    fn new() -> Self {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::default();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "Test CA");
+        let cert = params.self_signed(&key).unwrap();
+        Ca { cert, key }
+    }
+
+    /// Certificate for `common_name` and `alt_names`, with its private key, in PEM.
+    fn issue(&self, common_name: &str, alt_names: &[&str]) -> (String, String) {
+        let key = KeyPair::generate().unwrap();
+        let alt_names: Vec<String> = alt_names.iter().map(|name| name.to_string()).collect();
+        let mut params = CertificateParams::new(alt_names).unwrap();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, common_name);
+        let cert = params.signed_by(&key, &self.cert, &self.key).unwrap();
+        (cert.pem(), key.serialize_pem())
+    }
+}
+
+/// Directory with the PEM files of the test, removed when dropped.
+fn files(test: &str) -> TempDir {
+    tempfile::Builder::new()
+        .prefix(&format!("petstore-tls-{}-", test))
+        .tempdir()
+        .unwrap()
+}
+
+async fn start(tls: Option<TlsConfig>) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = server::new(Arc::new(ServerState));
+    tokio::spawn(serve::serve(listener, app, tls));
+    addr
+}
+
+fn client(ca: &Ca, addr: SocketAddr) -> reqwest::ClientBuilder {
+    Client::builder()
+        .use_rustls_tls()
+        .add_root_certificate(reqwest::Certificate::from_pem(ca.cert.pem().as_bytes()).unwrap())
+        .resolve("localhost", addr)
+        .tls_info(true)
+}
+
+fn pets_url(addr: SocketAddr) -> String {
+    format!("https://localhost:{}/v1/pets", addr.port())
+}
+
+#[tokio::test]
+async fn tls_negotiates_http2() {
+    let ca = Ca::new();
+    let dir = files("http2");
+    let (cert, key) = ca.issue("localhost", &["localhost"]);
+    std::fs::write(dir.path().join("cert.pem"), cert).unwrap();
+    std::fs::write(dir.path().join("key.pem"), key).unwrap();
+    let addr = start(Some(TlsConfig::new(
+        dir.path().join("cert.pem"),
+        dir.path().join("key.pem"),
+    )))
+    .await;
+
+    let response = client(&ca, addr)
+        .build()
+        .unwrap()
+        .get(pets_url(addr))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.version(), Version::HTTP_2);
+}
+
+#[tokio::test]
+async fn plain_connections_speak_http1_and_h2c() {
+    let addr = start(None).await;
+    let url = format!("http://{}/v1/pets", addr);
+
+    let response = Client::new().get(&url).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.version(), Version::HTTP_11);
+
+    let h2c = Client::builder().http2_prior_knowledge().build().unwrap();
+    let response = h2c.get(&url).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.version(), Version::HTTP_2);
+}
+
+#[tokio::test]
+async fn client_certificates_identify_clients() {
+    let ca = Ca::new();
+    let dir = files("mtls");
+    let (cert, key) = ca.issue("localhost", &["localhost"]);
+    std::fs::write(dir.path().join("cert.pem"), cert).unwrap();
+    std::fs::write(dir.path().join("key.pem"), key).unwrap();
+    std::fs::write(dir.path().join("ca.pem"), ca.cert.pem()).unwrap();
+    let addr = start(Some(
+        TlsConfig::new(dir.path().join("cert.pem"), dir.path().join("key.pem"))
+            .with_client_ca(dir.path().join("ca.pem")),
+    ))
+    .await;
+
+    let (cert, key) = ca.issue("client", &["client.example"]);
+    let identity = reqwest::Identity::from_pem(format!("{}{}", cert, key).as_bytes()).unwrap();
+    let pets: serde_json::Value = client(&ca, addr)
+        .identity(identity)
+        .build()
+        .unwrap()
+        .get(pets_url(addr))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(pets[0]["name"], "CN=client");
+
+    let anonymous = client(&ca, addr)
+        .build()
+        .unwrap()
+        .get(pets_url(addr))
+        .send()
+        .await;
+    assert!(anonymous.is_err());
+}
+
+#[tokio::test]
+async fn certificates_are_reloaded() {
+    let ca = Ca::new();
+    let dir = files("reload");
+    let (cert, key) = ca.issue("localhost", &["localhost"]);
+    std::fs::write(dir.path().join("cert.pem"), &cert).unwrap();
+    std::fs::write(dir.path().join("key.pem"), key).unwrap();
+    let addr = start(Some(
+        TlsConfig::new(dir.path().join("cert.pem"), dir.path().join("key.pem"))
+            .with_reload_interval(Duration::from_millis(50)),
+    ))
+    .await;
+
+    let peer_certificate = || async {
+        // a new client per request, so that every request opens a new connection
+        let response = client(&ca, addr)
+            .build()
+            .unwrap()
+            .get(pets_url(addr))
+            .send()
+            .await
+            .unwrap();
+        let tls_info = response.extensions().get::<TlsInfo>().unwrap();
+        tls_info.peer_certificate().unwrap().to_vec()
+    };
+    let served = peer_certificate().await;
+
+    let (renewed, key) = ca.issue("localhost", &["localhost"]);
+    std::fs::write(dir.path().join("cert.pem"), &renewed).unwrap();
+    std::fs::write(dir.path().join("key.pem"), key).unwrap();
+
+    for _ in 0..100 {
+        if peer_certificate().await != served {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("The renewed certificate is not served");
+}
+
+#[tokio::test]
+async fn stalled_handshakes_are_closed() {
+    let ca = Ca::new();
+    let dir = files("handshake");
+    let (cert, key) = ca.issue("localhost", &["localhost"]);
+    std::fs::write(dir.path().join("cert.pem"), cert).unwrap();
+    std::fs::write(dir.path().join("key.pem"), key).unwrap();
+    let addr = start(Some(
+        TlsConfig::new(dir.path().join("cert.pem"), dir.path().join("key.pem"))
+            .with_handshake_timeout(Duration::from_millis(100)),
+    ))
+    .await;
+
+    // connects, but never sends a ClientHello
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let mut buf = [0; 1];
+    let read = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf))
+        .await
+        .expect("the connection is closed");
+    assert_eq!(read.unwrap(), 0);
+}
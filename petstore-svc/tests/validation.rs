@@ -0,0 +1,305 @@
+//! Schema constraints of requests, and of responses in debug builds.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{body::Body, extract::*};
+use axum_extra::extract::CookieJar;
+use http::{Method, Request, StatusCode};
+use tower::ServiceExt;
+
+use petstore_api::*;
+
+/// API answering with responses which break the constraints of the OpenAPI document.
+struct InvalidState;
+
+#[allow(unused_variables)]
+#[async_trait]
+impl Api for InvalidState {
+    async fn create_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: models::CreatePetsHeaderParams,
+        body: models::Pet,
+    ) -> Result<CreatePetsResponse, String> {
+        Ok(CreatePetsResponse::Status201_NullResponse {
+            headers: models::CreatePets201ResponseHeaders::new(),
+        })
+    }
+
+    async fn list_pets(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        cookie_params: models::ListPetsCookieParams,
+        query_params: models::ListPetsQueryParams,
+    ) -> Result<ListPetsResponse, String> {
+        // maxItems: 100
+        let pets = (1..=101)
+            .map(|id| models::Pet::new(id, "pet".to_string()))
+            .collect::<Vec<_>>();
+        Ok(ListPetsResponse::Status200_APagedArrayOfPets {
+            body: pets.into(),
+            headers: models::ListPets200ResponseHeaders::new(),
+        })
+    }
+
+    async fn show_pet_by_id(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::ShowPetByIdPathParams,
+    ) -> Result<ShowPetByIdResponse, String> {
+        // minLength: 1
+        Ok(
+            ShowPetByIdResponse::Status200_ExpectedResponseToAValidRequest {
+                body: models::Pet::new(path_params.pet_id, String::new()),
+                headers: models::ShowPetById200ResponseHeaders::new(),
+            },
+        )
+    }
+
+    async fn show_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        header_params: models::ShowPetPhotoHeaderParams,
+        path_params: models::ShowPetPhotoPathParams,
+    ) -> Result<ShowPetPhotoResponse, String> {
+        Ok(ShowPetPhotoResponse::Status200_ThePhoto {
+            body: vec![0; 4].into(),
+            headers: models::ShowPetPhoto200ResponseHeaders::new(4),
+        })
+    }
+
+    async fn upload_pet_documents(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::UploadPetDocumentsPathParams,
+        body: models::UploadPetDocumentsForm,
+    ) -> Result<UploadPetDocumentsResponse, String> {
+        Ok(UploadPetDocumentsResponse::Status201_DocumentsStored {
+            body: Vec::new().into(),
+            headers: models::UploadPetDocuments201ResponseHeaders::new(),
+        })
+    }
+
+    async fn upload_pet_photo(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        identity: Option<types::ClientIdentity>,
+        path_params: models::UploadPetPhotoPathParams,
+        body: types::FileUpload,
+    ) -> Result<UploadPetPhotoResponse, String> {
+        Ok(UploadPetPhotoResponse::Status204_PhotoStored {
+            headers: models::UploadPetPhoto204ResponseHeaders::new(),
+        })
+    }
+}
+
+fn get(uri: &str) -> Request<Body> {
+    Request::get(uri).body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn request_bodies_are_validated() {
+    let app = server::new(Arc::new(InvalidState));
+    let create_pet = |body: &str| {
+        Request::post("http://localhost/v1/pets")
+            .header("content-type", "application/json")
+            .header("idempotency-key", "1")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(create_pet(r#"{"id":1,"name":"Rex"}"#))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = app
+        .clone()
+        .oneshot(create_pet(r#"{"id":1,"name":""}"#))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // nullable: true
+    let response = app
+        .clone()
+        .oneshot(create_pet(r#"{"id":1,"name":"Rex","tag":null}"#))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // maxLength: 50
+    let body = format!(r#"{{"id":1,"name":"Rex","tag":"{}"}}"#, "t".repeat(51));
+    let response = app.oneshot(create_pet(&body)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn null_is_distinct_from_absent() {
+    let pet: models::Pet = serde_json::from_str(r#"{"id":1,"name":"Rex","tag":null}"#).unwrap();
+    assert_eq!(pet.tag, Some(types::Nullable::Null));
+    assert_eq!(
+        serde_json::to_string(&pet).unwrap(),
+        r#"{"id":1,"name":"Rex","tag":null}"#
+    );
+
+    let pet: models::Pet = serde_json::from_str(r#"{"id":1,"name":"Rex"}"#).unwrap();
+    assert_eq!(pet.tag, None);
+    assert_eq!(
+        serde_json::to_string(&pet).unwrap(),
+        r#"{"id":1,"name":"Rex"}"#
+    );
+}
+
+#[tokio::test]
+async fn invalid_responses_fail_in_debug_builds() {
+    let app = server::new(Arc::new(InvalidState));
+
+    let response = app
+        .clone()
+        .oneshot(get("http://localhost/v1/pets"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+    let response = app
+        .oneshot(get("http://localhost/v1/pets/1"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[test]
+fn composed_models_are_validated() {
+    use validator::Validate;
+
+    let variant: models::PetVariant =
+        serde_json::from_str(r#"{"petType":"dog","id":1,"name":"Rex","bark":true}"#).unwrap();
+    assert!(matches!(variant, models::PetVariant::Dog(_)));
+    assert!(serde_json::from_str::<models::PetVariant>(
+        r#"{"petType":"fish","id":1,"name":"Nemo"}"#
+    )
+    .is_err());
+
+    let tagged: models::TaggedPet =
+        serde_json::from_str(r#"{"kind":"cat","pet":{"id":2,"name":"Tom","hunts":true}}"#).unwrap();
+    assert!(matches!(&tagged, models::TaggedPet::Cat(cat) if cat.hunts == Some(true)));
+    assert_eq!(
+        serde_json::to_value(&tagged).unwrap(),
+        serde_json::json!({ "kind": "cat", "pet": { "id": 2, "name": "Tom", "hunts": true } })
+    );
+    assert_eq!(tagged.to_string().parse::<models::TaggedPet>(), Ok(tagged));
+    // the pet is not next to its kind
+    assert!(
+        serde_json::from_str::<models::TaggedPet>(r#"{"kind":"cat","id":2,"name":"Tom"}"#).is_err()
+    );
+
+    let companion: models::Companion =
+        serde_json::from_str(r#"{"id":1,"name":"Tom","hunts":true}"#).unwrap();
+    assert!(companion.validate().is_ok());
+    // minLength: 1, for both Dog and Cat
+    let companion: models::Companion = serde_json::from_str(r#"{"id":1,"name":""}"#).unwrap();
+    assert!(companion.validate().is_err());
+
+    // a dog and a cat at once: read as either, with nothing lost in either representation
+    let companion: models::Companion =
+        serde_json::from_str(r#"{"id":1,"name":"Tom","bark":false,"hunts":true}"#).unwrap();
+    assert_eq!(companion.dog().unwrap().bark, Some(false));
+    assert_eq!(companion.cat().unwrap().hunts, Some(true));
+    assert_eq!(
+        companion,
+        serde_json::from_str(r#"{"hunts":true, "bark":false, "name":"Tom", "id":1}"#).unwrap()
+    );
+    assert_eq!(
+        companion.to_string().parse::<models::Companion>(),
+        Ok(companion)
+    );
+}
+
+#[test]
+fn additional_properties_are_kept_and_validated() {
+    use validator::Validate;
+
+    let json = r#"{"name":"Ann","pets":{"Rex":{"id":1,"name":"Rex","attributes":{"size":{"height":40}}}},"phone":"555-0100"}"#;
+    let owner: models::Owner = serde_json::from_str(json).unwrap();
+    assert!(owner.validate().is_ok());
+    assert_eq!(
+        owner.additional_properties["phone"].as_value(),
+        &serde_json::json!("555-0100")
+    );
+    let attributes = owner.pets.as_ref().unwrap()["Rex"]
+        .attributes
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        attributes.pointer_as::<i32>("/size/height").unwrap(),
+        Some(40)
+    );
+    assert_eq!(
+        serde_json::to_value(&owner).unwrap(),
+        serde_json::from_str::<serde_json::Value>(json).unwrap()
+    );
+
+    // minLength: 1, of a pet in the map
+    let owner: models::Owner =
+        serde_json::from_str(r#"{"name":"Ann","pets":{"Rex":{"id":1,"name":""}}}"#).unwrap();
+    assert!(owner.validate().is_err());
+}
+
+#[test]
+fn free_form_values_are_rejected_in_parameters() {
+    let mut pet = models::Pet {
+        id: 1,
+        name: "Rex".to_string(),
+        tag: None,
+        attributes: None,
+    };
+    assert_eq!(pet.to_string().parse::<models::Pet>().unwrap(), pet);
+
+    // the attributes would otherwise be read as the string "color,brown"
+    pet.attributes = Some(serde_json::json!({ "color": "brown" }).into());
+    let error = pet.to_string().parse::<models::Pet>().unwrap_err();
+    assert!(error.contains("no parameter representation"), "{}", error);
+
+    let mut owner = models::Owner {
+        name: "Ann".to_string(),
+        pets: Some(
+            [(
+                "Rex".to_string(),
+                models::Pet {
+                    attributes: None,
+                    ..pet
+                },
+            )]
+            .into(),
+        ),
+        additional_properties: Default::default(),
+    };
+    assert_eq!(owner.to_string().parse::<models::Owner>().unwrap(), owner);
+
+    owner
+        .additional_properties
+        .insert("phone".to_string(), serde_json::json!("555-0100").into());
+    let error = owner.to_string().parse::<models::Owner>().unwrap_err();
+    assert!(error.contains("no parameter representation"), "{}", error);
+}
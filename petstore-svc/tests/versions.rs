@@ -0,0 +1,207 @@
+//! Versions 1 and 2 of the API served side by side.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::body::{self, Body};
+use chrono::{TimeZone, Utc};
+use http::{
+    header::{LINK, LOCATION},
+    Request, StatusCode,
+};
+use tower::ServiceExt;
+
+use petstore_api::{
+    cache::ResponseCache,
+    deprecation::{Deprecation, DEPRECATION, SUNSET},
+    models,
+    security::RateLimiter,
+    server,
+    types::Nullable,
+    v2,
+};
+
+use common::ServerState;
+
+fn app() -> axum::Router {
+    app_caching_in(ResponseCache::default())
+}
+
+/// Both versions, caching their responses in `cache` and sharing a rate limiter.
+fn app_caching_in(cache: ResponseCache) -> axum::Router {
+    let state = Arc::new(ServerState);
+    let rate_limiter = RateLimiter::default();
+    server::builder(state.clone())
+        .cache(cache.clone())
+        .rate_limiter(rate_limiter.clone())
+        .deprecation(
+            Deprecation::new(Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap())
+                .sunset(Utc.with_ymd_and_hms(2027, 6, 1, 0, 0, 0).unwrap())
+                .link("https://example.com/v2"),
+        )
+        .version(
+            v2::server::builder(state)
+                .cache(cache)
+                .rate_limiter(rate_limiter)
+                .routes(),
+        )
+        .build()
+}
+
+fn get(uri: &str) -> Request<Body> {
+    Request::get(uri)
+        .header("accept", "application/json")
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn create_pet(uri: &str, pet: &str) -> Request<Body> {
+    Request::post(uri)
+        .header("content-type", "application/json")
+        .header("idempotency-key", "1")
+        .body(Body::from(pet.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn deprecated_version_announces_its_sunset() {
+    let response = app()
+        .oneshot(create_pet(
+            "http://localhost/v1/pets",
+            r#"{"id":7,"name":"Rex"}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()[DEPRECATION], "@1780272000");
+    assert_eq!(response.headers()[SUNSET], "Tue, 01 Jun 2027 00:00:00 GMT");
+    let links = response
+        .headers()
+        .get_all(LINK)
+        .iter()
+        .map(|link| link.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        links,
+        [
+            r#"<https://example.com/v2>; rel="deprecation""#,
+            r#"<https://example.com/v2>; rel="sunset""#,
+        ]
+    );
+
+    let response = app()
+        .oneshot(create_pet(
+            "http://localhost/v2/pets",
+            r#"{"id":7,"name":"Rex","tags":["dog"]}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()[LOCATION], "/v2/pets/7");
+    assert!(!response.headers().contains_key(DEPRECATION));
+    assert!(!response.headers().contains_key(SUNSET));
+}
+
+#[tokio::test]
+async fn versions_have_their_own_models() {
+    // version 1 pets have a single tag
+    let response = app()
+        .oneshot(create_pet(
+            "http://localhost/v1/pets",
+            r#"{"id":7,"name":"Rex","tags":["dog"]}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let response = app()
+        .oneshot(create_pet(
+            "http://localhost/v2/pets",
+            r#"{"id":7,"name":"Rex","tags":["a","b","c","d","e","f","g","h","i","j","k"]}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app()
+        .oneshot(get("http://localhost/v2/pets/7"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let pet: v2::models::Pet = serde_json::from_slice(&body).unwrap();
+    assert_eq!(pet.tags, Some(vec!["dog".to_string(), "brown".to_string()]));
+}
+
+#[tokio::test]
+async fn versions_invalidate_each_others_cached_responses() {
+    let cache = ResponseCache::default();
+    let app = app_caching_in(cache.clone());
+
+    for uri in ["http://localhost/v1/pets", "http://localhost/v2/pets"] {
+        let response = app.clone().oneshot(get(uri)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+    }
+    assert_eq!(cache.len(), 2);
+
+    let response = app
+        .oneshot(create_pet(
+            "http://localhost/v2/pets",
+            r#"{"id":7,"name":"Rex","tags":["dog"]}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert!(cache.is_empty());
+}
+
+#[tokio::test]
+async fn versions_share_rate_limits() {
+    let app = app();
+
+    // createPets allows 10 requests a minute, to either version
+    for _ in 0..10 {
+        let response = app
+            .clone()
+            .oneshot(create_pet(
+                "http://localhost/v1/pets",
+                r#"{"id":7,"name":"Rex"}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+    let response = app
+        .oneshot(create_pet(
+            "http://localhost/v2/pets",
+            r#"{"id":7,"name":"Rex"}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[test]
+fn pets_convert_between_versions() {
+    let pet = models::Pet {
+        tag: Some(Nullable::Present("dog".to_string())),
+        ..models::Pet::new(7, "Rex".to_string())
+    };
+    let pet = v2::models::Pet::from(pet);
+    assert_eq!(pet.tags, Some(vec!["dog".to_string()]));
+
+    let pet = v2::models::Pet {
+        tags: Some(vec!["dog".to_string(), "brown".to_string()]),
+        ..pet
+    };
+    let pet = models::Pet::from(pet);
+    assert_eq!(pet.tag, Some(Nullable::Present("dog".to_string())));
+
+    let untagged = v2::models::Pet::from(models::Pet::new(7, "Rex".to_string()));
+    assert_eq!(untagged.tags, None);
+}
@@ -0,0 +1,449 @@
+//! Breaking changes between two revisions of an OpenAPI document: changes which make requests
+//! of existing clients fail, or responses they don't expect.
+//!
+//! Request schemas may only accept more than before, and response schemas only return less.
+//! Composed schemas (`allOf`, `oneOf`, `anyOf`) and `additionalProperties` are not compared.
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use crate::{spec::Operation, Finding, Spec};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Direction {
+    Request,
+    Response,
+}
+
+impl Direction {
+    /// Rule of changes of the values of a schema.
+    fn rule(self) -> &'static str {
+        match self {
+            Direction::Request => "narrowed-type",
+            Direction::Response => "widened-type",
+        }
+    }
+}
+
+/// Breaking changes from `old` to `new`.
+pub fn breaking_changes(old: &Spec, new: &Spec) -> Vec<Finding> {
+    let mut diff = Diff {
+        old,
+        new,
+        comparing: HashSet::new(),
+        findings: Vec::new(),
+    };
+    for old_operation in old.operations() {
+        match new.operation(old_operation.method, old_operation.path) {
+            Some(new_operation) => diff.operation(&old_operation, &new_operation),
+            None => diff.finding(
+                "removed-operation",
+                old_operation.name(),
+                "operation removed".to_string(),
+            ),
+        }
+    }
+    diff.findings
+}
+
+struct Diff<'a> {
+    old: &'a Spec,
+    new: &'a Spec,
+    /// Pairs of referenced schemas being compared, so recursive schemas end.
+    comparing: HashSet<(String, String, Direction)>,
+    findings: Vec<Finding>,
+}
+
+impl Diff<'_> {
+    fn finding(&mut self, rule: &'static str, location: String, message: String) {
+        self.findings.push(Finding {
+            rule,
+            location,
+            message,
+        });
+    }
+
+    fn operation(&mut self, old: &Operation, new: &Operation) {
+        let name = new.name();
+
+        let old_parameters = old.parameters(self.old);
+        let old_path_names = old.path_parameter_names();
+        let new_path_names = new.path_parameter_names();
+        for parameter in new.parameters(self.new) {
+            let location = format!(
+                "{} {} parameter {}",
+                name,
+                parameter["in"].as_str().unwrap_or_default(),
+                parameter["name"].as_str().unwrap_or_default()
+            );
+            // path parameters renamed with their template are matched by their position in it
+            let old_name = new_path_names
+                .iter()
+                .position(|name| parameter["in"] == "path" && parameter["name"] == *name)
+                .and_then(|i| old_path_names.get(i).copied())
+                .or(parameter["name"].as_str());
+            let before = old_parameters
+                .iter()
+                .find(|old| old["name"].as_str() == old_name && old["in"] == parameter["in"]);
+            // path parameters are always required, and may be renamed with their template
+            if parameter["in"] != "path" && is_true(&parameter["required"]) {
+                match before {
+                    None => self.finding(
+                        "new-required-parameter",
+                        location.clone(),
+                        "new required parameter".to_string(),
+                    ),
+                    Some(before) if !is_true(&before["required"]) => self.finding(
+                        "new-required-parameter",
+                        location.clone(),
+                        "parameter is now required".to_string(),
+                    ),
+                    Some(_) => {}
+                }
+            }
+            if let Some(before) = before {
+                self.schema(
+                    &location,
+                    "",
+                    &before["schema"],
+                    &parameter["schema"],
+                    Direction::Request,
+                );
+            }
+        }
+
+        let old_body = self.old.resolve(&old.operation["requestBody"]);
+        let new_body = self.new.resolve(&new.operation["requestBody"]);
+        let location = format!("{} request body", name);
+        if is_true(&new_body["required"]) && !is_true(&old_body["required"]) {
+            let message = if old_body.is_null() {
+                "new required request body"
+            } else {
+                "request body is now required"
+            };
+            self.finding(
+                "required-request-body",
+                location.clone(),
+                message.to_string(),
+            );
+        }
+        if !new_body.is_null() {
+            self.content(
+                &location,
+                &old_body["content"],
+                &new_body["content"],
+                Direction::Request,
+            );
+        }
+
+        for (status, old_response) in old.operation["responses"].as_object().into_iter().flatten() {
+            let location = format!("{} response {}", name, status);
+            let Some(new_response) = new.operation["responses"].get(status) else {
+                self.finding("removed-response", location, "response removed".to_string());
+                continue;
+            };
+            let old_response = self.old.resolve(old_response);
+            let new_response = self.new.resolve(new_response);
+
+            let new_headers = new_response["headers"].as_object();
+            for (header, old_header) in old_response["headers"].as_object().into_iter().flatten() {
+                let new_header = new_headers
+                    .into_iter()
+                    .flatten()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(header));
+                let location = format!("{} header {}", location, header);
+                match new_header {
+                    Some((_, new_header)) => {
+                        let old_header = self.old.resolve(old_header);
+                        let new_header = self.new.resolve(new_header);
+                        if is_true(&old_header["required"]) && !is_true(&new_header["required"]) {
+                            self.finding(
+                                "removed-response-header",
+                                location.clone(),
+                                "header is no longer required".to_string(),
+                            );
+                        }
+                        self.schema(
+                            &location,
+                            "",
+                            &old_header["schema"],
+                            &new_header["schema"],
+                            Direction::Response,
+                        );
+                    }
+                    None => self.finding(
+                        "removed-response-header",
+                        location,
+                        "header removed".to_string(),
+                    ),
+                }
+            }
+
+            self.content(
+                &location,
+                &old_response["content"],
+                &new_response["content"],
+                Direction::Response,
+            );
+        }
+    }
+
+    /// Compare the media types of a request body or response.
+    fn content(&mut self, location: &str, old: &Value, new: &Value, direction: Direction) {
+        for (media_type, old_media) in old.as_object().into_iter().flatten() {
+            match new.get(media_type) {
+                Some(new_media) => self.schema(
+                    &format!("{} {}", location, media_type),
+                    "",
+                    &old_media["schema"],
+                    &new_media["schema"],
+                    direction,
+                ),
+                None => self.finding(
+                    "removed-media-type",
+                    location.to_string(),
+                    format!("media type {} removed", media_type),
+                ),
+            }
+        }
+    }
+
+    /// Compare the schemas of the value at `path` of `location`.
+    fn schema(
+        &mut self,
+        location: &str,
+        path: &str,
+        old: &Value,
+        new: &Value,
+        direction: Direction,
+    ) {
+        let (Some(old_ref), Some(new_ref)) = (old["$ref"].as_str(), new["$ref"].as_str()) else {
+            return self.resolved_schema(location, path, old, new, direction);
+        };
+        let key = (old_ref.to_string(), new_ref.to_string(), direction);
+        if self.comparing.insert(key.clone()) {
+            self.resolved_schema(location, path, old, new, direction);
+            self.comparing.remove(&key);
+        }
+    }
+
+    fn resolved_schema(
+        &mut self,
+        location: &str,
+        path: &str,
+        old: &Value,
+        new: &Value,
+        direction: Direction,
+    ) {
+        let old = self.old.resolve(old);
+        let new = self.new.resolve(new);
+        // a missing schema is any value
+        if old.is_null() || new.is_null() {
+            return;
+        }
+        let at = if path.is_empty() {
+            location.to_string()
+        } else {
+            format!("{} {}", location, path)
+        };
+        let rule = direction.rule();
+
+        match (old["type"].as_str(), new["type"].as_str()) {
+            (Some(old_type), Some(new_type)) if old_type != new_type => {
+                let compatible = match direction {
+                    Direction::Request => old_type == "integer" && new_type == "number",
+                    Direction::Response => old_type == "number" && new_type == "integer",
+                };
+                if !compatible {
+                    self.finding(
+                        rule,
+                        at,
+                        format!("type changed from {} to {}", old_type, new_type),
+                    );
+                    return;
+                }
+            }
+            (None, Some(new_type)) if direction == Direction::Request => {
+                self.finding(rule, at.clone(), format!("type restricted to {}", new_type));
+            }
+            _ => {}
+        }
+
+        match (old["format"].as_str(), new["format"].as_str()) {
+            (Some(old_format), Some(new_format)) if old_format != new_format => {
+                let (narrow, wide) = match direction {
+                    Direction::Request => (old_format, new_format),
+                    Direction::Response => (new_format, old_format),
+                };
+                if !matches!((narrow, wide), ("int32", "int64") | ("float", "double")) {
+                    self.finding(
+                        rule,
+                        at.clone(),
+                        format!("format changed from {} to {}", old_format, new_format),
+                    );
+                }
+            }
+            (None, Some(new_format)) if direction == Direction::Request => {
+                self.finding(rule, at.clone(), format!("format {} added", new_format));
+            }
+            (Some(old_format), None) if direction == Direction::Response => {
+                self.finding(rule, at.clone(), format!("format {} removed", old_format));
+            }
+            _ => {}
+        }
+
+        // values a request may no longer have, or a response may now have
+        let (fewer, more) = match direction {
+            Direction::Request => (old, new),
+            Direction::Response => (new, old),
+        };
+        match (fewer["enum"].as_array(), more["enum"].as_array()) {
+            (None, Some(_)) if direction == Direction::Request => {
+                self.finding(rule, at.clone(), "values restricted to an enum".to_string());
+            }
+            (Some(_), None) if direction == Direction::Response => {
+                self.finding(rule, at.clone(), "enum removed".to_string());
+            }
+            (Some(fewer), Some(more)) => {
+                let verb = match direction {
+                    Direction::Request => "removed",
+                    Direction::Response => "added",
+                };
+                for value in fewer.iter().filter(|value| !more.contains(value)) {
+                    self.finding(rule, at.clone(), format!("enum value {} {}", value, verb));
+                }
+            }
+            _ => {}
+        }
+        if is_true(&fewer["nullable"]) && !is_true(&more["nullable"]) {
+            let message = match direction {
+                Direction::Request => "no longer nullable",
+                Direction::Response => "now nullable",
+            };
+            self.finding(rule, at.clone(), message.to_string());
+        }
+
+        if direction == Direction::Request {
+            for key in [
+                "maximum",
+                "exclusiveMaximum",
+                "maxLength",
+                "maxItems",
+                "maxProperties",
+            ] {
+                match (old[key].as_f64(), new[key].as_f64()) {
+                    (None, Some(_)) => {
+                        self.finding(rule, at.clone(), format!("{} {} added", key, new[key]))
+                    }
+                    (Some(old_bound), Some(new_bound)) if new_bound < old_bound => self.finding(
+                        rule,
+                        at.clone(),
+                        format!("{} lowered from {} to {}", key, old[key], new[key]),
+                    ),
+                    _ => {}
+                }
+            }
+            for key in [
+                "minimum",
+                "exclusiveMinimum",
+                "minLength",
+                "minItems",
+                "minProperties",
+            ] {
+                match (old[key].as_f64(), new[key].as_f64()) {
+                    (None, Some(_)) => {
+                        self.finding(rule, at.clone(), format!("{} {} added", key, new[key]))
+                    }
+                    (Some(old_bound), Some(new_bound)) if new_bound > old_bound => self.finding(
+                        rule,
+                        at.clone(),
+                        format!("{} raised from {} to {}", key, old[key], new[key]),
+                    ),
+                    _ => {}
+                }
+            }
+            if let Some(pattern) = new["pattern"].as_str() {
+                if old["pattern"].as_str() != Some(pattern) {
+                    self.finding(rule, at.clone(), format!("pattern {} added", pattern));
+                }
+            }
+        }
+
+        let old_properties = old["properties"].as_object();
+        let new_properties = new["properties"].as_object();
+        let old_required = names(&old["required"]);
+        let new_required = names(&new["required"]);
+        let property_path = |name: &str| format!("{}.{}", path, name);
+        match direction {
+            Direction::Request => {
+                for name in new_required
+                    .iter()
+                    .filter(|name| !old_required.contains(name))
+                {
+                    let message = if old_properties.is_some_and(|old| old.contains_key(*name)) {
+                        "property is now required"
+                    } else {
+                        "new required property"
+                    };
+                    self.finding(
+                        "new-required-property",
+                        format!("{} {}", location, property_path(name)),
+                        message.to_string(),
+                    );
+                }
+            }
+            Direction::Response => {
+                for name in old_properties.into_iter().flat_map(|old| old.keys()) {
+                    let message = if !new_properties.is_some_and(|new| new.contains_key(name)) {
+                        "property removed"
+                    } else if old_required.contains(&name.as_str())
+                        && !new_required.contains(&name.as_str())
+                    {
+                        "property is no longer required"
+                    } else {
+                        continue;
+                    };
+                    self.finding(
+                        "removed-property",
+                        format!("{} {}", location, property_path(name)),
+                        message.to_string(),
+                    );
+                }
+            }
+        }
+        for (name, old_property) in old_properties.into_iter().flatten() {
+            if let Some(new_property) = new_properties.and_then(|new| new.get(name)) {
+                self.schema(
+                    location,
+                    &property_path(name),
+                    old_property,
+                    new_property,
+                    direction,
+                );
+            }
+        }
+
+        self.schema(
+            location,
+            &format!("{}[]", path),
+            &old["items"],
+            &new["items"],
+            direction,
+        );
+    }
+}
+
+fn is_true(value: &Value) -> bool {
+    value.as_bool() == Some(true)
+}
+
+/// The strings of the array `value`, e.g. of `required`.
+fn names(value: &Value) -> Vec<&str> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect()
+}
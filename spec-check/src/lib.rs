@@ -0,0 +1,26 @@
+//! Checks of OpenAPI documents, for CI: the breaking changes between two revisions of a
+//! document ([`diff`]), and style rules of a single one ([`lint`]).
+
+use std::fmt;
+
+pub mod diff;
+pub mod lint;
+pub mod spec;
+
+pub use spec::Spec;
+
+/// A breaking change or a style rule violation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// Name of the rule, to allow it on the command line.
+    pub rule: &'static str,
+    /// Where in the document, e.g. `GET /pets response 200 application/json .name`.
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} [{}]", self.location, self.message, self.rule)
+    }
+}
@@ -0,0 +1,101 @@
+//! Style rules of an OpenAPI document, which the generated code relies on:
+//! - `operation-id`: every operation has an `operationId`, which names its `Api` method,
+//! - `operation-id-case`: `operationId`s are lowerCamelCase,
+//! - `operation-id-unique`: no two operations have the same `operationId`,
+//! - `path-parameter`: the parameters of path templates are declared as path parameters,
+//! - `default-response`: every operation has a `default` response for unexpected errors,
+//! - `error-schema`: `default` responses have a JSON schema, the same for all operations.
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::{Finding, Spec};
+
+/// Violations of the style rules in `spec`.
+pub fn lint(spec: &Spec) -> Vec<Finding> {
+    let camel_case = Regex::new("^[a-z][a-zA-Z0-9]*$").unwrap();
+    let path_parameter = Regex::new(r"\{([^}]+)\}").unwrap();
+
+    let mut findings = Vec::new();
+    let mut finding = |rule, location: String, message: String| {
+        findings.push(Finding {
+            rule,
+            location,
+            message,
+        })
+    };
+    let mut operation_ids: Vec<(&str, String)> = Vec::new();
+    let mut error_schema: Option<(&str, String)> = None;
+
+    for operation in spec.operations() {
+        let name = operation.name();
+
+        match operation.operation["operationId"].as_str() {
+            None => finding("operation-id", name.clone(), "no operationId".to_string()),
+            Some(id) => {
+                if !camel_case.is_match(id) {
+                    finding(
+                        "operation-id-case",
+                        name.clone(),
+                        format!("operationId {} is not lowerCamelCase", id),
+                    );
+                }
+                if let Some((_, first)) = operation_ids.iter().find(|(other, _)| *other == id) {
+                    finding(
+                        "operation-id-unique",
+                        name.clone(),
+                        format!("operationId {} is also the one of {}", id, first),
+                    );
+                } else {
+                    operation_ids.push((id, name.clone()));
+                }
+            }
+        }
+
+        let parameters = operation.parameters(spec);
+        for captures in path_parameter.captures_iter(operation.path) {
+            let parameter = &captures[1];
+            if !parameters
+                .iter()
+                .any(|declared| declared["in"] == "path" && declared["name"] == parameter)
+            {
+                finding(
+                    "path-parameter",
+                    name.clone(),
+                    format!("path parameter {} is not declared", parameter),
+                );
+            }
+        }
+
+        let Some(default) = operation.operation["responses"].get("default") else {
+            finding(
+                "default-response",
+                name.clone(),
+                "no default response".to_string(),
+            );
+            continue;
+        };
+        let schema = &spec.resolve(default)["content"]["application/json"]["schema"];
+        if schema.is_null() {
+            finding(
+                "error-schema",
+                format!("{} response default", name),
+                "no application/json schema".to_string(),
+            );
+        } else if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            match &error_schema {
+                None => error_schema = Some((reference, name)),
+                Some((first, first_operation)) if *first != reference => finding(
+                    "error-schema",
+                    format!("{} response default", name),
+                    format!(
+                        "schema {} differs from {} of {}",
+                        reference, first, first_operation
+                    ),
+                ),
+                Some(_) => {}
+            }
+        }
+    }
+    findings
+}
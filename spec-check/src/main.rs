@@ -0,0 +1,76 @@
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+use spec_check::{diff, lint, Finding, Spec};
+
+/// Checks of OpenAPI documents for CI.
+///
+/// Exits with 0 without findings, 1 with findings, and 2 if a document can't be read.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Don't report findings of the rule, e.g. `removed-response`.
+    #[arg(long, global = true, value_name = "RULE")]
+    allow: Vec<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Report the changes from OLD to NEW which break existing clients.
+    Diff {
+        old: String,
+        new: String,
+        /// Also check NEW against the style rules.
+        #[arg(long)]
+        lint: bool,
+    },
+    /// Check SPEC against the style rules.
+    Lint { spec: String },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let findings = match run(&cli.command) {
+        Ok(findings) => findings,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let findings = findings
+        .into_iter()
+        .filter(|finding| !cli.allow.iter().any(|rule| rule == finding.rule))
+        .collect::<Vec<_>>();
+    for finding in &findings {
+        println!("{}", finding);
+    }
+    if findings.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("{} finding(s)", findings.len());
+        ExitCode::from(1)
+    }
+}
+
+fn run(command: &Command) -> Result<Vec<Finding>, String> {
+    match command {
+        Command::Diff {
+            old,
+            new,
+            lint: with_lint,
+        } => {
+            let old = Spec::from_file(old)?;
+            let new = Spec::from_file(new)?;
+            let mut findings = diff::breaking_changes(&old, &new);
+            if *with_lint {
+                findings.extend(lint::lint(&new));
+            }
+            Ok(findings)
+        }
+        Command::Lint { spec } => Ok(lint::lint(&Spec::from_file(spec)?)),
+    }
+}
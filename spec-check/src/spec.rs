@@ -0,0 +1,151 @@
+use std::fs;
+
+use serde_json::Value;
+
+/// Keys of the operations of a path item and their methods, in the order they are reported.
+const METHODS: [(&str, &str); 8] = [
+    ("get", "GET"),
+    ("put", "PUT"),
+    ("post", "POST"),
+    ("delete", "DELETE"),
+    ("options", "OPTIONS"),
+    ("head", "HEAD"),
+    ("patch", "PATCH"),
+    ("trace", "TRACE"),
+];
+
+/// An OpenAPI document.
+#[derive(Debug, Clone)]
+pub struct Spec {
+    doc: Value,
+}
+
+/// An operation of a [`Spec`].
+#[derive(Debug, Clone, Copy)]
+pub struct Operation<'a> {
+    /// Upper case HTTP method.
+    pub method: &'static str,
+    pub path: &'a str,
+    pub operation: &'a Value,
+    /// Parameters of the path item, which apply to all its operations.
+    path_parameters: &'a Value,
+}
+
+impl Spec {
+    /// Parse an OpenAPI document in YAML (or JSON) form.
+    pub fn from_yaml(spec: &str) -> Result<Self, String> {
+        let doc: Value = serde_yaml::from_str(spec).map_err(|e| e.to_string())?;
+        if !doc.get("paths").is_some_and(Value::is_object) {
+            return Err("not an OpenAPI document: `paths` is missing".to_string());
+        }
+        Ok(Spec { doc })
+    }
+
+    /// Read and parse the OpenAPI document at `path`.
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let spec = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        Self::from_yaml(&spec).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// Operations of all paths.
+    pub fn operations(&self) -> Vec<Operation<'_>> {
+        let Some(paths) = self.doc["paths"].as_object() else {
+            return Vec::new();
+        };
+        let mut operations = Vec::new();
+        for (path, item) in paths {
+            let item = self.resolve(item);
+            for (key, method) in METHODS {
+                if let Some(operation) = item.get(key) {
+                    operations.push(Operation {
+                        method,
+                        path,
+                        operation,
+                        path_parameters: &item["parameters"],
+                    });
+                }
+            }
+        }
+        operations
+    }
+
+    /// The operation `method` (upper case) of `path`, which matches paths with other names of
+    /// path parameters, e.g. `/pets/{id}` for `/pets/{petId}`.
+    pub fn operation(&self, method: &str, path: &str) -> Option<Operation<'_>> {
+        self.operations().into_iter().find(|operation| {
+            operation.method == method && template(operation.path) == template(path)
+        })
+    }
+
+    /// The value `value` refers to with a local `$ref`, following chains of references, or
+    /// `value` itself. Unresolvable references give `null`.
+    pub fn resolve<'a>(&'a self, mut value: &'a Value) -> &'a Value {
+        // bounded, so reference cycles end
+        for _ in 0..32 {
+            let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+                return value;
+            };
+            value = reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.doc.pointer(pointer))
+                .unwrap_or(&Value::Null);
+        }
+        &Value::Null
+    }
+}
+
+impl<'a> Operation<'a> {
+    /// `METHOD /path`, to locate findings.
+    pub fn name(&self) -> String {
+        format!("{} {}", self.method, self.path)
+    }
+
+    /// Names of the parameters in the template of the path, in order.
+    pub fn path_parameter_names(&self) -> Vec<&'a str> {
+        self.path
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}'))
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Parameters of the operation and of its path item, resolved, the operation's overriding
+    /// those of the path item with the same name and location.
+    pub fn parameters(&self, spec: &'a Spec) -> Vec<&'a Value> {
+        let own = self.operation["parameters"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|parameter| spec.resolve(parameter))
+            .collect::<Vec<_>>();
+        let inherited = self
+            .path_parameters
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|parameter| spec.resolve(parameter))
+            .filter(|parameter| {
+                !own.iter()
+                    .any(|own| own["name"] == parameter["name"] && own["in"] == parameter["in"])
+            })
+            .collect::<Vec<_>>();
+        inherited.into_iter().chain(own).collect()
+    }
+}
+
+/// `path` without the names of its path parameters.
+fn template(path: &str) -> String {
+    let mut template = String::with_capacity(path.len());
+    let mut in_parameter = false;
+    for c in path.chars() {
+        match c {
+            '{' => in_parameter = true,
+            '}' => in_parameter = false,
+            _ if in_parameter => continue,
+            _ => {}
+        }
+        template.push(c);
+    }
+    template
+}
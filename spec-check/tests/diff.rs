@@ -0,0 +1,178 @@
+//! Breaking changes between revisions of a document.
+
+use spec_check::{diff::breaking_changes, Spec};
+
+const OLD: &str = r#"
+openapi: 3.0.0
+info: { title: Pets, version: 1.0.0 }
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      parameters:
+        - { name: limit, in: query, schema: { type: integer, maximum: 100 } }
+      responses:
+        '200':
+          description: Pets
+          headers:
+            x-next: { schema: { type: string } }
+          content:
+            application/json:
+              schema: { type: array, items: { $ref: '#/components/schemas/Pet' } }
+        default:
+          description: Error
+    post:
+      operationId: createPets
+      requestBody:
+        content:
+          application/json:
+            schema: { $ref: '#/components/schemas/Pet' }
+      responses:
+        '201': { description: Created }
+  /pets/{petId}:
+    delete:
+      operationId: deletePet
+      parameters:
+        - { name: petId, in: path, required: true, schema: { type: integer } }
+      responses:
+        '204': { description: Deleted }
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [id, name]
+      properties:
+        id: { type: integer, format: int64 }
+        name: { type: string, maxLength: 100 }
+        kind: { type: string, enum: [cat, dog] }
+        parent: { $ref: '#/components/schemas/Pet' }
+"#;
+
+fn changes(new: &str) -> Vec<String> {
+    let old = Spec::from_yaml(OLD).unwrap();
+    let new = Spec::from_yaml(new).unwrap();
+    breaking_changes(&old, &new)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn unchanged_documents_have_no_breaking_changes() {
+    assert_eq!(changes(OLD), Vec::<String>::new());
+
+    // path parameters may be renamed, and requests may accept more
+    let new = OLD
+        .replace("/pets/{petId}:", "/pets/{id}:")
+        .replace("name: petId", "name: id")
+        .replace("maximum: 100", "maximum: 1000")
+        .replace("enum: [cat, dog]", "enum: [cat, dog, bird]")
+        .replace("maxLength: 100", "maxLength: 200");
+    let new = new.replace(
+        "      responses:\n        '201'",
+        "      responses:\n        '409': { description: Conflict }\n        '201'",
+    );
+    // enum values added to requests are also added to responses
+    assert_eq!(
+        changes(&new),
+        [
+            "GET /pets response 200 application/json [].kind: enum value \"bird\" added [widened-type]",
+        ]
+    );
+}
+
+#[test]
+fn removed_operations_and_responses_break_clients() {
+    let new = OLD.replace("    delete:", "    patch:").replace(
+        "        '201': { description: Created }",
+        "        '200': { description: Ok }",
+    );
+    assert_eq!(
+        changes(&new),
+        [
+            "POST /pets response 201: response removed [removed-response]",
+            "DELETE /pets/{petId}: operation removed [removed-operation]",
+        ]
+    );
+}
+
+#[test]
+fn new_required_inputs_break_clients() {
+    let new = OLD
+        .replace(
+            "{ name: limit, in: query, schema",
+            "{ name: limit, in: query, required: true, schema",
+        )
+        .replace(
+            "      requestBody:\n",
+            "      requestBody:\n        required: true\n",
+        )
+        .replace("required: [id, name]", "required: [id, name, kind]");
+    assert_eq!(
+        changes(&new),
+        [
+            "GET /pets query parameter limit: parameter is now required [new-required-parameter]",
+            "POST /pets request body: request body is now required [required-request-body]",
+            "POST /pets request body application/json .kind: property is now required [new-required-property]",
+        ]
+    );
+}
+
+#[test]
+fn narrowed_requests_and_widened_responses_break_clients() {
+    let new = OLD
+        .replace("maximum: 100", "maximum: 10")
+        .replace("enum: [cat, dog]", "enum: [cat]")
+        .replace("format: int64", "format: int32")
+        .replace("required: [id, name]", "required: [id]")
+        .replace(
+            "          headers:\n            x-next: { schema: { type: string } }\n",
+            "",
+        );
+    let changes = changes(&new);
+    for change in [
+        "GET /pets query parameter limit: maximum lowered from 100 to 10 [narrowed-type]",
+        "GET /pets response 200 header x-next: header removed [removed-response-header]",
+        "GET /pets response 200 application/json [].name: property is no longer required [removed-property]",
+        "POST /pets request body application/json .id: format changed from int64 to int32 [narrowed-type]",
+        "POST /pets request body application/json .kind: enum value \"dog\" removed [narrowed-type]",
+    ] {
+        assert!(changes.iter().any(|c| c == change), "{}\nin {:#?}", change, changes);
+    }
+    // int32 ids still fit clients expecting int64, and fewer kinds are fine
+    assert!(!changes
+        .iter()
+        .any(|c| c.starts_with("GET /pets response 200 application/json [].id")));
+    assert!(!changes
+        .iter()
+        .any(|c| c.contains("GET") && c.contains("enum")));
+}
+
+#[test]
+fn changed_types_break_clients() {
+    let new = OLD.replace(
+        "id: { type: integer, format: int64 }",
+        "id: { type: string }",
+    );
+    assert_eq!(
+        changes(&new),
+        [
+            "GET /pets response 200 application/json [].id: type changed from integer to string [widened-type]",
+            "POST /pets request body application/json .id: type changed from integer to string [narrowed-type]",
+        ]
+    );
+}
+
+#[test]
+fn renamed_path_parameters_are_compared() {
+    let new = OLD.replace("/pets/{petId}:", "/pets/{id}:").replace(
+        "name: petId, in: path, required: true, schema: { type: integer }",
+        "name: id, in: path, required: true, schema: { type: string, maxLength: 10 }",
+    );
+    assert_eq!(
+        changes(&new),
+        [
+            "DELETE /pets/{id} path parameter id: type changed from integer to string [narrowed-type]",
+        ]
+    );
+}
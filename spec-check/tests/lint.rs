@@ -0,0 +1,106 @@
+//! Style rules, and the exit codes of the command line.
+
+use std::{fs, process::Command};
+
+use spec_check::{lint::lint, Spec};
+
+const SPEC: &str = r#"
+openapi: 3.0.0
+info: { title: Pets, version: 1.0.0 }
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        '200': { description: Pets }
+        default:
+          description: Error
+          content:
+            application/json:
+              schema: { $ref: '#/components/schemas/Error' }
+    post:
+      operationId: ListPets
+      responses:
+        '201': { description: Created }
+  /pets/{petId}:
+    get:
+      responses:
+        default:
+          description: Error
+          content:
+            application/json:
+              schema: { $ref: '#/components/schemas/Problem' }
+components:
+  schemas:
+    Error: { type: object }
+    Problem: { type: object }
+"#;
+
+#[test]
+fn style_rules_are_checked() {
+    let findings = lint(&Spec::from_yaml(SPEC).unwrap())
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        findings,
+        [
+            "POST /pets: operationId ListPets is not lowerCamelCase [operation-id-case]",
+            "POST /pets: no default response [default-response]",
+            "GET /pets/{petId}: no operationId [operation-id]",
+            "GET /pets/{petId}: path parameter petId is not declared [path-parameter]",
+            "GET /pets/{petId} response default: schema #/components/schemas/Problem differs from #/components/schemas/Error of GET /pets [error-schema]",
+        ]
+    );
+}
+
+#[test]
+fn petstore_follows_the_style_rules() {
+    let spec = Spec::from_yaml(include_str!("../../petstore.yaml")).unwrap();
+    assert_eq!(lint(&spec), []);
+}
+
+#[test]
+fn exit_codes_tell_findings_from_errors() {
+    let dir = std::env::temp_dir().join(format!("spec-check-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let spec = dir.join("spec.yaml");
+    fs::write(&spec, SPEC).unwrap();
+    let petstore = concat!(env!("CARGO_MANIFEST_DIR"), "/../petstore.yaml");
+
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_spec-check"))
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let output = run(&["diff", petstore, petstore, "--lint"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+
+    let output = run(&["lint", spec.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 5);
+
+    let allowed = [
+        "lint",
+        spec.to_str().unwrap(),
+        "--allow",
+        "operation-id",
+        "--allow",
+        "operation-id-case",
+        "--allow",
+        "default-response",
+        "--allow",
+        "path-parameter",
+        "--allow",
+        "error-schema",
+    ];
+    assert_eq!(run(&allowed).status.code(), Some(0));
+
+    let output = run(&["diff", petstore, dir.join("missing.yaml").to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+
+    fs::remove_dir_all(dir).unwrap();
+}